@@ -12,7 +12,9 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 #[cfg(feature = "audio")]
-use notan_audio::AudioBackend;
+use notan_audio::{
+    AudioBackend, AudioEffect, AudioGeneratorFactory, AudioListener, AudioSourceKind,
+};
 
 #[derive(Default)]
 pub struct EmptyWindowBackend {
@@ -308,6 +310,8 @@ impl DeviceBackend for EmptyDeviceBackend {
 pub struct EmptyAudioBackend {
     id_count: u64,
     volume: f32,
+    effects: Vec<AudioEffect>,
+    listener: AudioListener,
 }
 
 #[cfg(feature = "audio")]
@@ -320,13 +324,39 @@ impl AudioBackend for EmptyAudioBackend {
         self.volume
     }
 
-    fn create_source(&mut self, _bytes: &[u8]) -> Result<u64, String> {
+    fn create_source(&mut self, _bytes: &[u8], _kind: AudioSourceKind) -> Result<u64, String> {
+        let id = self.id_count;
+        self.id_count += 1;
+        Ok(id)
+    }
+
+    fn create_source_from_pcm(
+        &mut self,
+        _samples: &[f32],
+        _channels: u16,
+        _sample_rate: u32,
+    ) -> Result<u64, String> {
+        let id = self.id_count;
+        self.id_count += 1;
+        Ok(id)
+    }
+
+    fn create_source_from_generator(
+        &mut self,
+        _factory: AudioGeneratorFactory,
+    ) -> Result<u64, String> {
         let id = self.id_count;
         self.id_count += 1;
         Ok(id)
     }
 
-    fn play_sound(&mut self, _source: u64, _volume: f32, _repeat: bool) -> Result<u64, String> {
+    fn play_sound(
+        &mut self,
+        _source: u64,
+        _volume: f32,
+        _repeat: bool,
+        _bus: Option<u64>,
+    ) -> Result<u64, String> {
         let id = self.id_count;
         self.id_count += 1;
         Ok(id)
@@ -352,5 +382,27 @@ impl AudioBackend for EmptyAudioBackend {
         0.0
     }
 
-    fn clean(&mut self, _sources: &[u64], _sounds: &[u64]) {}
+    fn set_global_effects(&mut self, effects: &[AudioEffect]) {
+        self.effects = effects.to_vec();
+    }
+
+    fn global_effects(&self) -> Vec<AudioEffect> {
+        self.effects.clone()
+    }
+
+    fn set_listener(&mut self, listener: AudioListener) {
+        self.listener = listener;
+    }
+
+    fn listener(&self) -> AudioListener {
+        self.listener
+    }
+
+    fn create_bus(&mut self) -> Result<u64, String> {
+        let id = self.id_count;
+        self.id_count += 1;
+        Ok(id)
+    }
+
+    fn clean(&mut self, _sources: &[u64], _sounds: &[u64], _buses: &[u64]) {}
 }
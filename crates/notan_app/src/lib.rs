@@ -23,3 +23,6 @@ pub use plugins::*;
 pub use graphics::*;
 
 pub use config::WindowConfig;
+
+#[cfg(feature = "audio")]
+pub use parsers::create_streaming_audio_parser;
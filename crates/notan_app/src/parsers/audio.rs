@@ -8,8 +8,22 @@ pub fn create_audio_parser() -> AssetLoader {
         .extensions(&["mp3", "ogg", "wav", "flac"])
 }
 
+/// Loader that creates streaming sources, decoded while they play
+/// It can replace the default audio loader using `add_loader`
+pub fn create_streaming_audio_parser() -> AssetLoader {
+    AssetLoader::new()
+        .use_parser(parse_streaming_audio)
+        .extensions(&["mp3", "ogg", "wav", "flac"])
+}
+
 fn parse_audio(id: &str, data: Vec<u8>, app: &mut App) -> Result<AudioSource, String> {
     let source = app.audio.create_source(&data)?;
     log::debug!("Asset '{}' parsed as AudioSource", id);
     Ok(source)
 }
+
+fn parse_streaming_audio(id: &str, data: Vec<u8>, app: &mut App) -> Result<AudioSource, String> {
+    let source = app.audio.create_streaming_source(&data)?;
+    log::debug!("Asset '{}' parsed as streaming AudioSource", id);
+    Ok(source)
+}
@@ -1,12 +1,50 @@
+use crate::effect::AudioEffect;
+use crate::generator::AudioGeneratorFactory;
+use crate::spatial::{AudioListener, SpatialOptions};
 use crate::tracker::{ResourceId, ResourceTracker};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+/// How the audio data of a source is decoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AudioSourceKind {
+    /// Decoded at once when the source is created, best for short sounds
+    #[default]
+    Static,
+    /// Decoded incrementally on a background thread while it plays, best for long music tracks
+    Streaming,
+}
+
+/// Part of a sound that repeats after playing once what's before it, like an intro
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LoopRegion {
+    /// Position in seconds where the loop starts
+    pub start: f32,
+    /// Position in seconds where the loop ends, by default the end of the sound
+    pub end: Option<f32>,
+}
+
+impl LoopRegion {
+    pub fn new(start: f32, end: Option<f32>) -> Self {
+        Self { start, end }
+    }
+}
+
 /// Represent the audio implementation backend
+///
+/// Only the basic playback methods are required, the rest have default
+/// implementations that do nothing so backends can support them gradually
 pub trait AudioBackend {
     fn set_global_volume(&mut self, volume: f32);
     fn global_volume(&self) -> f32;
-    fn create_source(&mut self, bytes: &[u8]) -> Result<u64, String>;
-    fn play_sound(&mut self, source: u64, volume: f32, repeat: bool) -> Result<u64, String>;
+    fn create_source(&mut self, bytes: &[u8], kind: AudioSourceKind) -> Result<u64, String>;
+    fn play_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+    ) -> Result<u64, String>;
     fn pause(&mut self, sound: u64);
     fn resume(&mut self, sound: u64);
     fn stop(&mut self, sound: u64);
@@ -16,8 +54,150 @@ pub trait AudioBackend {
     fn is_paused(&mut self, sound: u64) -> bool;
     fn set_volume(&mut self, sound: u64, volume: f32);
     fn volume(&self, sound: u64) -> f32;
-    fn clean(&mut self, sources: &[u64], sounds: &[u64]);
-    // fn remaining_time(&self, sound: u64) -> f32;
+    fn clean(&mut self, sources: &[u64], sounds: &[u64], buses: &[u64]);
+
+    fn create_source_from_pcm(
+        &mut self,
+        _samples: &[f32],
+        _channels: u16,
+        _sample_rate: u32,
+    ) -> Result<u64, String> {
+        Err("PCM sources are not supported by this backend".to_string())
+    }
+
+    fn create_source_from_generator(
+        &mut self,
+        _factory: AudioGeneratorFactory,
+    ) -> Result<u64, String> {
+        Err("Generated sources are not supported by this backend".to_string())
+    }
+
+    /// Plays the sound without the spatial options by default
+    fn play_spatial_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+        _options: SpatialOptions,
+    ) -> Result<u64, String> {
+        self.play_sound(source, volume, repeat, bus)
+    }
+
+    fn set_pan(&mut self, _sound: u64, _pan: f32) {}
+
+    fn pan(&self, _sound: u64) -> f32 {
+        0.0
+    }
+
+    fn set_spatial_options(&mut self, _sound: u64, _options: SpatialOptions) {}
+
+    fn spatial_options(&self, _sound: u64) -> Option<SpatialOptions> {
+        None
+    }
+
+    fn set_listener(&mut self, _listener: AudioListener) {}
+
+    fn listener(&self) -> AudioListener {
+        AudioListener::default()
+    }
+
+    /// Sets the target volume at once by default
+    fn fade(&mut self, sound: u64, _from: Option<f32>, to: f32, _duration: f32, stop: bool) {
+        if stop {
+            self.stop(sound);
+        } else {
+            self.set_volume(sound, to);
+        }
+    }
+
+    fn set_effects(&mut self, _sound: u64, _effects: &[AudioEffect]) {}
+
+    fn effects(&self, _sound: u64) -> Vec<AudioEffect> {
+        vec![]
+    }
+
+    fn set_global_effects(&mut self, _effects: &[AudioEffect]) {}
+
+    fn global_effects(&self) -> Vec<AudioEffect> {
+        vec![]
+    }
+
+    fn duration(&self, _sound: u64) -> Option<f32> {
+        None
+    }
+
+    fn position(&self, _sound: u64) -> f32 {
+        0.0
+    }
+
+    fn seek(&mut self, _sound: u64, _position: f32) {}
+
+    fn set_playback_rate(&mut self, _sound: u64, _rate: f32) {}
+
+    fn playback_rate(&self, _sound: u64) -> f32 {
+        1.0
+    }
+
+    fn set_loop_region(&mut self, _sound: u64, _region: Option<LoopRegion>) {}
+
+    fn loop_region(&self, _sound: u64) -> Option<LoopRegion> {
+        None
+    }
+
+    fn create_bus(&mut self) -> Result<u64, String> {
+        Err("Audio buses are not supported by this backend".to_string())
+    }
+
+    fn set_bus_volume(&mut self, _bus: u64, _volume: f32) {}
+
+    fn bus_volume(&self, _bus: u64) -> f32 {
+        1.0
+    }
+
+    fn set_bus_muted(&mut self, _bus: u64, _muted: bool) {}
+
+    fn is_bus_muted(&self, _bus: u64) -> bool {
+        false
+    }
+
+    fn pause_bus(&mut self, _bus: u64) {}
+
+    fn resume_bus(&mut self, _bus: u64) {}
+
+    fn is_bus_paused(&self, _bus: u64) -> bool {
+        false
+    }
+
+    fn set_bus_effects(&mut self, _bus: u64, _effects: &[AudioEffect]) {}
+
+    fn bus_effects(&self, _bus: u64) -> Vec<AudioEffect> {
+        vec![]
+    }
+
+    fn input_devices(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn start_capture(&mut self, _device: Option<&str>) -> Result<(), String> {
+        Err("Audio capture is not supported by this backend".to_string())
+    }
+
+    fn stop_capture(&mut self) {}
+
+    fn is_capturing(&self) -> bool {
+        false
+    }
+
+    fn capture_sample_rate(&self) -> Option<u32> {
+        None
+    }
+
+    fn read_capture(&mut self, _samples: &mut Vec<f32>) {}
+
+    fn capture_level(&self) -> f32 {
+        0.0
+    }
 }
 
 #[derive(Debug)]
@@ -63,17 +243,33 @@ impl Drop for SoundIdRef {
     }
 }
 
+// ids of the sounds that were never played, they count down from the top of
+// the range so they don't match each other or the ids used by the backends
+static STOPPED_SOUND_ID: AtomicU64 = AtomicU64::new(u64::MAX);
+
 #[derive(Debug, Clone)]
 pub struct Sound {
     pub(crate) id: u64,
-    _id_ref: Arc<SoundIdRef>,
+    _id_ref: Option<Arc<SoundIdRef>>,
 }
 
 impl Sound {
     pub(crate) fn new(id: u64, tracker: Arc<ResourceTracker>) -> Self {
-        let _id_ref = Arc::new(SoundIdRef { id, tracker });
+        let _id_ref = Some(Arc::new(SoundIdRef { id, tracker }));
         Self { id, _id_ref }
     }
+
+    /// Sound rejected without reaching the backend, it's always stopped
+    pub(crate) fn stopped() -> Self {
+        Self {
+            id: STOPPED_SOUND_ID.fetch_sub(1, Ordering::Relaxed),
+            _id_ref: None,
+        }
+    }
+
+    pub(crate) fn is_played(&self) -> bool {
+        self._id_ref.is_some()
+    }
 }
 
 impl PartialEq for Sound {
@@ -81,3 +277,45 @@ impl PartialEq for Sound {
         self.id == other.id
     }
 }
+
+#[derive(Debug)]
+struct BusIdRef {
+    id: u64,
+    tracker: Arc<ResourceTracker>,
+}
+
+impl Drop for BusIdRef {
+    fn drop(&mut self) {
+        self.tracker.push(ResourceId::Bus(self.id));
+    }
+}
+
+/// Group of sounds mixed together with its own volume, mute and pause state
+/// The bus and its sounds are removed when it's dropped
+#[derive(Debug, Clone)]
+pub struct AudioBus {
+    pub(crate) id: u64,
+    name: Arc<str>,
+    _id_ref: Arc<BusIdRef>,
+}
+
+impl AudioBus {
+    pub(crate) fn new(id: u64, name: &str, tracker: Arc<ResourceTracker>) -> Self {
+        let _id_ref = Arc::new(BusIdRef { id, tracker });
+        Self {
+            id,
+            name: name.into(),
+            _id_ref,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl PartialEq for AudioBus {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
@@ -0,0 +1,76 @@
+/// Built-in effect applied to a sound or to the global mix
+/// The `mix` of the effects blends the original sound (0.0) with the processed one (1.0)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AudioEffect {
+    /// Removes the frequencies above the cutoff in Hz, like a muffled or underwater sound
+    LowPass { cutoff: f32, q: f32 },
+    /// Removes the frequencies below the cutoff in Hz, like a radio or a phone
+    HighPass { cutoff: f32, q: f32 },
+    /// Echoes the sound after `time` seconds (max 2.0), `feedback` is the level of each echo
+    Delay { time: f32, feedback: f32, mix: f32 },
+    /// Room ambience, `room_size` and `damping` go from 0.0 to 1.0
+    Reverb {
+        room_size: f32,
+        damping: f32,
+        mix: f32,
+    },
+    /// Reduces the level above the threshold in decibels by the ratio,
+    /// `attack` and `release` are the seconds it takes to react
+    Compressor {
+        threshold: f32,
+        ratio: f32,
+        attack: f32,
+        release: f32,
+    },
+}
+
+impl AudioEffect {
+    pub fn low_pass(cutoff: f32) -> Self {
+        Self::LowPass {
+            cutoff,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    pub fn high_pass(cutoff: f32) -> Self {
+        Self::HighPass {
+            cutoff,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    pub fn delay(time: f32, feedback: f32) -> Self {
+        Self::Delay {
+            time,
+            feedback,
+            mix: 0.5,
+        }
+    }
+
+    pub fn reverb(room_size: f32) -> Self {
+        Self::Reverb {
+            room_size,
+            damping: 0.5,
+            mix: 0.3,
+        }
+    }
+
+    pub fn compressor(threshold: f32, ratio: f32) -> Self {
+        Self::Compressor {
+            threshold,
+            ratio,
+            attack: 0.01,
+            release: 0.1,
+        }
+    }
+
+    /// Compressor that doesn't let the level go above the threshold in decibels
+    pub fn limiter(threshold: f32) -> Self {
+        Self::Compressor {
+            threshold,
+            ratio: f32::INFINITY,
+            attack: 0.001,
+            release: 0.05,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+/// Produces stereo frames on the audio thread, like synthesizers or tone generators
+/// It runs on the audio thread so it should not wait, allocate or lock
+pub trait AudioGenerator: Send {
+    /// Fills the frames at the sample rate given, returns false once it ends
+    fn generate(&mut self, sample_rate: u32, frames: &mut [[f32; 2]]) -> bool;
+}
+
+impl<F> AudioGenerator for F
+where
+    F: FnMut(u32, &mut [[f32; 2]]) -> bool + Send,
+{
+    fn generate(&mut self, sample_rate: u32, frames: &mut [[f32; 2]]) -> bool {
+        self(sample_rate, frames)
+    }
+}
+
+/// Creates a new generator each time a generated source is played
+pub type AudioGeneratorFactory = Arc<dyn Fn() -> Box<dyn AudioGenerator> + Send + Sync>;
@@ -1,7 +1,15 @@
 mod backend;
+mod effect;
+mod generator;
 mod manager;
 pub mod prelude;
+mod spatial;
 mod tracker;
+mod voices;
 
 pub use backend::*;
+pub use effect::*;
+pub use generator::*;
 pub use manager::Audio;
+pub use spatial::*;
+pub use voices::{SourceVoices, VoiceStealing};
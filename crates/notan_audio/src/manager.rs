@@ -1,12 +1,17 @@
-use crate::backend::{AudioBackend, AudioSource, Sound};
+use crate::backend::{AudioBackend, AudioBus, AudioSource, AudioSourceKind, LoopRegion, Sound};
+use crate::effect::AudioEffect;
+use crate::generator::AudioGenerator;
+use crate::spatial::{AudioListener, SpatialOptions};
 use crate::tracker::{ResourceId, ResourceTracker};
-use std::cell::RefCell;
+use crate::voices::{SourceVoices, VoicePool, VoiceStealing};
+use std::cell::{RefCell, RefMut};
 use std::rc::Rc;
 use std::sync::Arc;
 
 pub struct Audio {
     backend: Rc<RefCell<dyn AudioBackend>>,
     resource_tracker: Arc<ResourceTracker>,
+    voices: VoicePool,
 }
 
 impl Audio {
@@ -15,49 +20,297 @@ impl Audio {
         Ok(Self {
             backend,
             resource_tracker,
+            voices: VoicePool::default(),
         })
     }
 
     #[inline]
     pub fn create_source(&mut self, bytes: &[u8]) -> Result<AudioSource, String> {
-        let id = self.backend.borrow_mut().create_source(bytes)?;
+        self.create_source_with_kind(bytes, AudioSourceKind::Static)
+    }
+
+    /// Creates a source that is decoded while it plays instead of at once
+    #[inline]
+    pub fn create_streaming_source(&mut self, bytes: &[u8]) -> Result<AudioSource, String> {
+        self.create_source_with_kind(bytes, AudioSourceKind::Streaming)
+    }
+
+    #[inline]
+    pub fn create_source_with_kind(
+        &mut self,
+        bytes: &[u8],
+        kind: AudioSourceKind,
+    ) -> Result<AudioSource, String> {
+        let id = self.backend.borrow_mut().create_source(bytes, kind)?;
         Ok(AudioSource::new(id, self.resource_tracker.clone()))
     }
 
+    /// Creates a source from interleaved samples with one or two channels
     #[inline]
-    pub fn play_sound(&mut self, source: &AudioSource, volume: f32, repeat: bool) -> Sound {
-        let volume = clamp_volume(volume);
+    pub fn create_source_from_pcm(
+        &mut self,
+        samples: &[f32],
+        channels: u16,
+        sample_rate: u32,
+    ) -> Result<AudioSource, String> {
+        let id =
+            self.backend
+                .borrow_mut()
+                .create_source_from_pcm(samples, channels, sample_rate)?;
+        Ok(AudioSource::new(id, self.resource_tracker.clone()))
+    }
+
+    /// Creates a source that plays a copy of the generator each time it's played
+    pub fn create_source_from_generator<G>(&mut self, generator: G) -> Result<AudioSource, String>
+    where
+        G: AudioGenerator + Clone + Sync + 'static,
+    {
+        let factory = Arc::new(move || Box::new(generator.clone()) as Box<dyn AudioGenerator>);
         let id = self
             .backend
             .borrow_mut()
-            .play_sound(source.id, volume, repeat)
-            .unwrap();
-        Sound::new(id, self.resource_tracker.clone())
+            .create_source_from_generator(factory)?;
+        Ok(AudioSource::new(id, self.resource_tracker.clone()))
+    }
+
+    #[inline]
+    pub fn play_sound(&mut self, source: &AudioSource, volume: f32, repeat: bool) -> Sound {
+        self.play(source, volume, repeat, None)
+    }
+
+    /// Plays the sound mixed in the bus given
+    #[inline]
+    pub fn play_sound_on_bus(
+        &mut self,
+        source: &AudioSource,
+        bus: &AudioBus,
+        volume: f32,
+        repeat: bool,
+    ) -> Sound {
+        self.play(source, volume, repeat, Some(bus.id))
+    }
+
+    /// Plays the sound at a position around the listener
+    #[inline]
+    pub fn play_spatial_sound(
+        &mut self,
+        source: &AudioSource,
+        options: SpatialOptions,
+        volume: f32,
+        repeat: bool,
+    ) -> Sound {
+        self.play_spatial(source, volume, repeat, None, options)
+    }
+
+    /// Plays the sound at a position around the listener mixed in the bus given
+    #[inline]
+    pub fn play_spatial_sound_on_bus(
+        &mut self,
+        source: &AudioSource,
+        bus: &AudioBus,
+        options: SpatialOptions,
+        volume: f32,
+        repeat: bool,
+    ) -> Sound {
+        self.play_spatial(source, volume, repeat, Some(bus.id), options)
+    }
+
+    fn play(&mut self, source: &AudioSource, volume: f32, repeat: bool, bus: Option<u64>) -> Sound {
+        let volume = clamp_volume(volume);
+        self.play_voice(source, |backend| {
+            backend.play_sound(source.id, volume, repeat, bus)
+        })
+    }
+
+    fn play_spatial(
+        &mut self,
+        source: &AudioSource,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+        options: SpatialOptions,
+    ) -> Sound {
+        let volume = clamp_volume(volume);
+        self.play_voice(source, |backend| {
+            backend.play_spatial_sound(source.id, volume, repeat, bus, options)
+        })
+    }
+
+    // plays the sound if there is a voice for it, otherwise or if the backend fails
+    // a stopped sound is returned
+    fn play_voice(
+        &mut self,
+        source: &AudioSource,
+        play: impl FnOnce(&mut dyn AudioBackend) -> Result<u64, String>,
+    ) -> Sound {
+        if let Some(sound) = self.voices.cooldown(source.id) {
+            return sound;
+        }
+
+        let mut backend = self.backend.borrow_mut();
+        self.voices.retain(|id| !backend.is_stopped(id));
+        let Some(stolen) = self.voices.reserve(source.id, |id| backend.volume(id)) else {
+            return Sound::stopped();
+        };
+
+        // the sounds stolen keep playing if the new one fails
+        let id = match play(&mut *backend) {
+            Ok(id) => id,
+            Err(err) => {
+                log::error!("Cannot play the sound: {}", err);
+                return Sound::stopped();
+            }
+        };
+
+        stolen.iter().for_each(|s| backend.stop(s.id));
+        self.voices.release(&stolen);
+
+        let sound = Sound::new(id, self.resource_tracker.clone());
+        self.voices.add(source.id, &sound);
+        sound
+    }
+
+    /// Max sounds playing at once, when there is no voice left a sound is stopped
+    /// following the voice stealing policy
+    /// Only the sounds played after setting a limit are counted
+    #[inline]
+    pub fn set_max_voices(&mut self, max: Option<usize>) {
+        self.voices.max_voices = max;
+    }
+
+    #[inline]
+    pub fn max_voices(&self) -> Option<usize> {
+        self.voices.max_voices
+    }
+
+    #[inline]
+    pub fn set_voice_stealing(&mut self, stealing: VoiceStealing) {
+        self.voices.stealing = stealing;
+    }
+
+    #[inline]
+    pub fn voice_stealing(&self) -> VoiceStealing {
+        self.voices.stealing
+    }
+
+    /// Sets the max voices, priority and cooldown of the sounds played from the source
+    #[inline]
+    pub fn set_source_voices(&mut self, source: &AudioSource, voices: SourceVoices) {
+        self.voices.set_source(source.id, voices);
+    }
+
+    #[inline]
+    pub fn source_voices(&self, source: &AudioSource) -> SourceVoices {
+        self.voices.source(source.id)
+    }
+
+    /// Changes the priority of a sound played with a voice limit
+    #[inline]
+    pub fn set_sound_priority(&mut self, sound: &Sound, priority: i32) {
+        self.voices.set_priority(sound.id, priority);
+    }
+
+    #[inline]
+    pub fn sound_priority(&self, sound: &Sound) -> Option<i32> {
+        self.voices.priority(sound.id)
+    }
+
+    /// Number of sounds played with a voice limit that are still playing
+    #[inline]
+    pub fn active_voices(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Creates a bus to group sounds, like music, effects or voices
+    #[inline]
+    pub fn create_bus(&mut self, name: &str) -> Result<AudioBus, String> {
+        let id = self.backend.borrow_mut().create_bus()?;
+        Ok(AudioBus::new(id, name, self.resource_tracker.clone()))
+    }
+
+    #[inline]
+    pub fn set_bus_volume(&mut self, bus: &AudioBus, volume: f32) {
+        self.backend
+            .borrow_mut()
+            .set_bus_volume(bus.id, clamp_volume(volume));
+    }
+
+    #[inline]
+    pub fn bus_volume(&self, bus: &AudioBus) -> f32 {
+        self.backend.borrow().bus_volume(bus.id)
+    }
+
+    /// Silences the bus keeping its volume
+    #[inline]
+    pub fn set_bus_muted(&mut self, bus: &AudioBus, muted: bool) {
+        self.backend.borrow_mut().set_bus_muted(bus.id, muted);
+    }
+
+    #[inline]
+    pub fn is_bus_muted(&self, bus: &AudioBus) -> bool {
+        self.backend.borrow().is_bus_muted(bus.id)
+    }
+
+    /// Pauses all the sounds of the bus
+    #[inline]
+    pub fn pause_bus(&mut self, bus: &AudioBus) {
+        self.backend.borrow_mut().pause_bus(bus.id);
+    }
+
+    #[inline]
+    pub fn resume_bus(&mut self, bus: &AudioBus) {
+        self.backend.borrow_mut().resume_bus(bus.id);
+    }
+
+    #[inline]
+    pub fn is_bus_paused(&self, bus: &AudioBus) -> bool {
+        self.backend.borrow().is_bus_paused(bus.id)
+    }
+
+    /// Replaces the effects applied to the mix of the sounds of the bus
+    #[inline]
+    pub fn set_bus_effects(&mut self, bus: &AudioBus, effects: &[AudioEffect]) {
+        self.backend.borrow_mut().set_bus_effects(bus.id, effects);
+    }
+
+    #[inline]
+    pub fn bus_effects(&self, bus: &AudioBus) -> Vec<AudioEffect> {
+        self.backend.borrow().bus_effects(bus.id)
     }
 
     #[inline]
     pub fn resume(&mut self, sound: &Sound) {
-        self.backend.borrow_mut().resume(sound.id);
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.resume(sound.id);
+        }
     }
 
     #[inline]
     pub fn stop(&mut self, sound: &Sound) {
-        self.backend.borrow_mut().stop(sound.id);
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.stop(sound.id);
+        }
     }
 
     #[inline]
     pub fn pause(&mut self, sound: &Sound) {
-        self.backend.borrow_mut().pause(sound.id);
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.pause(sound.id);
+        }
     }
 
     #[inline]
     pub fn is_stopped(&self, sound: &Sound) -> bool {
-        self.backend.borrow_mut().is_stopped(sound.id)
+        match self.sound_backend(sound) {
+            Some(mut backend) => backend.is_stopped(sound.id),
+            None => true,
+        }
     }
 
     #[inline]
     pub fn is_paused(&self, sound: &Sound) -> bool {
-        self.backend.borrow_mut().is_paused(sound.id)
+        self.sound_backend(sound)
+            .is_some_and(|mut backend| backend.is_paused(sound.id))
     }
 
     #[inline]
@@ -74,18 +327,269 @@ impl Audio {
 
     #[inline]
     pub fn set_volume(&mut self, sound: &Sound, volume: f32) {
-        self.backend
-            .borrow_mut()
-            .set_volume(sound.id, clamp_volume(volume));
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_volume(sound.id, clamp_volume(volume));
+        }
     }
 
     #[inline]
     pub fn volume(&self, sound: &Sound) -> f32 {
-        self.backend.borrow().volume(sound.id)
+        self.sound_backend(sound)
+            .map_or(0.0, |backend| backend.volume(sound.id))
+    }
+
+    /// Fades the sound from silence to its volume
+    #[inline]
+    pub fn fade_in(&mut self, sound: &Sound, duration: f32) {
+        self.fade(sound, Some(0.0), 1.0, duration, false);
+    }
+
+    /// Fades the sound to silence, it keeps playing until it's stopped
+    #[inline]
+    pub fn fade_out(&mut self, sound: &Sound, duration: f32) {
+        self.fade(sound, None, 0.0, duration, false);
+    }
+
+    /// Fades the sound to silence and stops it
+    #[inline]
+    pub fn fade_out_and_stop(&mut self, sound: &Sound, duration: f32) {
+        self.fade(sound, None, 0.0, duration, true);
+    }
+
+    /// Fades from the current level to the level given, it's applied on top of the volume
+    #[inline]
+    pub fn fade_to(&mut self, sound: &Sound, level: f32, duration: f32) {
+        self.fade(sound, None, clamp_volume(level), duration, false);
+    }
+
+    /// Fades out and stops a sound while the other one fades in
+    pub fn crossfade(&mut self, from: &Sound, to: &Sound, duration: f32) {
+        self.fade_out_and_stop(from, duration);
+        self.fade_in(to, duration);
+    }
+
+    fn fade(&mut self, sound: &Sound, from: Option<f32>, to: f32, duration: f32, stop: bool) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.fade(sound.id, from, to, duration.max(0.0), stop);
+        }
+    }
+
+    /// Replaces the effects of the sound, they are applied in order
+    /// Effects of the same kind in the same position keep their state, so it can be called
+    /// again with new parameters to change them while the sound plays
+    #[inline]
+    pub fn set_effects(&mut self, sound: &Sound, effects: &[AudioEffect]) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_effects(sound.id, effects);
+        }
+    }
+
+    #[inline]
+    pub fn effects(&self, sound: &Sound) -> Vec<AudioEffect> {
+        self.sound_backend(sound)
+            .map_or(vec![], |backend| backend.effects(sound.id))
     }
 
+    #[inline]
+    pub fn clear_effects(&mut self, sound: &Sound) {
+        self.set_effects(sound, &[]);
+    }
+
+    /// Replaces the effects applied to the mix of all the sounds
+    #[inline]
+    pub fn set_global_effects(&mut self, effects: &[AudioEffect]) {
+        self.backend.borrow_mut().set_global_effects(effects);
+    }
+
+    #[inline]
+    pub fn global_effects(&self) -> Vec<AudioEffect> {
+        self.backend.borrow().global_effects()
+    }
+
+    /// Duration in seconds, streaming sounds may not know it until they are decoded
+    #[inline]
+    pub fn duration(&self, sound: &Sound) -> Option<f32> {
+        self.sound_backend(sound)
+            .and_then(|backend| backend.duration(sound.id))
+    }
+
+    /// Current playback position in seconds
+    #[inline]
+    pub fn position(&self, sound: &Sound) -> f32 {
+        self.sound_backend(sound)
+            .map_or(0.0, |backend| backend.position(sound.id))
+    }
+
+    /// Moves the playback to the position in seconds
+    #[inline]
+    pub fn seek(&mut self, sound: &Sound, position: f32) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.seek(sound.id, position.max(0.0));
+        }
+    }
+
+    /// Changes the speed and the pitch of the sound, 1.0 is the original rate
+    #[inline]
+    pub fn set_playback_rate(&mut self, sound: &Sound, rate: f32) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_playback_rate(sound.id, rate.clamp(0.01, 100.0));
+        }
+    }
+
+    #[inline]
+    pub fn playback_rate(&self, sound: &Sound) -> f32 {
+        self.sound_backend(sound)
+            .map_or(1.0, |backend| backend.playback_rate(sound.id))
+    }
+
+    /// Repeats the region of the sound, `None` plays the sound until the end without repeat
+    #[inline]
+    pub fn set_loop_region(&mut self, sound: &Sound, region: Option<LoopRegion>) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_loop_region(sound.id, region);
+        }
+    }
+
+    /// Returns the region repeated, sounds played with repeat loop the whole sound
+    #[inline]
+    pub fn loop_region(&self, sound: &Sound) -> Option<LoopRegion> {
+        self.sound_backend(sound)
+            .and_then(|backend| backend.loop_region(sound.id))
+    }
+
+    /// Moves the sound from left (-1.0) to right (1.0), spatial sounds are panned by its position
+    #[inline]
+    pub fn set_pan(&mut self, sound: &Sound, pan: f32) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_pan(sound.id, pan.clamp(-1.0, 1.0));
+        }
+    }
+
+    #[inline]
+    pub fn pan(&self, sound: &Sound) -> f32 {
+        self.sound_backend(sound)
+            .map_or(0.0, |backend| backend.pan(sound.id))
+    }
+
+    /// Updates the position and attenuation of a spatial sound
+    #[inline]
+    pub fn set_spatial_options(&mut self, sound: &Sound, options: SpatialOptions) {
+        if let Some(mut backend) = self.sound_backend(sound) {
+            backend.set_spatial_options(sound.id, options);
+        }
+    }
+
+    /// Returns the options of the sound if it's spatial
+    #[inline]
+    pub fn spatial_options(&self, sound: &Sound) -> Option<SpatialOptions> {
+        self.sound_backend(sound)
+            .and_then(|backend| backend.spatial_options(sound.id))
+    }
+
+    /// Moves a spatial sound keeping the rest of its options
+    pub fn set_sound_position(&mut self, sound: &Sound, x: f32, y: f32, z: f32) {
+        if !sound.is_played() {
+            return;
+        }
+
+        match self.spatial_options(sound) {
+            Some(options) => self.set_spatial_options(
+                sound,
+                SpatialOptions {
+                    position: [x, y, z],
+                    ..options
+                },
+            ),
+            None => log::warn!(
+                "Cannot set the position of a non spatial sound: {}",
+                sound.id
+            ),
+        }
+    }
+
+    // rejected sounds never reached the backend, so the calls using them are skipped
+    fn sound_backend(&self, sound: &Sound) -> Option<RefMut<'_, dyn AudioBackend + 'static>> {
+        sound.is_played().then(|| self.backend.borrow_mut())
+    }
+
+    #[inline]
+    pub fn set_listener(&mut self, listener: AudioListener) {
+        self.backend.borrow_mut().set_listener(listener);
+    }
+
+    #[inline]
+    pub fn listener(&self) -> AudioListener {
+        self.backend.borrow().listener()
+    }
+
+    /// Moves the listener keeping its orientation
+    pub fn set_listener_position(&mut self, x: f32, y: f32, z: f32) {
+        let listener = AudioListener {
+            position: [x, y, z],
+            ..self.listener()
+        };
+        self.set_listener(listener);
+    }
+
+    /// Names of the input devices, like microphones
+    #[inline]
+    pub fn input_devices(&self) -> Vec<String> {
+        self.backend.borrow().input_devices()
+    }
+
+    /// Starts capturing audio from the input device with that name, or the default one
+    /// Only one device is captured at a time, starting a new capture stops the previous one
+    #[inline]
+    pub fn start_capture(&mut self, device: Option<&str>) -> Result<(), String> {
+        self.backend.borrow_mut().start_capture(device)
+    }
+
+    #[inline]
+    pub fn stop_capture(&mut self) {
+        self.backend.borrow_mut().stop_capture();
+    }
+
+    #[inline]
+    pub fn is_capturing(&self) -> bool {
+        self.backend.borrow().is_capturing()
+    }
+
+    /// Sample rate of the captured samples
+    #[inline]
+    pub fn capture_sample_rate(&self) -> Option<u32> {
+        self.backend.borrow().capture_sample_rate()
+    }
+
+    /// Takes the samples captured since the last read, mixed to mono
+    /// Up to two seconds are kept if they are not read, the newer samples are dropped
+    #[inline]
+    pub fn read_capture(&mut self) -> Vec<f32> {
+        let mut samples = vec![];
+        self.read_capture_into(&mut samples);
+        samples
+    }
+
+    /// Appends the samples captured since the last read to the vector
+    #[inline]
+    pub fn read_capture_into(&mut self, samples: &mut Vec<f32>) {
+        self.backend.borrow_mut().read_capture(samples);
+    }
+
+    /// RMS level from 0.0 to 1.0 of the last samples captured
+    #[inline]
+    pub fn capture_level(&self) -> f32 {
+        self.backend.borrow().capture_level()
+    }
+
+    /// Called once per frame to clean the dropped resources and the stopped voices
     #[inline]
     pub fn clean(&mut self) {
+        // the voices can drop their sounds, so they are updated before reading the resources
+        self.voices.next_frame();
+        let backend = self.backend.clone();
+        self.voices
+            .retain(|id| !backend.borrow_mut().is_stopped(id));
+
         let resources = self.resource_tracker.dropped.read();
         if resources.is_empty() {
             return;
@@ -93,17 +597,20 @@ impl Audio {
 
         let mut sources = vec![];
         let mut sounds = vec![];
+        let mut buses = vec![];
         for res in resources.iter() {
             match res {
                 ResourceId::Source(id) => sources.push(*id),
                 ResourceId::Sound(id) => sounds.push(*id),
+                ResourceId::Bus(id) => buses.push(*id),
             }
         }
 
         // drop resources here to avoid deadlock calling clean
         drop(resources);
 
-        self.backend.borrow_mut().clean(&sources, &sounds);
+        sources.iter().for_each(|id| self.voices.remove_source(*id));
+        self.backend.borrow_mut().clean(&sources, &sounds, &buses);
         self.resource_tracker.clean();
     }
 }
@@ -111,3 +618,179 @@ impl Audio {
 fn clamp_volume(volume: f32) -> f32 {
     volume.clamp(0.0, 1.0)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generator::AudioGeneratorFactory;
+
+    // plays every sound without ending them and records the sounds used by each call
+    #[derive(Default)]
+    struct TestBackend {
+        fail: bool,
+        next_id: u64,
+        playing: Vec<u64>,
+        calls: Vec<u64>,
+    }
+
+    impl TestBackend {
+        fn play(&mut self) -> Result<u64, String> {
+            if self.fail {
+                return Err("Test error".to_string());
+            }
+
+            self.next_id += 1;
+            self.playing.push(self.next_id);
+            Ok(self.next_id)
+        }
+
+        fn call(&mut self, sound: u64) {
+            self.calls.push(sound);
+        }
+    }
+
+    impl AudioBackend for TestBackend {
+        fn set_global_volume(&mut self, _volume: f32) {}
+        fn global_volume(&self) -> f32 {
+            1.0
+        }
+        fn create_source(&mut self, _bytes: &[u8], _kind: AudioSourceKind) -> Result<u64, String> {
+            Ok(0)
+        }
+        fn create_source_from_pcm(
+            &mut self,
+            _samples: &[f32],
+            _channels: u16,
+            _sample_rate: u32,
+        ) -> Result<u64, String> {
+            Ok(0)
+        }
+        fn create_source_from_generator(
+            &mut self,
+            _factory: AudioGeneratorFactory,
+        ) -> Result<u64, String> {
+            Ok(0)
+        }
+        fn play_sound(
+            &mut self,
+            _source: u64,
+            _volume: f32,
+            _repeat: bool,
+            _bus: Option<u64>,
+        ) -> Result<u64, String> {
+            self.play()
+        }
+        fn pause(&mut self, sound: u64) {
+            self.call(sound);
+        }
+        fn resume(&mut self, sound: u64) {
+            self.call(sound);
+        }
+        fn stop(&mut self, sound: u64) {
+            self.call(sound);
+            self.playing.retain(|id| *id != sound);
+        }
+        fn is_stopped(&mut self, sound: u64) -> bool {
+            self.call(sound);
+            !self.playing.contains(&sound)
+        }
+        fn is_paused(&mut self, sound: u64) -> bool {
+            self.call(sound);
+            false
+        }
+        fn set_volume(&mut self, sound: u64, _volume: f32) {
+            self.call(sound);
+        }
+        fn volume(&self, _sound: u64) -> f32 {
+            1.0
+        }
+        fn set_pan(&mut self, sound: u64, _pan: f32) {
+            self.call(sound);
+        }
+        fn set_spatial_options(&mut self, sound: u64, _options: SpatialOptions) {
+            self.call(sound);
+        }
+        fn fade(&mut self, sound: u64, _from: Option<f32>, _to: f32, _duration: f32, _stop: bool) {
+            self.call(sound);
+        }
+        fn set_effects(&mut self, sound: u64, _effects: &[AudioEffect]) {
+            self.call(sound);
+        }
+        fn seek(&mut self, sound: u64, _position: f32) {
+            self.call(sound);
+        }
+        fn set_playback_rate(&mut self, sound: u64, _rate: f32) {
+            self.call(sound);
+        }
+        fn set_loop_region(&mut self, sound: u64, _region: Option<LoopRegion>) {
+            self.call(sound);
+        }
+        fn clean(&mut self, _sources: &[u64], _sounds: &[u64], _buses: &[u64]) {}
+    }
+
+    #[test]
+    fn rejected_voice() {
+        let backend = Rc::new(RefCell::new(TestBackend::default()));
+        let mut audio = Audio::new(backend.clone()).unwrap();
+        audio.set_max_voices(Some(1));
+        audio.set_voice_stealing(VoiceStealing::Reject);
+
+        let source = audio.create_source_from_pcm(&[0.5; 100], 1, 1000).unwrap();
+        let sound = audio.play_sound(&source, 1.0, false);
+        let rejected = audio.play_sound(&source, 1.0, false);
+        assert!(!audio.is_stopped(&sound));
+        assert!(audio.is_stopped(&rejected));
+        assert_eq!(backend.borrow().next_id, 1);
+
+        // each rejected sound is a different sound
+        let other = audio.play_sound(&source, 1.0, false);
+        assert_ne!(rejected, other);
+        assert_eq!(rejected, rejected.clone());
+
+        // the calls on the rejected sound never reach the backend
+        backend.borrow_mut().calls.clear();
+        audio.set_volume(&rejected, 0.5);
+        audio.fade_out_and_stop(&rejected, 1.0);
+        audio.seek(&rejected, 1.0);
+        audio.set_sound_position(&rejected, 1.0, 0.0, 0.0);
+        audio.pause(&rejected);
+        audio.stop(&rejected);
+        assert!(!audio.is_paused(&rejected));
+        assert!(backend.borrow().calls.is_empty());
+        assert_eq!(audio.active_voices(), 1);
+    }
+
+    #[test]
+    fn failed_play() {
+        let backend = Rc::new(RefCell::new(TestBackend {
+            fail: true,
+            ..Default::default()
+        }));
+        let mut audio = Audio::new(backend.clone()).unwrap();
+        audio.set_max_voices(Some(1));
+
+        // the error returns a stopped sound that doesn't take the voice
+        let source = audio.create_source_from_pcm(&[0.5; 100], 1, 1000).unwrap();
+        let sound = audio.play_sound(&source, 1.0, false);
+        assert!(audio.is_stopped(&sound));
+        assert_eq!(audio.active_voices(), 0);
+
+        backend.borrow_mut().fail = false;
+        let sound = audio.play_sound(&source, 1.0, false);
+        assert!(!audio.is_stopped(&sound));
+        assert_eq!(audio.active_voices(), 1);
+
+        // the sound that would be stolen keeps playing if the new one fails
+        backend.borrow_mut().fail = true;
+        let failed = audio.play_sound(&source, 1.0, false);
+        assert!(audio.is_stopped(&failed));
+        assert!(!audio.is_stopped(&sound));
+        assert_eq!(audio.active_voices(), 1);
+
+        backend.borrow_mut().fail = false;
+        let next = audio.play_sound(&source, 1.0, false);
+        assert!(!audio.is_stopped(&next));
+        assert!(audio.is_stopped(&sound));
+        assert_eq!(audio.active_voices(), 1);
+    }
+}
@@ -1,2 +1,6 @@
 pub use crate::backend::*;
+pub use crate::effect::*;
+pub use crate::generator::*;
 pub use crate::manager::Audio;
+pub use crate::spatial::*;
+pub use crate::voices::{SourceVoices, VoiceStealing};
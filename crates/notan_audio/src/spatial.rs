@@ -0,0 +1,130 @@
+/// How the volume of a spatial sound decreases with the distance to the listener
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DistanceModel {
+    /// `ref / (ref + rolloff * (distance - ref))`
+    #[default]
+    Inverse,
+    /// `1 - rolloff * (distance - ref) / (max - ref)`
+    Linear,
+    /// `(distance / ref) ^ -rolloff`
+    Exponential,
+}
+
+/// Position and attenuation of a sound played in the space around the listener
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialOptions {
+    pub position: [f32; 3],
+    /// Velocity in units per second, only used for the doppler effect
+    pub velocity: [f32; 3],
+    pub model: DistanceModel,
+    /// Distance where the volume starts to decrease
+    pub ref_distance: f32,
+    /// Distance where the volume stops decreasing
+    pub max_distance: f32,
+    /// How fast the volume decreases with the distance
+    pub rolloff: f32,
+    /// Changes the pitch when the sound and the listener move relative to each other
+    pub doppler: bool,
+}
+
+impl Default for SpatialOptions {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            model: DistanceModel::Inverse,
+            ref_distance: 1.0,
+            max_distance: 10000.0,
+            rolloff: 1.0,
+            doppler: false,
+        }
+    }
+}
+
+impl SpatialOptions {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self {
+            position: [x, y, z],
+            ..Default::default()
+        }
+    }
+
+    /// Volume multiplier for the distance given
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        let ref_distance = self.ref_distance.max(f32::EPSILON);
+        let max_distance = self.max_distance.max(ref_distance);
+        let distance = distance.clamp(ref_distance, max_distance);
+        let volume = match self.model {
+            DistanceModel::Inverse => {
+                ref_distance / (ref_distance + self.rolloff * (distance - ref_distance))
+            }
+            DistanceModel::Linear => {
+                let range = max_distance - ref_distance;
+                if range <= 0.0 {
+                    1.0
+                } else {
+                    1.0 - self.rolloff * (distance - ref_distance) / range
+                }
+            }
+            DistanceModel::Exponential => (distance / ref_distance).powf(-self.rolloff),
+        };
+
+        volume.clamp(0.0, 1.0)
+    }
+}
+
+/// Position and orientation used to hear the spatial sounds
+/// By default it faces -Z with +Y up, so sounds on the XY plane
+/// are heard like on a top-down view with +X on the right
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioListener {
+    pub position: [f32; 3],
+    /// Velocity in units per second, only used for the doppler effect
+    pub velocity: [f32; 3],
+    pub forward: [f32; 3],
+    pub up: [f32; 3],
+    /// World units in a meter, used to compute the doppler effect
+    pub units_per_meter: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            forward: [0.0, 0.0, -1.0],
+            up: [0.0, 1.0, 0.0],
+            units_per_meter: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn distance_models() {
+        let mut options = SpatialOptions {
+            ref_distance: 10.0,
+            max_distance: 110.0,
+            ..Default::default()
+        };
+
+        // no attenuation until the reference distance
+        assert_eq!(options.attenuation(0.0), 1.0);
+        assert_eq!(options.attenuation(10.0), 1.0);
+        assert_eq!(options.attenuation(20.0), 0.5);
+
+        options.model = DistanceModel::Linear;
+        assert_eq!(options.attenuation(60.0), 0.5);
+        assert_eq!(options.attenuation(500.0), 0.0);
+
+        options.model = DistanceModel::Exponential;
+        options.rolloff = 2.0;
+        assert_eq!(options.attenuation(20.0), 0.25);
+
+        // the volume doesn't change after the max distance
+        assert_eq!(options.attenuation(110.0), options.attenuation(1000.0));
+    }
+}
@@ -4,6 +4,7 @@ use parking_lot::RwLock;
 pub(crate) enum ResourceId {
     Source(u64),
     Sound(u64),
+    Bus(u64),
 }
 
 #[derive(Debug, Default)]
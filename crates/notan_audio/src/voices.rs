@@ -0,0 +1,254 @@
+use crate::backend::Sound;
+use std::collections::HashMap;
+
+/// Sound stopped to play a new one when there are no voices left
+/// Sounds with a higher priority than the new one are never stopped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VoiceStealing {
+    /// Stops the sound that started first
+    #[default]
+    Oldest,
+    /// Stops the sound with the lowest volume set, fades, buses and distance are not counted
+    Quietest,
+    /// Stops the sound with the lowest priority, the oldest one if there are several
+    LowestPriority,
+    /// Doesn't stop any sound, the new one is returned stopped without playing
+    Reject,
+}
+
+/// Voice settings of the sounds played from a source
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SourceVoices {
+    /// Max sounds of the source playing at once
+    pub max_voices: Option<usize>,
+    /// Priority of the sounds when they are played
+    pub priority: i32,
+    /// Frames until the source can play again, 1 ignores the plays in the same frame
+    pub cooldown: u32,
+}
+
+struct Voice {
+    sound: Sound,
+    source: u64,
+    priority: i32,
+}
+
+/// Keeps the sounds played with voice limits until they stop, ordered by start
+#[derive(Default)]
+pub(crate) struct VoicePool {
+    pub max_voices: Option<usize>,
+    pub stealing: VoiceStealing,
+    sources: HashMap<u64, SourceVoices>,
+    voices: Vec<Voice>,
+    cooldowns: HashMap<u64, (u64, Sound)>,
+    frame: u64,
+}
+
+impl VoicePool {
+    pub fn set_source(&mut self, source: u64, voices: SourceVoices) {
+        self.sources.insert(source, voices);
+    }
+
+    pub fn source(&self, source: u64) -> SourceVoices {
+        self.sources.get(&source).copied().unwrap_or_default()
+    }
+
+    pub fn remove_source(&mut self, source: u64) {
+        self.sources.remove(&source);
+        self.cooldowns.remove(&source);
+    }
+
+    pub fn len(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Sounds are only kept when they have a limit
+    fn is_limited(&self, source: u64) -> bool {
+        self.max_voices.is_some() || self.sources.contains_key(&source)
+    }
+
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+        let (frame, sources) = (self.frame, &self.sources);
+        self.cooldowns.retain(|source, (played, _)| {
+            let cooldown = sources.get(source).map_or(0, |s| s.cooldown);
+            frame - *played < cooldown as u64
+        });
+    }
+
+    pub fn retain(&mut self, mut is_playing: impl FnMut(u64) -> bool) {
+        self.voices.retain(|v| is_playing(v.sound.id));
+    }
+
+    /// Sound played from the source in the cooldown
+    pub fn cooldown(&self, source: u64) -> Option<Sound> {
+        self.cooldowns.get(&source).map(|(_, sound)| sound.clone())
+    }
+
+    /// Returns the sounds to stop to free the voices for a new sound of the source,
+    /// or `None` if the sound cannot be played
+    /// The sounds are kept until they're released, in case the new sound fails to play
+    pub fn reserve(&mut self, source: u64, volume: impl Fn(u64) -> f32) -> Option<Vec<Sound>> {
+        if !self.is_limited(source) {
+            return Some(vec![]);
+        }
+
+        let settings = self.source(source);
+        let mut stolen: Vec<usize> = vec![];
+        if let Some(max) = settings.max_voices {
+            let count = self.voices.iter().filter(|v| v.source == source).count();
+            while count - stolen.len() >= max {
+                let i = self.victim(settings.priority, &stolen, Some(source), &volume)?;
+                stolen.push(i);
+            }
+        }
+
+        if let Some(max) = self.max_voices {
+            while self.voices.len() - stolen.len() >= max {
+                let i = self.victim(settings.priority, &stolen, None, &volume)?;
+                stolen.push(i);
+            }
+        }
+
+        Some(
+            stolen
+                .into_iter()
+                .map(|i| self.voices[i].sound.clone())
+                .collect(),
+        )
+    }
+
+    /// Frees the voices of the sounds stolen
+    pub fn release(&mut self, stolen: &[Sound]) {
+        self.voices
+            .retain(|v| !stolen.iter().any(|s| s.id == v.sound.id));
+    }
+
+    fn victim(
+        &self,
+        priority: i32,
+        stolen: &[usize],
+        source: Option<u64>,
+        volume: &impl Fn(u64) -> f32,
+    ) -> Option<usize> {
+        let mut candidates = self.voices.iter().enumerate().filter(|(i, v)| {
+            v.priority <= priority
+                && !stolen.contains(i)
+                && (source.is_none() || source == Some(v.source))
+        });
+
+        match self.stealing {
+            VoiceStealing::Oldest => candidates.next(),
+            VoiceStealing::Quietest => candidates
+                .min_by(|(_, a), (_, b)| volume(a.sound.id).total_cmp(&volume(b.sound.id))),
+            VoiceStealing::LowestPriority => candidates.min_by_key(|(_, v)| v.priority),
+            VoiceStealing::Reject => None,
+        }
+        .map(|(i, _)| i)
+    }
+
+    /// Keeps the sound until it stops if it has a limit
+    pub fn add(&mut self, source: u64, sound: &Sound) {
+        if !self.is_limited(source) {
+            return;
+        }
+
+        let settings = self.source(source);
+        if settings.cooldown > 0 {
+            self.cooldowns.insert(source, (self.frame, sound.clone()));
+        }
+
+        self.voices.push(Voice {
+            sound: sound.clone(),
+            source,
+            priority: settings.priority,
+        });
+    }
+
+    pub fn set_priority(&mut self, sound: u64, priority: i32) {
+        if let Some(v) = self.voices.iter_mut().find(|v| v.sound.id == sound) {
+            v.priority = priority;
+        }
+    }
+
+    pub fn priority(&self, sound: u64) -> Option<i32> {
+        self.voices
+            .iter()
+            .find(|v| v.sound.id == sound)
+            .map(|v| v.priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tracker::ResourceTracker;
+    use std::sync::Arc;
+
+    fn play(pool: &mut VoicePool, source: u64, id: u64) -> Option<Vec<u64>> {
+        let stolen = pool.reserve(source, |id| id as f32)?;
+        pool.release(&stolen);
+        pool.add(
+            source,
+            &Sound::new(id, Arc::new(ResourceTracker::default())),
+        );
+        Some(stolen.iter().map(|s| s.id).collect())
+    }
+
+    #[test]
+    fn voice_stealing() {
+        let mut pool = VoicePool::default();
+        assert_eq!(play(&mut pool, 0, 0), Some(vec![]));
+        assert_eq!(pool.len(), 0);
+
+        pool.set_source(
+            0,
+            SourceVoices {
+                max_voices: Some(2),
+                ..Default::default()
+            },
+        );
+        pool.max_voices = Some(3);
+        assert_eq!(play(&mut pool, 0, 1), Some(vec![]));
+        assert_eq!(play(&mut pool, 0, 2), Some(vec![]));
+        assert_eq!(play(&mut pool, 0, 3), Some(vec![1]));
+        assert_eq!(play(&mut pool, 1, 4), Some(vec![]));
+        assert_eq!(play(&mut pool, 1, 5), Some(vec![2]));
+
+        // the sounds with a higher priority are not stolen
+        pool.set_priority(3, 1);
+        pool.set_priority(4, 1);
+        pool.set_priority(5, 1);
+        pool.stealing = VoiceStealing::Quietest;
+        assert_eq!(play(&mut pool, 1, 6), None);
+        pool.set_priority(5, 0);
+        pool.set_priority(4, -1);
+        assert_eq!(play(&mut pool, 1, 7), Some(vec![4]));
+        pool.stealing = VoiceStealing::LowestPriority;
+        assert_eq!(play(&mut pool, 1, 8), Some(vec![5]));
+
+        pool.retain(|id| id != 3);
+        pool.stealing = VoiceStealing::Reject;
+        assert_eq!(play(&mut pool, 1, 9), Some(vec![]));
+        assert_eq!(play(&mut pool, 1, 10), None);
+    }
+
+    #[test]
+    fn cooldown() {
+        let mut pool = VoicePool::default();
+        pool.set_source(
+            0,
+            SourceVoices {
+                cooldown: 2,
+                ..Default::default()
+            },
+        );
+
+        play(&mut pool, 0, 0);
+        assert_eq!(pool.cooldown(0).map(|s| s.id), Some(0));
+        pool.next_frame();
+        assert!(pool.cooldown(0).is_some());
+        pool.next_frame();
+        assert!(pool.cooldown(0).is_none());
+    }
+}
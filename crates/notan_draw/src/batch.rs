@@ -1,6 +1,10 @@
+use crate::cache::DrawCache;
+use crate::draw::GlyphEffect;
+use crate::images::ImageInstances;
+use crate::material::Material;
 use notan_glyph::OwnedSection;
 use notan_graphics::prelude::*;
-use notan_math::{Mat3, Vec3};
+use notan_math::{Mat3, Rect, Vec2, Vec3};
 
 #[derive(Clone, Debug)]
 pub(crate) struct TextData {
@@ -9,6 +13,30 @@ pub(crate) struct TextData {
     pub alpha: f32,
     pub count: usize,
     pub flip: (bool, bool),
+    pub effects: Option<Vec<GlyphEffect>>,
+    pub outline: Option<(Color, f32)>,
+    pub shadow: Option<(Color, Vec2, f32)>,
+}
+
+impl TextData {
+    /// The outline and the shadow need the pipeline with effects
+    pub fn has_effects(&self) -> bool {
+        self.outline.is_some() || self.shadow.is_some()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct CachedData {
+    pub cache: DrawCache,
+    pub transform: Mat3,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct InstancedData {
+    pub instances: ImageInstances,
+    pub transform: Mat3,
+    pub color: Color,
 }
 
 #[derive(Clone, Debug)]
@@ -17,6 +45,28 @@ pub(crate) enum BatchType {
     Pattern { texture: Texture },
     Shape,
     Text { texts: Vec<TextData> },
+    Cached { data: CachedData },
+    Instanced { data: InstancedData },
+}
+
+/// Stencil operation used by a batch to write or to test the masks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum StencilMode {
+    #[default]
+    None,
+    /// Draw only where the stencil value is equal to the reference
+    Test(u8),
+    /// Apply the action where the stencil value is equal to the reference without drawing any color
+    Write {
+        reference: u8,
+        action: StencilAction,
+    },
+}
+
+impl StencilMode {
+    pub fn is_write(&self) -> bool {
+        matches!(self, Self::Write { .. })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -26,10 +76,11 @@ pub(crate) struct Batch {
     pub indices: Vec<u32>,
     pub pipeline: Option<Pipeline>,
     pub uniform_buffers: Option<Vec<Buffer>>,
+    pub material: Option<Material>,
     pub blend_mode: Option<BlendMode>,
     pub alpha_mode: Option<BlendMode>,
-    pub is_mask: bool,
-    pub masking: bool,
+    pub stencil: StencilMode,
+    pub clip: Option<Rect>,
 }
 
 impl Batch {
@@ -69,6 +120,8 @@ impl Batch {
             BatchType::Pattern { .. } => 12,
             BatchType::Shape => 6,
             BatchType::Text { .. } => 8,
+            // cached and instanced batches keep their vertices on the gpu
+            BatchType::Cached { .. } | BatchType::Instanced { .. } => 0,
         }
     }
 }
@@ -0,0 +1,85 @@
+mod cached;
+mod draw_cache;
+mod painter;
+
+use crate::builder::DrawBuilder;
+use crate::draw::Draw;
+pub use cached::*;
+pub use draw_cache::*;
+pub(crate) use painter::*;
+
+pub trait DrawCached {
+    fn cached<'a>(&mut self, cache: &'a DrawCache) -> DrawBuilder<'_, Cached<'a>>;
+}
+
+impl DrawCached for Draw {
+    fn cached<'a>(&mut self, cache: &'a DrawCache) -> DrawBuilder<'_, Cached<'a>> {
+        DrawBuilder::new(self, Cached::new(cache))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::manager::DrawManager;
+    use crate::transform::DrawTransform;
+    use crate::DrawShapes;
+    use notan_app::empty::EmptyBackend;
+    use notan_app::{BackendSystem, Graphics};
+    use notan_glyph::GlyphBrushBuilder;
+    use notan_graphics::color::Color;
+    use notan_graphics::prelude::Commands;
+
+    #[test]
+    fn replay_cached_geometry() {
+        let backend = EmptyBackend::new().unwrap();
+        let mut gfx = Graphics::new(backend.get_graphics_backend()).unwrap();
+        let mut glyphs = GlyphBrushBuilder::using_fonts(vec![]).build(&mut gfx);
+        let mut manager = DrawManager::new(&mut gfx.device).unwrap();
+
+        let mut recorded = Draw::new(400, 300);
+        recorded.rect((0.0, 0.0), (10.0, 10.0));
+        recorded.triangle((0.0, 0.0), (10.0, 0.0), (5.0, 10.0));
+
+        // the geometry of both shapes is stored in one batch
+        let batch = recorded.current_batch.as_ref().unwrap();
+        assert_eq!(batch.vertices.len(), 7 * 6);
+        assert_eq!(batch.indices, [0, 1, 2, 0, 2, 3, 4, 5, 6]);
+        let triangle = batch.vertices[4 * 6..]
+            .chunks_exact(6)
+            .map(|v| (v[0], v[1]))
+            .collect::<Vec<_>>();
+        assert_eq!(triangle, [(0.0, 0.0), (10.0, 0.0), (5.0, 10.0)]);
+
+        let cache = DrawCache::new(&mut gfx.device, &recorded).unwrap();
+        let cached = cache.batches();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].count, batch.indices.len());
+
+        let mut draw = Draw::new(400, 300);
+        draw.cached(&cache);
+        draw.cached(&cache).color(Color::RED).translate(10.0, 0.0);
+
+        let commands = manager.process_draw(&draw, &mut gfx.device, &mut glyphs, false);
+        let draws = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Commands::Draw { offset, count, .. } => Some((*offset, *count)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(draws, [(0, 9), (0, 9)]);
+
+        // each replay binds the stored buffers with its own uniforms
+        let (stored, uniforms): (Vec<u64>, Vec<u64>) = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Commands::BindBuffer { id } => Some(*id),
+                _ => None,
+            })
+            .partition(|id| *id == cached[0].vbo.id() || *id == cached[0].ebo.id());
+        assert_eq!(stored.len(), 4);
+        assert_eq!(uniforms.len(), 2);
+        assert_ne!(uniforms[0], uniforms[1]);
+    }
+}
@@ -0,0 +1,84 @@
+use super::draw_cache::DrawCache;
+use crate::builder::DrawProcess;
+use crate::draw::{CacheInfo, Draw};
+use crate::transform::DrawTransform;
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_math::Mat3;
+
+pub struct Cached<'a> {
+    cache: &'a DrawCache,
+    matrix: Option<Mat3>,
+    color: Color,
+    alpha: f32,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+}
+
+impl<'a> Cached<'a> {
+    pub fn new(cache: &'a DrawCache) -> Self {
+        Self {
+            cache,
+            matrix: None,
+            color: Color::WHITE,
+            alpha: 1.0,
+            blend_mode: None,
+            alpha_mode: None,
+        }
+    }
+
+    /// Tint color multiplied by the cached vertices color
+    /// Geometry recorded with a custom pipeline ignores the tint
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Overrides the blend mode recorded on the geometry
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    /// Overrides the alpha mode recorded on the geometry
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+}
+
+impl DrawTransform for Cached<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for Cached<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let Self {
+            cache,
+            matrix,
+            color,
+            alpha,
+            blend_mode,
+            alpha_mode,
+        } = self;
+
+        if cache.is_empty() {
+            return;
+        }
+
+        draw.add_cache(&CacheInfo {
+            cache,
+            transform: matrix.as_ref(),
+            color: color.with_alpha(color.a * alpha),
+            blend_mode,
+            alpha_mode,
+        });
+    }
+}
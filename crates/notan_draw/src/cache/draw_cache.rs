@@ -0,0 +1,133 @@
+use super::painter::{image_vertex_info, pattern_vertex_info, shape_vertex_info};
+use crate::batch::{Batch, BatchType};
+use crate::draw::Draw;
+use notan_app::Graphics;
+use notan_graphics::prelude::*;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub(crate) enum CachedType {
+    Image { texture: Texture },
+    Pattern { texture: Texture },
+    Shape,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct CachedBatch {
+    pub typ: CachedType,
+    pub vbo: Buffer,
+    pub ebo: Buffer,
+    pub count: usize,
+    pub pipeline: Option<Pipeline>,
+    pub uniform_buffers: Option<Vec<Buffer>>,
+    pub textures: Vec<(u32, Texture)>,
+    pub blend_mode: Option<BlendMode>,
+    pub alpha_mode: Option<BlendMode>,
+}
+
+/// Geometry recorded from a `Draw` and stored on the GPU
+/// It can be drawn as many times as needed using `draw.cached(&cache)`
+/// without tessellating or uploading the vertices again.
+/// Texts and masks are not cached.
+#[derive(Clone, Debug)]
+pub struct DrawCache {
+    batches: Arc<Vec<CachedBatch>>,
+}
+
+impl DrawCache {
+    /// Creates a new cache uploading the geometry recorded on the draw passed
+    pub fn new(device: &mut Device, draw: &Draw) -> Result<Self, String> {
+        let batches = draw
+            .batches
+            .iter()
+            .chain(draw.current_batch.iter())
+            .filter_map(|b| create_cached_batch(device, b).transpose())
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            batches: Arc::new(batches),
+        })
+    }
+
+    /// Returns true if there is nothing to draw
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    pub(crate) fn batches(&self) -> &[CachedBatch] {
+        &self.batches
+    }
+}
+
+fn create_cached_batch(device: &mut Device, batch: &Batch) -> Result<Option<CachedBatch>, String> {
+    if batch.stencil.is_write() {
+        return Ok(None);
+    }
+
+    let (typ, info) = match &batch.typ {
+        BatchType::Image { texture } => (
+            CachedType::Image {
+                texture: texture.clone(),
+            },
+            image_vertex_info(),
+        ),
+        BatchType::Pattern { texture } => (
+            CachedType::Pattern {
+                texture: texture.clone(),
+            },
+            pattern_vertex_info(),
+        ),
+        BatchType::Shape => (CachedType::Shape, shape_vertex_info()),
+        BatchType::Text { .. } | BatchType::Cached { .. } | BatchType::Instanced { .. } => {
+            #[cfg(debug_assertions)]
+            {
+                log::warn!(
+                    "Texts, cached geometry and instanced images are skipped when creating a DrawCache"
+                );
+            }
+
+            return Ok(None);
+        }
+    };
+
+    if batch.indices.is_empty() {
+        return Ok(None);
+    }
+
+    let vbo = device
+        .create_vertex_buffer()
+        .with_info(&info)
+        .with_data(&batch.vertices)
+        .build()?;
+
+    let ebo = device
+        .create_index_buffer()
+        .with_data(&batch.indices)
+        .build()?;
+
+    Ok(Some(CachedBatch {
+        typ,
+        vbo,
+        ebo,
+        count: batch.indices.len(),
+        pipeline: batch.pipeline.clone(),
+        uniform_buffers: batch.uniform_buffers.clone(),
+        textures: batch
+            .material
+            .as_ref()
+            .map(|m| m.textures().to_vec())
+            .unwrap_or_default(),
+        blend_mode: batch.blend_mode,
+        alpha_mode: batch.alpha_mode,
+    }))
+}
+
+pub trait CreateDrawCache {
+    fn create_draw_cache(&mut self, draw: &Draw) -> Result<DrawCache, String>;
+}
+
+impl CreateDrawCache for Graphics {
+    fn create_draw_cache(&mut self, draw: &Draw) -> Result<DrawCache, String> {
+        DrawCache::new(&mut self.device, draw)
+    }
+}
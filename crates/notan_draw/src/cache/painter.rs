@@ -0,0 +1,264 @@
+use super::draw_cache::CachedType;
+use crate::batch::*;
+use crate::images::IMAGE_FRAGMENT;
+use crate::manager::set_pipeline;
+use crate::material::Material;
+use crate::patterns::PATTERN_FRAGMENT;
+use crate::shapes::SHAPES_FRAGMENT;
+use notan_graphics::prelude::*;
+use notan_macro::vertex_shader;
+use notan_math::{Mat3, Mat4, Vec4};
+
+//language=glsl
+const CACHED_SHAPES_VERTEX: ShaderSource = vertex_shader! {
+    r#"
+    #version 450
+    layout(location = 0) in vec2 a_pos;
+    layout(location = 1) in vec4 a_color;
+
+    layout(location = 0) out vec4 v_color;
+    layout(set = 0, binding = 0) uniform Locals {
+        mat4 u_projection;
+        vec4 u_tint;
+    };
+
+    void main() {
+        v_color = a_color * u_tint;
+        gl_Position = u_projection * vec4(a_pos, 0.0, 1.0);
+    }
+    "#
+};
+
+//language=glsl
+const CACHED_IMAGE_VERTEX: ShaderSource = vertex_shader! {
+    r#"
+    #version 450
+    layout(location = 0) in vec2 a_pos;
+    layout(location = 1) in vec2 a_uvs;
+    layout(location = 2) in vec4 a_color;
+
+    layout(location = 0) out vec4 v_color;
+    layout(location = 1) out vec2 v_uvs;
+    layout(set = 0, binding = 0) uniform Locals {
+        mat4 u_projection;
+        vec4 u_tint;
+    };
+
+    void main() {
+        v_color = a_color * u_tint;
+        v_uvs = a_uvs;
+        gl_Position = u_projection * vec4(a_pos, 0.0, 1.0);
+    }
+    "#
+};
+
+//language=glsl
+const CACHED_PATTERN_VERTEX: ShaderSource = vertex_shader! {
+    r#"
+    #version 450
+    layout(location = 0) in vec2 a_pos;
+    layout(location = 1) in vec2 a_uvs;
+    layout(location = 2) in vec4 a_frame;
+    layout(location = 3) in vec4 a_color;
+
+    layout(location = 0) out vec2 v_uvs;
+    layout(location = 1) out vec4 v_frame;
+    layout(location = 2) out vec4 v_color;
+
+    layout(set = 0, binding = 0) uniform Locals {
+        mat4 u_projection;
+        vec4 u_tint;
+    };
+
+    void main() {
+        v_uvs = a_uvs;
+        v_frame = a_frame;
+        v_color = a_color * u_tint;
+        gl_Position = u_projection * vec4(a_pos, 0.0, 1.0);
+    }
+    "#
+};
+
+pub(crate) fn shape_vertex_info() -> VertexInfo {
+    VertexInfo::new()
+        .attr(0, VertexFormat::Float32x2)
+        .attr(1, VertexFormat::Float32x4)
+}
+
+pub(crate) fn image_vertex_info() -> VertexInfo {
+    VertexInfo::new()
+        .attr(0, VertexFormat::Float32x2)
+        .attr(1, VertexFormat::Float32x2)
+        .attr(2, VertexFormat::Float32x4)
+}
+
+pub(crate) fn pattern_vertex_info() -> VertexInfo {
+    VertexInfo::new()
+        .attr(0, VertexFormat::Float32x2)
+        .attr(1, VertexFormat::Float32x2)
+        .attr(2, VertexFormat::Float32x4)
+        .attr(3, VertexFormat::Float32x4)
+}
+
+fn create_cached_pipeline(
+    device: &mut Device,
+    vertex: &ShaderSource,
+    fragment: &ShaderSource,
+    info: &VertexInfo,
+    textured: bool,
+) -> Result<Pipeline, String> {
+    let builder = device
+        .create_pipeline()
+        .from(vertex, fragment)
+        .with_vertex_info(info)
+        .with_color_blend(BlendMode::NORMAL);
+
+    if textured {
+        builder.with_texture_location(0, "u_texture").build()
+    } else {
+        builder.build()
+    }
+}
+
+/// Convert the 2d matrix to a 4x4 matrix to be used on the shaders
+fn mat3_to_mat4(m: &Mat3) -> Mat4 {
+    Mat4::from_cols(
+        Vec4::new(m.x_axis.x, m.x_axis.y, 0.0, 0.0),
+        Vec4::new(m.y_axis.x, m.y_axis.y, 0.0, 0.0),
+        Vec4::Z,
+        Vec4::new(m.z_axis.x, m.z_axis.y, 0.0, 1.0),
+    )
+}
+
+pub(crate) struct CachePainter {
+    shape_pipeline: Pipeline,
+    image_pipeline: Pipeline,
+    pattern_pipeline: Pipeline,
+    // each cached draw needs its own transform, so we keep a pool of buffers
+    ubos: Vec<Buffer>,
+    uniforms: Vec<[f32; 20]>,
+    count: usize,
+}
+
+impl CachePainter {
+    pub fn new(device: &mut Device) -> Result<Self, String> {
+        let shape_pipeline = create_cached_pipeline(
+            device,
+            &CACHED_SHAPES_VERTEX,
+            &SHAPES_FRAGMENT,
+            &shape_vertex_info(),
+            false,
+        )?;
+
+        let image_pipeline = create_cached_pipeline(
+            device,
+            &CACHED_IMAGE_VERTEX,
+            &IMAGE_FRAGMENT,
+            &image_vertex_info(),
+            true,
+        )?;
+
+        let pattern_pipeline = create_cached_pipeline(
+            device,
+            &CACHED_PATTERN_VERTEX,
+            &PATTERN_FRAGMENT,
+            &pattern_vertex_info(),
+            true,
+        )?;
+
+        Ok(Self {
+            shape_pipeline,
+            image_pipeline,
+            pattern_pipeline,
+            ubos: vec![],
+            uniforms: vec![],
+            count: 0,
+        })
+    }
+
+    pub fn push(
+        &mut self,
+        device: &mut Device,
+        renderer: &mut Renderer,
+        batch: &Batch,
+        projection: &Mat4,
+        is_rt: bool,
+    ) {
+        if let BatchType::Cached { data } = &batch.typ {
+            let CachedData {
+                cache,
+                transform,
+                color,
+            } = data;
+
+            let index = self.count;
+            if index >= self.ubos.len() {
+                let ubo = device
+                    .create_uniform_buffer(0, "Locals")
+                    .with_data(&[0.0f32; 20])
+                    .build();
+
+                match ubo {
+                    Ok(ubo) => {
+                        self.ubos.push(ubo);
+                        self.uniforms.push([0.0; 20]);
+                    }
+                    Err(err) => {
+                        log::error!("Cannot create the buffer for the cached draw: {}", err);
+                        return;
+                    }
+                }
+            }
+            self.count += 1;
+
+            let matrix = *projection * mat3_to_mat4(transform);
+            let uniforms = &mut self.uniforms[index];
+            uniforms[..16].copy_from_slice(&matrix.to_cols_array());
+            uniforms[16..].copy_from_slice(&color.rgba());
+
+            let ubo = &self.ubos[index];
+            cache.batches().iter().for_each(|cb| {
+                let (default_pipeline, texture) = match &cb.typ {
+                    CachedType::Shape => (&self.shape_pipeline, None),
+                    CachedType::Image { texture } => (&self.image_pipeline, Some(texture)),
+                    CachedType::Pattern { texture } => (&self.pattern_pipeline, Some(texture)),
+                };
+
+                set_pipeline(
+                    renderer,
+                    cb.pipeline.as_ref().unwrap_or(default_pipeline),
+                    batch.stencil,
+                    batch.blend_mode.or(cb.blend_mode),
+                    batch.alpha_mode.or(cb.alpha_mode),
+                    is_rt,
+                );
+
+                if let Some(buffers) = &cb.uniform_buffers {
+                    buffers.iter().for_each(|u| renderer.bind_buffer(u));
+                }
+
+                Material::bind_textures(&cb.textures, renderer);
+
+                if let Some(texture) = texture {
+                    renderer.bind_texture_slot(0, 0, texture);
+                }
+
+                renderer.bind_buffers(&[&cb.vbo, &cb.ebo, ubo]);
+                renderer.draw(0, cb.count as _);
+            });
+        }
+    }
+
+    #[inline]
+    pub fn upload_buffers(&mut self, device: &mut Device) {
+        self.ubos
+            .iter()
+            .zip(self.uniforms.iter())
+            .take(self.count)
+            .for_each(|(ubo, uniforms)| device.set_buffer_data(ubo, uniforms));
+    }
+
+    pub fn clear(&mut self) {
+        self.count = 0;
+    }
+}
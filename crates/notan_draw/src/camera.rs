@@ -0,0 +1,424 @@
+use notan_math::{vec2, vec3, Mat3, Mat4, Rect, Vec2};
+
+/// Defines how the work size is scaled to fit the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleMode {
+    /// No scale, the work size is ignored and the screen size is used instead
+    None,
+    /// Fill the screen without keeping the aspect ratio
+    Stretch,
+    /// Keep the aspect ratio showing all the content, adding bars on the sides if needed (letterbox)
+    #[default]
+    Fit,
+    /// Keep the aspect ratio filling the whole screen, cropping the content if needed
+    Fill,
+    /// Like `Fit` but using only integer scales, useful for pixel art
+    Integer,
+}
+
+impl ScaleMode {
+    /// Returns the scale to use for the work size on the screen size given
+    pub fn scale(&self, work_size: Vec2, screen_size: Vec2) -> Vec2 {
+        if work_size.x <= 0.0 || work_size.y <= 0.0 {
+            return Vec2::ONE;
+        }
+
+        let ratio = screen_size / work_size;
+        match self {
+            ScaleMode::None => Vec2::ONE,
+            ScaleMode::Stretch => ratio,
+            ScaleMode::Fit => Vec2::splat(ratio.x.min(ratio.y)),
+            ScaleMode::Fill => Vec2::splat(ratio.x.max(ratio.y)),
+            ScaleMode::Integer => Vec2::splat(ratio.x.min(ratio.y).floor().max(1.0)),
+        }
+    }
+
+    /// Returns the area of the screen (in pixels) used to display the work size
+    pub fn viewport(&self, work_size: Vec2, screen_size: Vec2) -> Rect {
+        if matches!(self, ScaleMode::None) {
+            return Rect {
+                x: 0.0,
+                y: 0.0,
+                width: screen_size.x,
+                height: screen_size.y,
+            };
+        }
+
+        let size = work_size * self.scale(work_size, screen_size);
+        let pos = (screen_size - size) * 0.5;
+        // integer positions to avoid blurry pixels
+        let pos = if matches!(self, ScaleMode::Integer) {
+            pos.floor()
+        } else {
+            pos
+        };
+        Rect {
+            x: pos.x,
+            y: pos.y,
+            width: size.x,
+            height: size.y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Shake {
+    intensity: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Shake {
+    fn offset(&self) -> Vec2 {
+        if self.elapsed >= self.duration {
+            return Vec2::ZERO;
+        }
+
+        // pseudo random noise mixing waves with different frequencies
+        let t = self.elapsed;
+        let force = self.intensity * (1.0 - t / self.duration);
+        let x = (t * 61.0).sin() * 0.6 + (t * 97.0).sin() * 0.4;
+        let y = (t * 73.0).cos() * 0.6 + (t * 89.0).cos() * 0.4;
+        vec2(x, y) * force
+    }
+}
+
+/// 2D Camera that calculates the projection and view for a `Draw`
+/// The position is the point of the world displayed at the center of the viewport.
+/// Using `ScaleMode::Fit` or `ScaleMode::Integer` the content outside the viewport is still drawn,
+/// use `camera.viewport()` to know where the bars are or use a `VirtualScreen` to clip it
+#[derive(Debug, Clone)]
+pub struct Camera2D {
+    position: Vec2,
+    zoom: f32,
+    rotation: f32,
+    work_size: Vec2,
+    screen_size: Vec2,
+    mode: ScaleMode,
+    bounds: Option<Rect>,
+    target: Option<Vec2>,
+    smoothing: f32,
+    shake: Shake,
+}
+
+impl Camera2D {
+    /// Create a new camera using the work size given, which is the visible size of the world with zoom 1.0
+    pub fn new(width: f32, height: f32) -> Self {
+        let size = vec2(width, height);
+        Self {
+            position: size * 0.5,
+            zoom: 1.0,
+            rotation: 0.0,
+            work_size: size,
+            screen_size: size,
+            mode: ScaleMode::default(),
+            bounds: None,
+            target: None,
+            smoothing: 0.0,
+            shake: Shake::default(),
+        }
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.position = vec2(x, y);
+        self.clamp_position();
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        debug_assert!(zoom > 0.0, "Camera zoom must be greater than 0");
+        self.zoom = zoom;
+        self.clamp_position();
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Set the rotation in radians
+    pub fn set_rotation(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    pub fn set_work_size(&mut self, width: f32, height: f32) {
+        self.work_size = vec2(width, height);
+        self.clamp_position();
+    }
+
+    pub fn work_size(&self) -> Vec2 {
+        self.work_size
+    }
+
+    /// Set the size of the screen or render texture where the camera is used
+    pub fn set_screen_size(&mut self, width: f32, height: f32) {
+        self.screen_size = vec2(width, height);
+        self.clamp_position();
+    }
+
+    pub fn screen_size(&self) -> Vec2 {
+        self.screen_size
+    }
+
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+        self.mode = mode;
+        self.clamp_position();
+    }
+
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.mode
+    }
+
+    /// Limits the camera movement to keep the visible area inside the bounds
+    pub fn set_bounds(&mut self, bounds: Option<Rect>) {
+        self.bounds = bounds;
+        self.clamp_position();
+    }
+
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// Point to follow when the camera is updated
+    pub fn follow(&mut self, x: f32, y: f32) {
+        self.target = Some(vec2(x, y));
+    }
+
+    /// Stop following the target
+    pub fn unfollow(&mut self) {
+        self.target = None;
+    }
+
+    pub fn target(&self) -> Option<Vec2> {
+        self.target
+    }
+
+    /// Time in seconds to reach (most of) the distance to the target, 0.0 means no smoothing
+    pub fn set_follow_smoothing(&mut self, seconds: f32) {
+        self.smoothing = seconds.max(0.0);
+    }
+
+    pub fn follow_smoothing(&self) -> f32 {
+        self.smoothing
+    }
+
+    /// Shake the camera using the intensity (in world units) and duration (in seconds) given
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        self.shake = Shake {
+            intensity,
+            duration,
+            elapsed: 0.0,
+        };
+    }
+
+    /// Returns true while the camera is shaking
+    pub fn is_shaking(&self) -> bool {
+        self.shake.elapsed < self.shake.duration
+    }
+
+    /// Move the camera to the target and update the shake effect
+    pub fn update(&mut self, delta: f32) {
+        if let Some(target) = self.target {
+            self.position = if self.smoothing > 0.0 {
+                let t = 1.0 - (-delta / self.smoothing).exp();
+                self.position.lerp(target, t)
+            } else {
+                target
+            };
+        }
+
+        if self.is_shaking() {
+            self.shake.elapsed += delta;
+        }
+
+        self.clamp_position();
+    }
+
+    /// Size of the world visible at zoom 1.0 taking in account the scale mode
+    fn base_visible_size(&self) -> Vec2 {
+        match self.mode {
+            ScaleMode::None => self.screen_size,
+            ScaleMode::Fill => self.screen_size / self.mode.scale(self.work_size, self.screen_size),
+            _ => self.work_size,
+        }
+    }
+
+    /// Size of the world visible (without the rotation)
+    pub fn visible_size(&self) -> Vec2 {
+        self.base_visible_size() / self.zoom
+    }
+
+    /// Area of the world visible (without the rotation)
+    pub fn visible_area(&self) -> Rect {
+        let size = self.visible_size();
+        let pos = self.position - size * 0.5;
+        Rect {
+            x: pos.x,
+            y: pos.y,
+            width: size.x,
+            height: size.y,
+        }
+    }
+
+    /// Area of the screen (in pixels) where the work size is displayed
+    pub fn viewport(&self) -> Rect {
+        self.mode.viewport(self.work_size, self.screen_size)
+    }
+
+    fn clamp_position(&mut self) {
+        let bounds = match self.bounds {
+            Some(b) => b,
+            None => return,
+        };
+
+        let half = self.visible_size() * 0.5;
+        let clamp = |value: f32, min: f32, max: f32| {
+            if min > max {
+                (min + max) * 0.5
+            } else {
+                value.clamp(min, max)
+            }
+        };
+
+        self.position = vec2(
+            clamp(
+                self.position.x,
+                bounds.min_x() + half.x,
+                bounds.max_x() - half.x,
+            ),
+            clamp(
+                self.position.y,
+                bounds.min_y() + half.y,
+                bounds.max_y() - half.y,
+            ),
+        );
+    }
+
+    /// Matrix to convert from the work space to the screen space
+    fn screen_matrix(&self) -> Mat3 {
+        if matches!(self.mode, ScaleMode::None) {
+            return Mat3::IDENTITY;
+        }
+
+        let scale = self.mode.scale(self.work_size, self.screen_size);
+        let viewport = self.viewport();
+        Mat3::from_translation(vec2(viewport.x, viewport.y)) * Mat3::from_scale(scale)
+    }
+
+    /// Returns the view matrix, which converts from world to work space
+    pub fn view(&self) -> Mat3 {
+        // the center of the work space is always at the center of the viewport
+        let center = match self.mode {
+            ScaleMode::None => self.screen_size * 0.5,
+            _ => self.work_size * 0.5,
+        };
+
+        let position = self.position + self.shake.offset();
+        Mat3::from_translation(center)
+            * Mat3::from_angle(-self.rotation)
+            * Mat3::from_scale(Vec2::splat(self.zoom))
+            * Mat3::from_translation(-position)
+    }
+
+    /// Returns the projection to use with the screen size
+    pub fn projection(&self) -> Mat4 {
+        let projection =
+            Mat4::orthographic_rh_gl(0.0, self.screen_size.x, self.screen_size.y, 0.0, -1.0, 1.0);
+
+        let m = self.screen_matrix();
+        let screen = Mat4::from_cols(
+            m.x_axis.extend(0.0),
+            m.y_axis.extend(0.0),
+            vec3(0.0, 0.0, 1.0).extend(0.0),
+            vec3(m.z_axis.x, m.z_axis.y, 0.0).extend(1.0),
+        );
+
+        projection * screen
+    }
+
+    /// Converts a screen position (like the mouse position) to world coordinates
+    pub fn screen_to_world(&self, x: f32, y: f32) -> Vec2 {
+        let matrix = self.screen_matrix() * self.view();
+        matrix.inverse().transform_point2(vec2(x, y))
+    }
+
+    /// Converts a world position to screen coordinates
+    pub fn world_to_screen(&self, x: f32, y: f32) -> Vec2 {
+        let matrix = self.screen_matrix() * self.view();
+        matrix.transform_point2(vec2(x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 0.001, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn scale_mode_viewport() {
+        let work = vec2(320.0, 180.0);
+        let screen = vec2(1000.0, 800.0);
+
+        let fit = ScaleMode::Fit.viewport(work, screen);
+        assert_eq!((fit.width, fit.height), (1000.0, 562.5));
+        assert_eq!(fit.y, 118.75);
+
+        let int = ScaleMode::Integer.viewport(work, screen);
+        assert_eq!(
+            (int.x, int.y, int.width, int.height),
+            (20.0, 130.0, 960.0, 540.0)
+        );
+
+        let stretch = ScaleMode::Stretch.viewport(work, screen);
+        assert_near(vec2(stretch.width, stretch.height), screen);
+    }
+
+    #[test]
+    fn screen_world_conversion() {
+        let mut cam = Camera2D::new(400.0, 300.0);
+        cam.set_screen_size(800.0, 800.0);
+        cam.set_position(1000.0, 500.0);
+        cam.set_zoom(2.0);
+
+        // the center of the screen is the camera position
+        assert_near(cam.screen_to_world(400.0, 400.0), vec2(1000.0, 500.0));
+        assert_near(cam.world_to_screen(1000.0, 500.0), vec2(400.0, 400.0));
+
+        cam.set_rotation(0.5);
+        let p = cam.screen_to_world(123.0, 456.0);
+        assert_near(cam.world_to_screen(p.x, p.y), vec2(123.0, 456.0));
+
+        cam.set_scale_mode(ScaleMode::Fill);
+        assert_near(cam.screen_to_world(400.0, 400.0), vec2(1000.0, 500.0));
+    }
+
+    #[test]
+    fn follow_and_bounds() {
+        let mut cam = Camera2D::new(100.0, 100.0);
+        cam.set_bounds(Some(Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 500.0,
+            height: 500.0,
+        }));
+
+        cam.follow(10.0, 490.0);
+        cam.update(0.016);
+        assert_near(cam.position(), vec2(50.0, 450.0));
+
+        cam.set_follow_smoothing(1.0);
+        cam.follow(250.0, 250.0);
+        cam.update(0.1);
+        let pos = cam.position();
+        assert!(pos.x > 50.0 && pos.x < 250.0);
+    }
+}
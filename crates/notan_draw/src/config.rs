@@ -9,6 +9,9 @@ where
     B: BackendSystem,
 {
     fn apply(&self, builder: AppBuilder<S, B>) -> AppBuilder<S, B> {
+        #[cfg(feature = "tilemap")]
+        let builder = builder.add_loader(crate::create_tiled_map_loader());
+
         builder.add_graphic_ext(|gfx: &mut Graphics| {
             // Add text extension if necessary
             if gfx.extension::<Text, TextExtension>().is_none() {
@@ -1,12 +1,16 @@
 use crate::batch::*;
+use crate::cache::DrawCache;
+use crate::camera::Camera2D;
 pub(crate) use crate::custom_pipeline::CustomPipeline;
+use crate::images::ImageInstances;
+use crate::material::Material;
 use crate::transform::Transform;
 use crate::{local_to_screen_position, screen_to_local_position};
 use notan_glyph::Section;
 use notan_graphics::color::Color;
 use notan_graphics::prelude::*;
 use notan_math::{vec2, Mat3, Mat4, Rect, Vec2};
-use notan_text::{Calculator, Font};
+use notan_text::{Calculator, Font, TextLayout};
 
 #[derive(Debug)]
 pub struct Draw {
@@ -26,10 +30,25 @@ pub struct Draw {
     pub(crate) pattern_pipeline: CustomPipeline,
     pub(crate) text_pipeline: CustomPipeline,
     pub(crate) text_batch_indices: Option<Vec<usize>>,
-    pub(crate) masking: bool,
     pub(crate) needs_to_clean_stencil: bool,
     pub(crate) glyphs_calculator: Calculator,
-    mask_batches: Option<Vec<Batch>>,
+    mask_stack: Vec<MaskLevel>,
+    clip_stack: Vec<ClipLevel>,
+}
+
+#[derive(Clone, Debug)]
+struct MaskLevel {
+    batches: Vec<Batch>,
+    // inverted masks use a quad covering the visible area to write the stencil outside the mask
+    area: Option<Batch>,
+    clip: Option<Rect>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ClipLevel {
+    Scissors(Rect),
+    // mask depth after pushing the clip's mask
+    Mask(usize),
 }
 
 impl Clone for Draw {
@@ -50,11 +69,11 @@ impl Clone for Draw {
             image_pipeline: self.image_pipeline.clone(),
             pattern_pipeline: self.pattern_pipeline.clone(),
             text_pipeline: self.text_pipeline.clone(),
-            masking: self.masking,
             needs_to_clean_stencil: self.needs_to_clean_stencil,
             text_batch_indices: self.text_batch_indices.clone(),
             glyphs_calculator: Calculator::new(),
-            mask_batches: self.mask_batches.clone(),
+            mask_stack: self.mask_stack.clone(),
+            clip_stack: self.clip_stack.clone(),
         }
     }
 }
@@ -80,11 +99,11 @@ impl Draw {
             image_pipeline: Default::default(),
             pattern_pipeline: Default::default(),
             text_pipeline: Default::default(),
-            masking: false,
             needs_to_clean_stencil: false,
             text_batch_indices: None,
             glyphs_calculator: Calculator::new(),
-            mask_batches: None,
+            mask_stack: vec![],
+            clip_stack: vec![],
         }
     }
 
@@ -94,58 +113,247 @@ impl Draw {
         self.transform.matrix()
     }
 
-    fn process_mask_batches(&mut self) {
-        if let Some(mask_batches) = self.mask_batches.take() {
-            //Move the current batch to the queue
-            if let Some(b) = self.current_batch.take() {
-                self.batches.push(b);
+    /// Set a mask using the content of another draw, only the pixels inside of it will be visible
+    /// Passing `None` removes the last mask set. Masks can be nested using `push_mask` and `pop_mask`.
+    pub fn mask(&mut self, mask: Option<&Self>) {
+        match mask {
+            Some(m) => self.push_mask(m),
+            None => self.pop_mask(),
+        }
+    }
+
+    /// Push a mask using the content of another draw, only the pixels inside of it and inside the
+    /// previous masks will be visible until `pop_mask` is called
+    pub fn push_mask(&mut self, mask: &Self) {
+        let batches = mask_batches(mask);
+        self.add_mask(batches, false);
+    }
+
+    /// Push a mask using the content of another draw, only the pixels outside of it and inside the
+    /// previous masks will be visible until `pop_mask` is called
+    pub fn push_inverted_mask(&mut self, mask: &Self) {
+        let batches = mask_batches(mask);
+        self.add_mask(batches, true);
+    }
+
+    /// Removes the last mask pushed
+    pub fn pop_mask(&mut self) {
+        match self.mask_stack.pop() {
+            Some(level) => self.remove_mask(level),
+            None => {
+                #[cfg(debug_assertions)]
+                {
+                    log::warn!("Draw removing a mask when there is no mask set is a no-op");
+                }
             }
+        }
+    }
 
-            self.batches.extend(mask_batches.iter().map(|batch| {
-                let mut b = batch.clone();
-                b.is_mask = true;
-                b
-            }));
+    /// Number of masks nested
+    pub fn mask_depth(&self) -> usize {
+        self.mask_stack.len()
+    }
+
+    fn add_mask(&mut self, batches: Vec<Batch>, inverted: bool) {
+        debug_assert!(self.mask_stack.len() < 127, "Too many nested masks.");
+
+        self.flush_batch();
+        self.needs_to_clean_stencil = true;
+
+        // the visible pixels of each level have the value of the level on the stencil buffer,
+        // normal masks increment the value of the visible pixels of the parent inside the mask,
+        // inverted masks set the pixels inside the mask to a value out of the levels range
+        // and increment the rest of the visible area
+        let parent = self.mask_stack.len() as u8;
+        let clip = self.current_clip();
+        let area = if inverted {
+            self.push_stencil_batches(&batches, parent, StencilAction::Invert, clip);
+            let area = self.visible_area_batch();
+            self.push_stencil_batches(
+                std::slice::from_ref(&area),
+                parent,
+                StencilAction::Increment,
+                clip,
+            );
+            Some(area)
+        } else {
+            self.push_stencil_batches(&batches, parent, StencilAction::Increment, clip);
+            None
+        };
+
+        self.mask_stack.push(MaskLevel {
+            batches,
+            area,
+            clip,
+        });
+    }
+
+    fn remove_mask(&mut self, level: MaskLevel) {
+        self.flush_batch();
+
+        // restore the stencil values to the parent level
+        let MaskLevel {
+            batches,
+            area,
+            clip,
+        } = level;
+        let parent = self.mask_stack.len() as u8;
+        match area {
+            Some(area) => {
+                self.push_stencil_batches(
+                    std::slice::from_ref(&area),
+                    parent + 1,
+                    StencilAction::Decrement,
+                    clip,
+                );
+                self.push_stencil_batches(&batches, !parent, StencilAction::Invert, clip);
+            }
+            None => {
+                self.push_stencil_batches(&batches, parent + 1, StencilAction::Decrement, clip);
+            }
         }
     }
 
-    pub fn mask(&mut self, mask: Option<&Self>) {
-        debug_assert!(!(self.masking && mask.is_some()), "Already using mask.");
+    fn push_stencil_batches(
+        &mut self,
+        batches: &[Batch],
+        reference: u8,
+        action: StencilAction,
+        clip: Option<Rect>,
+    ) {
+        self.batches.extend(batches.iter().map(|b| {
+            let mut b = b.clone();
+            b.stencil = StencilMode::Write { reference, action };
+            b.clip = clip;
+            b
+        }));
+    }
 
-        match mask {
-            Some(m) if !self.masking => {
-                let mut mask_batches = m.batches.clone();
-                if let Some(b) = m.current_batch.as_ref() {
-                    mask_batches.push(b.clone());
+    fn stencil_mode(&self) -> StencilMode {
+        match self.mask_stack.len() {
+            0 => StencilMode::None,
+            n => StencilMode::Test(n as _),
+        }
+    }
+
+    // Shape batch covering the area visible with the current projection
+    fn visible_area_batch(&mut self) -> Batch {
+        let inverse = *self
+            .inverse_projection
+            .get_or_insert(self.projection().inverse());
+        let size: Vec2 = self.size.into();
+        let [a, b, c, d] = [(0.0, 0.0), (size.x, 0.0), (size.x, size.y), (0.0, size.y)]
+            .map(|(x, y)| screen_to_local_position(vec2(x, y), size, inverse, Mat3::IDENTITY));
+        quad_batch([a, b, c, d], Mat3::IDENTITY)
+    }
+
+    /// Set a clip rectangle, only the pixels inside of it and inside the previous clips will be
+    /// visible until `pop_clip` is called. The rectangle is affected by the transformation matrix,
+    /// it uses scissors if it's aligned to the screen, otherwise it fallbacks to a stencil mask.
+    pub fn push_clip(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.flush_batch();
+
+        let matrix = *self.transform.matrix();
+        let points = [
+            (x, y),
+            (x + width, y),
+            (x + width, y + height),
+            (x, y + height),
+        ]
+        .map(|(x, y)| vec2(x, y));
+        match self.screen_rect(points, matrix) {
+            Some(rect) => {
+                // an empty rect is kept with a zero size so nothing is drawn
+                let parent = self.current_clip().unwrap_or(Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: self.size.0,
+                    height: self.size.1,
+                });
+                let rect = intersect_rect(&parent, &rect);
+                self.clip_stack.push(ClipLevel::Scissors(rect));
+            }
+            None => {
+                self.add_mask(vec![quad_batch(points, matrix)], false);
+                self.clip_stack.push(ClipLevel::Mask(self.mask_stack.len()));
+            }
+        }
+    }
+
+    /// Removes the last clip pushed
+    pub fn pop_clip(&mut self) {
+        match self.clip_stack.pop() {
+            Some(ClipLevel::Scissors(_)) => self.flush_batch(),
+            Some(ClipLevel::Mask(depth)) => {
+                if self.mask_stack.len() < depth {
+                    #[cfg(debug_assertions)]
+                    {
+                        log::warn!(
+                            "Draw removing a clip whose mask was already removed is a no-op"
+                        );
+                    }
+                    return;
                 }
 
-                if !mask_batches.is_empty() {
-                    self.masking = true;
-                    self.mask_batches = Some(mask_batches);
+                // masks pushed after the clip can't outlive it, they're removed with it
+                #[cfg(debug_assertions)]
+                if self.mask_stack.len() > depth {
+                    log::warn!(
+                        "Draw removing a clip with {} masks pushed after it, removing them too",
+                        self.mask_stack.len() - depth
+                    );
                 }
-            }
-            None if self.masking => {
-                self.masking = false;
-                self.mask_batches = None;
 
-                //Move the current batch to the queue
-                if let Some(b) = self.current_batch.take() {
-                    self.batches.push(b);
+                while self.mask_stack.len() >= depth {
+                    self.pop_mask();
                 }
             }
-            _ => {
+            None => {
                 #[cfg(debug_assertions)]
                 {
-                    log::warn!(
-                        "Draw setting mask as: {:?} when the value is {:?} is a no-op",
-                        mask.is_some(),
-                        self.masking
-                    );
+                    log::warn!("Draw removing a clip when there is no clip set is a no-op");
                 }
             }
         }
     }
 
+    fn current_clip(&self) -> Option<Rect> {
+        self.clip_stack.iter().rev().find_map(|c| match c {
+            ClipLevel::Scissors(rect) => Some(*rect),
+            ClipLevel::Mask(_) => None,
+        })
+    }
+
+    // Returns the rectangle on screen if the points are aligned to the screen axes
+    fn screen_rect(&self, points: [Vec2; 4], matrix: Mat3) -> Option<Rect> {
+        let size: Vec2 = self.size.into();
+        let projection = self.projection();
+        let [a, b, c, d] = points.map(|p| local_to_screen_position(p, size, projection, matrix));
+
+        let near = |v1: f32, v2: f32| (v1 - v2).abs() < 0.001;
+        let horizontal = near(a.y, b.y) && near(b.x, c.x) && near(c.y, d.y) && near(d.x, a.x);
+        let vertical = near(a.x, b.x) && near(b.y, c.y) && near(c.x, d.x) && near(d.y, a.y);
+        if !(horizontal || vertical) {
+            return None;
+        }
+
+        // scissors use whole pixels
+        let min = a.min(c).round();
+        let max = a.max(c).round();
+        Some(Rect {
+            x: min.x,
+            y: min.y,
+            width: max.x - min.x,
+            height: max.y - min.y,
+        })
+    }
+
+    fn flush_batch(&mut self) {
+        if let Some(b) = self.current_batch.take() {
+            self.batches.push(b);
+        }
+    }
+
     pub fn set_size(&mut self, width: f32, height: f32) {
         self.size = (width, height);
         self.base_projection = Mat4::orthographic_rh_gl(0.0, width, height, 0.0, -1.0, 1.0);
@@ -172,6 +380,12 @@ impl Draw {
         self.projection.unwrap_or(self.base_projection)
     }
 
+    /// Set the camera's projection and set its view as the base matrix of the transform stack
+    pub fn set_camera(&mut self, camera: &Camera2D) {
+        self.set_projection(Some(camera.projection()));
+        self.transform.set(camera.view());
+    }
+
     pub fn set_alpha(&mut self, alpha: f32) {
         self.alpha = alpha;
     }
@@ -210,11 +424,6 @@ impl Draw {
         F1: Fn(&Batch, &I) -> bool,
         F2: Fn(&I) -> BatchType,
     {
-        if self.masking {
-            self.needs_to_clean_stencil = true;
-            self.process_mask_batches();
-        }
-
         let needs_new_batch = needs_new_batch(self, info, is_diff_type);
         if needs_new_batch {
             if let Some(old) = self.current_batch.take() {
@@ -227,6 +436,17 @@ impl Draw {
                 BatchType::Pattern { .. } => &self.pattern_pipeline,
                 BatchType::Shape => &self.shape_pipeline,
                 BatchType::Text { .. } => &self.text_pipeline,
+                BatchType::Cached { .. } => unreachable!("Cached batches use 'add_cache'"),
+                BatchType::Instanced { .. } => {
+                    unreachable!("Instanced batches use 'add_instanced'")
+                }
+            };
+
+            // the element's material has priority over the custom pipeline
+            let material = info.material();
+            let (pipeline, uniform_buffers) = match material {
+                Some(m) => (Some(m.pipeline().clone()), Some(m.uniform_buffers())),
+                None => (custom.pipeline.clone(), custom.uniforms.clone()),
             };
 
             // blending modes, by priority:
@@ -244,12 +464,13 @@ impl Draw {
                 typ: create_type(info),
                 vertices: vec![],
                 indices: vec![],
-                pipeline: custom.pipeline.clone(),
-                uniform_buffers: custom.uniforms.clone(),
+                pipeline,
+                uniform_buffers,
+                material: material.cloned(),
                 blend_mode: cbm,
                 alpha_mode: abm,
-                is_mask: false,
-                masking: self.masking,
+                stencil: self.stencil_mode(),
+                clip: self.current_clip(),
             });
         }
 
@@ -308,7 +529,12 @@ impl Draw {
     }
 
     pub fn add_text(&mut self, info: &TextInfo) {
-        let is_diff_type = |b: &Batch, _: &TextInfo| !b.is_text();
+        // the texts with outline or shadow use another vertex layout
+        let is_diff_type = |b: &Batch, i: &TextInfo| {
+            !b.is_text()
+                || matches!(&b.typ, BatchType::Text { texts }
+                    if texts.last().is_some_and(|t| t.has_effects() != i.has_effects()))
+        };
         let create_type = |_: &TextInfo| BatchType::Text { texts: vec![] };
 
         self.add_batch(info, is_diff_type, create_type);
@@ -329,6 +555,9 @@ impl Draw {
                     alpha: self.alpha,
                     count: info.count,
                     flip: info.flip,
+                    effects: info.glyph_effects.map(|fx| fx.to_vec()),
+                    outline: info.outline,
+                    shadow: info.shadow,
                 });
             }
         }
@@ -338,6 +567,66 @@ impl Draw {
         indices.push(batch_len);
     }
 
+    pub fn add_cache(&mut self, info: &CacheInfo) {
+        self.flush_batch();
+
+        let global_matrix = *self.transform.matrix();
+        let transform = match info.transform {
+            Some(m) => global_matrix * *m,
+            _ => global_matrix,
+        };
+
+        let color = info.color;
+        self.current_batch = Some(Batch {
+            typ: BatchType::Cached {
+                data: CachedData {
+                    cache: info.cache.clone(),
+                    transform,
+                    color: color.with_alpha(color.a * self.alpha),
+                },
+            },
+            vertices: vec![],
+            indices: vec![],
+            pipeline: None,
+            uniform_buffers: None,
+            material: None,
+            blend_mode: info.blend_mode,
+            alpha_mode: info.alpha_mode,
+            stencil: self.stencil_mode(),
+            clip: self.current_clip(),
+        });
+    }
+
+    pub fn add_instanced(&mut self, info: &InstancedInfo) {
+        self.flush_batch();
+
+        let global_matrix = *self.transform.matrix();
+        let transform = match info.transform {
+            Some(m) => global_matrix * *m,
+            _ => global_matrix,
+        };
+
+        let color = info.color;
+        self.current_batch = Some(Batch {
+            typ: BatchType::Instanced {
+                data: InstancedData {
+                    instances: info.instances.clone(),
+                    transform,
+                    color: color.with_alpha(color.a * self.alpha),
+                },
+            },
+            vertices: vec![],
+            indices: vec![],
+            pipeline: None,
+            uniform_buffers: None,
+            material: None,
+            blend_mode: info.blend_mode,
+            alpha_mode: info.alpha_mode,
+            stencil: self.stencil_mode(),
+            clip: self.current_clip(),
+        });
+    }
+
     /// Get the bounds of the last text immediately after draw it
     /// The bounds doesn't take in account the Transformation matrix
     pub fn last_text_bounds(&mut self) -> Rect {
@@ -359,6 +648,28 @@ impl Draw {
         Rect::default()
     }
 
+    /// Get the layout of the last text immediately after draw it, useful to place carets,
+    /// select text or check which character is under the mouse.
+    /// The layout doesn't take in account the Transformation matrix
+    pub fn last_text_layout(&mut self) -> TextLayout {
+        if let Some(batch) = &self.current_batch {
+            if let BatchType::Text { texts } = &batch.typ {
+                if let Some(text) = texts.last() {
+                    return self.glyphs_calculator.layout(&text.section.to_borrowed());
+                }
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            log::debug!(
+                "'draw.last_text_layout()' must be called immediately after 'draw.text(..)"
+            );
+        }
+
+        TextLayout::default()
+    }
+
     pub fn screen_to_world_position(&mut self, screen_x: f32, screen_y: f32) -> Vec2 {
         let inverse = *self
             .inverse_projection
@@ -381,6 +692,7 @@ trait DrawInfo {
     fn indices(&self) -> &[u32];
     fn blend_mode(&self) -> Option<BlendMode>;
     fn alpha_mode(&self) -> Option<BlendMode>;
+    fn material(&self) -> Option<&Material>;
 }
 
 /// Information to render the image or pattern
@@ -391,6 +703,7 @@ pub struct ImageInfo<'a> {
     pub indices: &'a [u32],
     pub blend_mode: Option<BlendMode>,
     pub alpha_mode: Option<BlendMode>,
+    pub material: Option<&'a Material>,
 }
 
 impl DrawInfo for ImageInfo<'_> {
@@ -413,6 +726,10 @@ impl DrawInfo for ImageInfo<'_> {
     fn alpha_mode(&self) -> Option<BlendMode> {
         self.alpha_mode
     }
+
+    fn material(&self) -> Option<&Material> {
+        self.material
+    }
 }
 
 /// Information to render the shape
@@ -422,6 +739,7 @@ pub struct ShapeInfo<'a> {
     pub indices: &'a [u32],
     pub blend_mode: Option<BlendMode>,
     pub alpha_mode: Option<BlendMode>,
+    pub material: Option<&'a Material>,
 }
 
 impl DrawInfo for ShapeInfo<'_> {
@@ -444,6 +762,10 @@ impl DrawInfo for ShapeInfo<'_> {
     fn alpha_mode(&self) -> Option<BlendMode> {
         self.alpha_mode
     }
+
+    fn material(&self) -> Option<&Material> {
+        self.material
+    }
 }
 
 pub struct TextInfo<'a> {
@@ -454,6 +776,40 @@ pub struct TextInfo<'a> {
     pub blend_mode: Option<BlendMode>,
     pub alpha_mode: Option<BlendMode>,
     pub flip: (bool, bool),
+    /// Effect applied to each glyph
+    pub glyph_effects: Option<&'a [GlyphEffect]>,
+    /// Outline color and width in pixels
+    pub outline: Option<(Color, f32)>,
+    /// Shadow color, offset and blur in pixels
+    pub shadow: Option<(Color, Vec2, f32)>,
+}
+
+impl TextInfo<'_> {
+    pub(crate) fn has_effects(&self) -> bool {
+        self.outline.is_some() || self.shadow.is_some()
+    }
+}
+
+/// Transformation applied to a glyph before it's drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphEffect {
+    pub offset: Vec2,
+    /// Rotation in radians around the center of the glyph
+    pub rotation: f32,
+    /// Replaces the color of the glyph
+    pub color: Option<Color>,
+    pub alpha: f32,
+}
+
+impl Default for GlyphEffect {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            rotation: 0.0,
+            color: None,
+            alpha: 1.0,
+        }
+    }
 }
 
 impl DrawInfo for TextInfo<'_> {
@@ -476,6 +832,28 @@ impl DrawInfo for TextInfo<'_> {
     fn alpha_mode(&self) -> Option<BlendMode> {
         self.alpha_mode
     }
+
+    fn material(&self) -> Option<&Material> {
+        None
+    }
+}
+
+/// Information to render a cached geometry
+pub struct CacheInfo<'a> {
+    pub cache: &'a DrawCache,
+    pub transform: Option<&'a Mat3>,
+    pub color: Color,
+    pub blend_mode: Option<BlendMode>,
+    pub alpha_mode: Option<BlendMode>,
+}
+
+/// Information to render instanced images
+pub struct InstancedInfo<'a> {
+    pub instances: &'a ImageInstances,
+    pub transform: Option<&'a Mat3>,
+    pub color: Color,
+    pub blend_mode: Option<BlendMode>,
+    pub alpha_mode: Option<BlendMode>,
 }
 
 fn needs_new_batch<I: DrawInfo, F: Fn(&Batch, &I) -> bool>(
@@ -497,10 +875,23 @@ fn needs_new_batch<I: DrawInfo, F: Fn(&Batch, &I) -> bool>(
                 BatchType::Pattern { .. } => &draw.pattern_pipeline,
                 BatchType::Shape => &draw.shape_pipeline,
                 BatchType::Text { .. } => &draw.text_pipeline,
+                // cached geometry and instances are never extended
+                BatchType::Cached { .. } | BatchType::Instanced { .. } => return true,
             };
 
-            if b.pipeline.as_ref() != custom.pipeline.as_ref() {
-                return true;
+            // elements with a material only share the batch with the same material
+            match (&b.material, info.material()) {
+                (None, None) => {
+                    if b.pipeline.as_ref() != custom.pipeline.as_ref() {
+                        return true;
+                    }
+                }
+                (Some(current), Some(material)) => {
+                    if current != material {
+                        return true;
+                    }
+                }
+                _ => return true,
             }
 
             // new batch if the blend_mode is different
@@ -525,3 +916,180 @@ fn needs_new_batch<I: DrawInfo, F: Fn(&Batch, &I) -> bool>(
         }
     }
 }
+
+fn mask_batches(mask: &Draw) -> Vec<Batch> {
+    mask.batches
+        .iter()
+        .chain(mask.current_batch.iter())
+        .cloned()
+        .collect()
+}
+
+// White shape batch with the points given
+fn quad_batch(points: [Vec2; 4], matrix: Mat3) -> Batch {
+    let [a, b, c, d] = points;
+    #[rustfmt::skip]
+    let vertices = [
+        a.x, a.y, 1.0, 1.0, 1.0, 1.0,
+        b.x, b.y, 1.0, 1.0, 1.0, 1.0,
+        c.x, c.y, 1.0, 1.0, 1.0, 1.0,
+        d.x, d.y, 1.0, 1.0, 1.0, 1.0,
+    ];
+
+    let mut batch = Batch {
+        typ: BatchType::Shape,
+        vertices: vec![],
+        indices: vec![],
+        pipeline: None,
+        uniform_buffers: None,
+        material: None,
+        blend_mode: None,
+        alpha_mode: None,
+        stencil: StencilMode::None,
+        clip: None,
+    };
+    batch.add(&[0, 1, 2, 0, 2, 3], &vertices, matrix, 1.0);
+    batch
+}
+
+fn intersect_rect(r1: &Rect, r2: &Rect) -> Rect {
+    let x = r1.x.max(r2.x);
+    let y = r1.y.max(r2.y);
+    let width = (r1.max_x().min(r2.max_x()) - x).max(0.0);
+    let height = (r1.max_y().min(r2.max_y()) - y).max(0.0);
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nested_masks_and_clips() {
+        let mut mask = Draw::new(800, 600);
+        mask.add_shape(&ShapeInfo {
+            transform: None,
+            vertices: &[0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+            indices: &[0],
+            blend_mode: None,
+            alpha_mode: None,
+            material: None,
+        });
+
+        let mut draw = Draw::new(800, 600);
+        draw.push_mask(&mask);
+        draw.push_inverted_mask(&mask);
+        assert_eq!(draw.stencil_mode(), StencilMode::Test(2));
+
+        let stencil = draw.batches.iter().map(|b| b.stencil).collect::<Vec<_>>();
+        assert_eq!(
+            stencil,
+            [
+                StencilMode::Write {
+                    reference: 0,
+                    action: StencilAction::Increment
+                },
+                StencilMode::Write {
+                    reference: 1,
+                    action: StencilAction::Invert
+                },
+                StencilMode::Write {
+                    reference: 1,
+                    action: StencilAction::Increment
+                },
+            ]
+        );
+
+        draw.pop_mask();
+        draw.pop_mask();
+        assert_eq!(draw.mask_depth(), 0);
+        assert_eq!(draw.batches.len(), 6);
+
+        // aligned clips use scissors and are intersected
+        draw.push_clip(100.0, 100.0, 200.0, 200.0);
+        draw.push_clip(200.0, 50.0, 200.0, 200.0);
+        assert_eq!(
+            draw.current_clip(),
+            Some(Rect {
+                x: 200.0,
+                y: 100.0,
+                width: 100.0,
+                height: 150.0
+            })
+        );
+
+        // rotated clips fallback to masks
+        draw.transform().push(Mat3::from_angle(0.5));
+        draw.push_clip(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(draw.mask_depth(), 1);
+        draw.pop_clip();
+        assert_eq!(draw.mask_depth(), 0);
+    }
+
+    #[test]
+    fn clips_inside_bounds() {
+        // clips past the edges are clamped to the draw size
+        let mut draw = Draw::new(800, 600);
+        draw.push_clip(-50.0, 500.0, 200.0, 200.0);
+        assert_eq!(
+            draw.current_clip(),
+            Some(Rect {
+                x: 0.0,
+                y: 500.0,
+                width: 150.0,
+                height: 100.0
+            })
+        );
+        draw.pop_clip();
+
+        // clips that don't overlap are empty
+        draw.push_clip(0.0, 0.0, 100.0, 100.0);
+        draw.push_clip(200.0, 200.0, 50.0, 50.0);
+        let clip = draw.current_clip().unwrap();
+        assert_eq!((clip.width, clip.height), (0.0, 0.0));
+    }
+
+    #[test]
+    fn pop_clip_with_masks_after_it() {
+        let mut mask = Draw::new(800, 600);
+        mask.add_shape(&ShapeInfo {
+            transform: None,
+            vertices: &[0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+            indices: &[0],
+            blend_mode: None,
+            alpha_mode: None,
+            material: None,
+        });
+
+        let mut draw = Draw::new(800, 600);
+        draw.push_mask(&mask);
+
+        // a rotated clip is a mask on top of the user's mask
+        draw.transform().push(Mat3::from_angle(0.5));
+        draw.push_clip(0.0, 0.0, 100.0, 100.0);
+        draw.transform().pop();
+        assert_eq!(draw.mask_depth(), 2);
+
+        // the masks pushed after the clip are removed with it, the previous ones are kept
+        draw.push_mask(&mask);
+        assert_eq!(draw.mask_depth(), 3);
+        draw.pop_clip();
+        assert_eq!(draw.mask_depth(), 1);
+        assert_eq!(draw.stencil_mode(), StencilMode::Test(1));
+
+        // the clip's mask is not removed twice if the user removed it
+        draw.transform().push(Mat3::from_angle(0.5));
+        draw.push_clip(0.0, 0.0, 100.0, 100.0);
+        draw.transform().pop();
+        draw.pop_mask();
+        draw.pop_mask();
+        assert_eq!(draw.mask_depth(), 0);
+        draw.pop_clip();
+        assert_eq!(draw.mask_depth(), 0);
+    }
+}
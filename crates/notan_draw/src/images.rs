@@ -4,15 +4,15 @@ mod instanced;
 mod nine_slice;
 mod painter;
 
-//pub use instanced::*;
 use crate::builder::DrawBuilder;
 use crate::draw::Draw;
 pub use animation::*;
 pub use image::*;
+pub use instanced::*;
 pub use nine_slice::*;
 use notan_graphics::Texture;
-pub use painter::create_image_pipeline;
 pub(crate) use painter::*;
+pub use painter::{create_image_pipeline, create_instanced_image_pipeline};
 
 pub trait DrawImages {
     fn image<'a>(&mut self, texture: &'a Texture) -> DrawBuilder<Image<'a>>;
@@ -24,7 +24,10 @@ pub trait DrawImages {
         rows: usize,
     ) -> DrawBuilder<ImageAnimation<'a>>;
     fn animation_list<'a>(&mut self, list: &'a [&'a Texture]) -> DrawBuilder<ImageAnimation<'a>>;
-    //fn instanced_image<'a>(&mut self, texture: &'a Texture) -> DrawBuilder<InstancedImage<'a>>;
+    fn instanced_image<'a>(
+        &mut self,
+        instances: &'a ImageInstances,
+    ) -> DrawBuilder<InstancedImage<'a>>;
 }
 
 impl DrawImages for Draw {
@@ -48,4 +51,11 @@ impl DrawImages for Draw {
     fn animation_list<'a>(&mut self, list: &'a [&'a Texture]) -> DrawBuilder<ImageAnimation<'a>> {
         DrawBuilder::new(self, ImageAnimation::from_list(list))
     }
+
+    fn instanced_image<'a>(
+        &mut self,
+        instances: &'a ImageInstances,
+    ) -> DrawBuilder<InstancedImage<'a>> {
+        DrawBuilder::new(self, InstancedImage::new(instances))
+    }
 }
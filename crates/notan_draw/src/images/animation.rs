@@ -1,6 +1,7 @@
 use super::image::Image;
 use crate::builder::{DrawBuilder, DrawProcess};
 use crate::draw::Draw;
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
@@ -26,6 +27,7 @@ pub struct ImageAnimation<'a> {
     frames: Option<&'a [usize]>,
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     flip: (bool, bool),
 }
 
@@ -43,6 +45,7 @@ impl<'a> ImageAnimation<'a> {
             size: None,
             matrix: None,
             blend_mode: None,
+            material: None,
             frames: None,
             time: 0.0,
             flip: (false, false),
@@ -58,6 +61,7 @@ impl<'a> ImageAnimation<'a> {
             size: None,
             matrix: None,
             blend_mode: None,
+            material: None,
             frames: None,
             time: 0.0,
             flip: (false, false),
@@ -104,6 +108,12 @@ impl<'a> ImageAnimation<'a> {
         self
     }
 
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
+
     pub fn flip_x(&mut self, flip: bool) -> &mut Self {
         self.flip.0 = flip;
         self
@@ -132,6 +142,7 @@ impl DrawProcess for ImageAnimation<'_> {
             source,
             matrix,
             blend_mode,
+            material,
             frames,
             flip: (flip_x, flip_y),
         } = self;
@@ -166,7 +177,7 @@ impl DrawProcess for ImageAnimation<'_> {
 
                 let size = size.unwrap_or((tw as _, th as _));
 
-                img(draw, texture, matrix, blend_mode)
+                img(draw, texture, matrix, blend_mode, material)
                     .crop((tx as _, ty as _), (tw as _, th as _))
                     .size(size.0, size.1)
                     .position(x, y)
@@ -188,7 +199,7 @@ impl DrawProcess for ImageAnimation<'_> {
 
                 let texture = list[i];
                 let size = size.unwrap_or_else(|| texture.size());
-                img(draw, texture, matrix, blend_mode)
+                img(draw, texture, matrix, blend_mode, material)
                     .size(size.0, size.1)
                     .position(x, y)
                     .color(color)
@@ -206,11 +217,15 @@ fn img<'a>(
     tex: &'a Texture,
     mat: Option<Mat3>,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
 ) -> DrawBuilder<'a, Image<'a>> {
     let mut img = Image::new(tex);
     if let Some(bm) = blend_mode {
         img.blend_mode(bm);
     }
+    if let Some(m) = material {
+        img.material(m);
+    }
     if let Some(m) = mat {
         img.transform(m);
     }
@@ -1,5 +1,6 @@
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ImageInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
@@ -17,6 +18,7 @@ pub struct Image<'a> {
     alpha: f32,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     flip: (bool, bool),
 }
 
@@ -32,6 +34,7 @@ impl<'a> Image<'a> {
             crop: None,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             flip: (false, false),
         }
     }
@@ -78,6 +81,12 @@ impl<'a> Image<'a> {
         self
     }
 
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
+
     pub fn flip_x(&mut self, flip: bool) -> &mut Self {
         self.flip.0 = flip;
         self
@@ -107,6 +116,7 @@ impl DrawProcess for Image<'_> {
             crop,
             blend_mode,
             alpha_mode,
+            material,
             flip: (flip_x, flip_y),
         } = self;
 
@@ -174,6 +184,7 @@ impl DrawProcess for Image<'_> {
             indices: &[0, 1, 2, 2, 1, 3],
             blend_mode,
             alpha_mode,
+            material,
         });
     }
 }
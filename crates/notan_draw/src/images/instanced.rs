@@ -1 +1,380 @@
-//pub struct InstancedImage {}
+use super::painter::instance_vertex_info;
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, InstancedInfo};
+use crate::transform::DrawTransform;
+use notan_app::Graphics;
+use notan_graphics::prelude::*;
+use notan_math::{Mat3, Rect, Vec2};
+
+// position and size, rotation, color and uvs
+const INSTANCE_STRIDE: usize = 13;
+
+/// Per instance data of an `ImageInstances` buffer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageInstance {
+    /// Center of the image
+    pub position: Vec2,
+    /// Size of the image, by default the size of the crop or the texture
+    pub size: Option<Vec2>,
+    /// Rotation in radians around the center
+    pub rotation: f32,
+    pub color: Color,
+    /// Region of the texture to draw
+    pub crop: Option<Rect>,
+}
+
+impl Default for ImageInstance {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            size: None,
+            rotation: 0.0,
+            color: Color::WHITE,
+            crop: None,
+        }
+    }
+}
+
+impl ImageInstance {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            position: Vec2::new(x, y),
+            ..Default::default()
+        }
+    }
+}
+
+/// Per instance data stored on the GPU to draw the same texture many times
+/// with one draw call using `draw.instanced_image(&instances)`
+#[derive(Clone, Debug)]
+pub struct ImageInstances {
+    texture: Texture,
+    vbo: Option<Buffer>,
+    len: usize,
+}
+
+impl ImageInstances {
+    pub fn new(
+        device: &mut Device,
+        texture: &Texture,
+        instances: &[ImageInstance],
+    ) -> Result<Self, String> {
+        let mut ii = Self {
+            texture: texture.clone(),
+            vbo: None,
+            len: 0,
+        };
+        ii.set_instances(device, instances)?;
+        Ok(ii)
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Number of instances
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Replaces the instances, the buffer is created once and its data replaced after that
+    pub fn set_instances(
+        &mut self,
+        device: &mut Device,
+        instances: &[ImageInstance],
+    ) -> Result<(), String> {
+        let mut data = Vec::with_capacity(instances.len() * INSTANCE_STRIDE);
+        instances
+            .iter()
+            .for_each(|instance| push_instance(&mut data, &self.texture, instance));
+
+        match &self.vbo {
+            Some(vbo) => {
+                device.set_buffer_data(vbo, &data);
+            }
+            _ if instances.is_empty() => {}
+            _ => {
+                let vbo = device
+                    .create_vertex_buffer()
+                    .with_info(&instance_vertex_info())
+                    .with_data(&data)
+                    .build()?;
+
+                self.vbo = Some(vbo);
+            }
+        }
+
+        self.len = instances.len();
+        Ok(())
+    }
+
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.vbo.as_ref().filter(|_| self.len > 0)
+    }
+}
+
+fn push_instance(data: &mut Vec<f32>, texture: &Texture, instance: &ImageInstance) {
+    let frame = texture.frame();
+    let Rect {
+        x: sx,
+        y: sy,
+        width: sw,
+        height: sh,
+    } = instance.crop.map_or_else(
+        || *frame,
+        |mut r| {
+            r.x += frame.x;
+            r.y += frame.y;
+            r
+        },
+    );
+
+    let size = instance.size.unwrap_or_else(|| Vec2::new(sw, sh));
+
+    let base_width = texture.base_width();
+    let base_height = texture.base_height();
+    let u1 = sx / base_width;
+    let v1 = sy / base_height;
+    let u2 = (sx + sw) / base_width;
+    let v2 = (sy + sh) / base_height;
+    let (v1, v2) = if texture.is_render_texture() {
+        (v2, v1)
+    } else {
+        (v1, v2)
+    };
+
+    let c = instance.color;
+    let Vec2 { x, y } = instance.position;
+    data.extend_from_slice(&[
+        x,
+        y,
+        size.x,
+        size.y,
+        instance.rotation,
+        c.r,
+        c.g,
+        c.b,
+        c.a,
+        u1,
+        v1,
+        u2,
+        v2,
+    ]);
+}
+
+pub trait CreateImageInstances {
+    fn create_image_instances(
+        &mut self,
+        texture: &Texture,
+        instances: &[ImageInstance],
+    ) -> Result<ImageInstances, String>;
+}
+
+impl CreateImageInstances for Graphics {
+    fn create_image_instances(
+        &mut self,
+        texture: &Texture,
+        instances: &[ImageInstance],
+    ) -> Result<ImageInstances, String> {
+        ImageInstances::new(&mut self.device, texture, instances)
+    }
+}
+
+pub struct InstancedImage<'a> {
+    instances: &'a ImageInstances,
+    matrix: Option<Mat3>,
+    color: Color,
+    alpha: f32,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+}
+
+impl<'a> InstancedImage<'a> {
+    pub fn new(instances: &'a ImageInstances) -> Self {
+        Self {
+            instances,
+            matrix: None,
+            color: Color::WHITE,
+            alpha: 1.0,
+            blend_mode: None,
+            alpha_mode: None,
+        }
+    }
+
+    /// Tint color multiplied by the color of each instance
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+}
+
+impl DrawTransform for InstancedImage<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for InstancedImage<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let Self {
+            instances,
+            matrix,
+            color,
+            alpha,
+            blend_mode,
+            alpha_mode,
+        } = self;
+
+        if instances.is_empty() {
+            return;
+        }
+
+        let blend_mode = blend_mode.or_else(|| {
+            if instances.texture().is_render_texture() {
+                Some(BlendMode::OVER)
+            } else {
+                None
+            }
+        });
+
+        draw.add_instanced(&InstancedInfo {
+            instances,
+            transform: matrix.as_ref(),
+            color: color.with_alpha(color.a * alpha),
+            blend_mode,
+            alpha_mode,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::painter::INSTANCE_ATTRS;
+    use super::*;
+    use notan_app::empty::EmptyBackend;
+    use notan_app::BackendSystem;
+
+    fn device() -> Device {
+        let backend = EmptyBackend::new().unwrap();
+        Device::new(backend.get_graphics_backend()).unwrap()
+    }
+
+    #[test]
+    fn instance_layout() {
+        let floats: i32 = INSTANCE_ATTRS.iter().map(|(_, f)| f.size()).sum();
+        assert_eq!(floats as usize, INSTANCE_STRIDE);
+
+        let mut device = device();
+        let texture = device
+            .create_texture()
+            .from_empty_buffer(100, 50)
+            .build()
+            .unwrap();
+
+        let mut data = vec![];
+        let instance = ImageInstance {
+            position: Vec2::new(10.0, 20.0),
+            rotation: 0.5,
+            color: Color::RED,
+            ..Default::default()
+        };
+        push_instance(&mut data, &texture, &instance);
+        assert_eq!(
+            data,
+            [10.0, 20.0, 100.0, 50.0, 0.5, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn instance_crop_and_flip() {
+        let mut device = device();
+        let texture = device
+            .create_texture()
+            .from_empty_buffer(100, 100)
+            .build()
+            .unwrap()
+            .with_frame(50.0, 0.0, 50.0, 50.0);
+
+        // the crop is relative to the frame and sets the default size
+        let mut data = vec![];
+        let instance = ImageInstance {
+            crop: Some(Rect {
+                x: 10.0,
+                y: 20.0,
+                width: 20.0,
+                height: 10.0,
+            }),
+            ..Default::default()
+        };
+        push_instance(&mut data, &texture, &instance);
+        assert_eq!(data[2..4], [20.0, 10.0]);
+        assert_eq!(data[9..], [0.6, 0.2, 0.8, 0.3]);
+
+        // the size set replaces the default one
+        data.clear();
+        let instance = ImageInstance {
+            size: Some(Vec2::new(5.0, 5.0)),
+            ..Default::default()
+        };
+        push_instance(&mut data, &texture, &instance);
+        assert_eq!(data[2..4], [5.0, 5.0]);
+        assert_eq!(data[9..], [0.5, 0.0, 1.0, 0.5]);
+
+        // render textures are flipped vertically
+        data.clear();
+        let rt = device.create_render_texture(100, 100).build().unwrap();
+        push_instance(&mut data, &rt, &ImageInstance::default());
+        assert_eq!(data[9..], [0.0, 1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn reuse_buffer() {
+        let mut device = device();
+        let texture = device
+            .create_texture()
+            .from_empty_buffer(10, 10)
+            .build()
+            .unwrap();
+
+        let mut instances = ImageInstances::new(&mut device, &texture, &[]).unwrap();
+        assert!(instances.is_empty());
+        assert!(instances.buffer().is_none());
+
+        let list = vec![ImageInstance::default(); 4];
+        instances.set_instances(&mut device, &list).unwrap();
+        let id = instances.buffer().unwrap().id();
+        assert_eq!(instances.len(), 4);
+
+        // fewer or more instances reuse the buffer
+        instances.set_instances(&mut device, &list[..2]).unwrap();
+        assert_eq!(instances.buffer().unwrap().id(), id);
+        assert_eq!(instances.len(), 2);
+
+        let list = vec![ImageInstance::default(); 8];
+        instances.set_instances(&mut device, &list).unwrap();
+        assert_eq!(instances.buffer().unwrap().id(), id);
+        assert_eq!(instances.len(), 8);
+
+        instances.set_instances(&mut device, &[]).unwrap();
+        assert!(instances.buffer().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use super::image::Image;
 use crate::builder::{DrawBuilder, DrawProcess};
 use crate::draw::Draw;
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
@@ -19,6 +20,7 @@ pub struct NineSlice<'a> {
     bottom: Option<f32>,
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
 }
 
 impl<'a> NineSlice<'a> {
@@ -35,6 +37,7 @@ impl<'a> NineSlice<'a> {
             bottom: None,
             matrix: None,
             blend_mode: None,
+            material: None,
         }
     }
 
@@ -82,6 +85,12 @@ impl<'a> NineSlice<'a> {
         self.blend_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
 impl DrawTransform for NineSlice<'_> {
@@ -104,6 +113,7 @@ impl DrawProcess for NineSlice<'_> {
             bottom,
             matrix,
             blend_mode,
+            material,
         } = self;
 
         let img_ww = texture.width();
@@ -128,21 +138,21 @@ impl DrawProcess for NineSlice<'_> {
         };
 
         //top-left
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x, y)
             .size(left, top)
             .crop((0.0, 0.0), (left, top));
         //top-center
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x + left, y)
             .size(center_w, top)
             .crop((left, 0.0), (center_img_w, top));
         //top-right
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x + left + center_w, y)
@@ -150,21 +160,21 @@ impl DrawProcess for NineSlice<'_> {
             .crop((left + center_img_w, 0.0), (right, top));
 
         //center-left
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x, y + top)
             .size(left, center_h)
             .crop((0.0, top), (left, center_img_h));
         //center-center
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x + left, y + top)
             .size(center_w, center_h)
             .crop((left, top), (center_img_w, center_img_h));
         //center-right
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x + left + center_w, y + top)
@@ -172,21 +182,21 @@ impl DrawProcess for NineSlice<'_> {
             .crop((left + center_img_w, top), (right, center_img_h));
 
         //bottom-left
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x, y + top + center_h)
             .size(left, bottom)
             .crop((0.0, top + center_img_h), (left, bottom));
         //bottom-center
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .position(x + left, y + top + center_h)
             .size(center_w, bottom)
             .crop((left, top + center_img_h), (center_img_w, bottom));
         //bottom-right
-        img(draw, texture, blend_mode)
+        img(draw, texture, blend_mode, material)
             .color(color)
             .alpha(alpha)
             .size(right, bottom)
@@ -204,11 +214,15 @@ fn img<'a>(
     draw: &'a mut Draw,
     tex: &'a Texture,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
 ) -> DrawBuilder<'a, Image<'a>> {
     let mut img = Image::new(tex);
     if let Some(bm) = blend_mode {
         img.blend_mode(bm);
     }
+    if let Some(m) = material {
+        img.material(m);
+    }
 
     DrawBuilder::new(draw, img)
 }
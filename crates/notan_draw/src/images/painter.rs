@@ -2,7 +2,7 @@ use crate::batch::*;
 use crate::manager::process_pipeline;
 use notan_graphics::prelude::*;
 use notan_macro::{fragment_shader, vertex_shader};
-use notan_math::Mat4;
+use notan_math::{Mat3, Mat4, Vec4};
 
 fn vertex_info() -> VertexInfo {
     VertexInfo::new()
@@ -34,7 +34,7 @@ const IMAGE_VERTEX: ShaderSource = vertex_shader! {
 };
 
 //language=glsl
-const IMAGE_FRAGMENT: ShaderSource = fragment_shader! {
+pub(crate) const IMAGE_FRAGMENT: ShaderSource = fragment_shader! {
     r#"
     #version 450
     precision mediump float;
@@ -57,13 +57,19 @@ pub fn create_image_pipeline(
     fragment: Option<&ShaderSource>,
 ) -> Result<Pipeline, String> {
     let fragment = fragment.unwrap_or(&IMAGE_FRAGMENT);
+    image_pipeline_builder(device, fragment).build()
+}
+
+pub(crate) fn image_pipeline_builder<'a>(
+    device: &'a mut Device,
+    fragment: &'a ShaderSource,
+) -> PipelineBuilder<'a, 'a> {
     device
         .create_pipeline()
         .from(&IMAGE_VERTEX, fragment)
         .with_vertex_info(&vertex_info())
         .with_color_blend(BlendMode::NORMAL)
         .with_texture_location(0, "u_texture")
-        .build()
 }
 
 pub(crate) struct ImagePainter {
@@ -150,3 +156,191 @@ impl ImagePainter {
         self.indices.clear();
     }
 }
+
+/// Attributes of the per instance data: position and size, rotation, color and uvs
+pub(crate) const INSTANCE_ATTRS: [(u32, VertexFormat); 4] = [
+    (2, VertexFormat::Float32x4),
+    (3, VertexFormat::Float32),
+    (4, VertexFormat::Float32x4),
+    (5, VertexFormat::Float32x4),
+];
+
+pub(crate) fn instance_vertex_info() -> VertexInfo {
+    INSTANCE_ATTRS
+        .into_iter()
+        .fold(VertexInfo::new(), |info, (location, format)| {
+            info.attr(location, format)
+        })
+        .step_mode(VertexStepMode::Instance)
+}
+
+fn quad_vertex_info() -> VertexInfo {
+    VertexInfo::new()
+        .attr(0, VertexFormat::Float32x2)
+        .attr(1, VertexFormat::Float32x2)
+}
+
+//language=glsl
+const INSTANCED_IMAGE_VERTEX: ShaderSource = vertex_shader! {
+    r#"
+    #version 450
+    layout(location = 0) in vec2 a_corner;
+    layout(location = 1) in vec2 a_weight;
+    layout(location = 2) in vec4 a_rect;
+    layout(location = 3) in float a_rotation;
+    layout(location = 4) in vec4 a_color;
+    layout(location = 5) in vec4 a_frame;
+
+    layout(location = 0) out vec4 v_color;
+    layout(location = 1) out vec2 v_uvs;
+    layout(set = 0, binding = 0) uniform Locals {
+        mat4 u_projection;
+        vec4 u_tint;
+    };
+
+    void main() {
+        vec2 pos = a_corner * a_rect.zw;
+        float s = sin(a_rotation);
+        float c = cos(a_rotation);
+        pos = vec2(pos.x * c - pos.y * s, pos.x * s + pos.y * c) + a_rect.xy;
+
+        v_color = a_color * u_tint;
+        v_uvs = mix(a_frame.xy, a_frame.zw, a_weight);
+        gl_Position = u_projection * vec4(pos, 0.0, 1.0);
+    }
+    "#
+};
+
+pub fn create_instanced_image_pipeline(
+    device: &mut Device,
+    fragment: Option<&ShaderSource>,
+) -> Result<Pipeline, String> {
+    let fragment = fragment.unwrap_or(&IMAGE_FRAGMENT);
+    device
+        .create_pipeline()
+        .from(&INSTANCED_IMAGE_VERTEX, fragment)
+        .with_vertex_info(&quad_vertex_info())
+        .with_vertex_info(&instance_vertex_info())
+        .with_color_blend(BlendMode::NORMAL)
+        .with_texture_location(0, "u_texture")
+        .build()
+}
+
+/// Convert the 2d matrix to a 4x4 matrix to be used on the shaders
+fn mat3_to_mat4(m: &Mat3) -> Mat4 {
+    Mat4::from_cols(
+        Vec4::new(m.x_axis.x, m.x_axis.y, 0.0, 0.0),
+        Vec4::new(m.y_axis.x, m.y_axis.y, 0.0, 0.0),
+        Vec4::Z,
+        Vec4::new(m.z_axis.x, m.z_axis.y, 0.0, 1.0),
+    )
+}
+
+pub(crate) struct InstancedPainter {
+    vbo: Buffer,
+    ebo: Buffer,
+    pipeline: Pipeline,
+    // each instanced draw needs its own transform, so we keep a pool of buffers
+    ubos: Vec<Buffer>,
+    uniforms: Vec<[f32; 20]>,
+    count: usize,
+}
+
+impl InstancedPainter {
+    pub fn new(device: &mut Device) -> Result<Self, String> {
+        let pipeline = create_instanced_image_pipeline(device, None)?;
+
+        // quad centered on the origin, and the weights to interpolate the uvs
+        #[rustfmt::skip]
+        let vertices = [
+            -0.5, -0.5, 0.0, 0.0,
+            0.5, -0.5, 1.0, 0.0,
+            -0.5, 0.5, 0.0, 1.0,
+            0.5, 0.5, 1.0, 1.0,
+        ];
+
+        let vbo = device
+            .create_vertex_buffer()
+            .with_info(&quad_vertex_info())
+            .with_data(&vertices)
+            .build()?;
+
+        let ebo = device
+            .create_index_buffer()
+            .with_data(&[0, 1, 2, 2, 1, 3])
+            .build()?;
+
+        Ok(Self {
+            vbo,
+            ebo,
+            pipeline,
+            ubos: vec![],
+            uniforms: vec![],
+            count: 0,
+        })
+    }
+
+    pub fn push(
+        &mut self,
+        device: &mut Device,
+        renderer: &mut Renderer,
+        batch: &Batch,
+        projection: &Mat4,
+        is_rt: bool,
+    ) {
+        if let BatchType::Instanced { data } = &batch.typ {
+            let InstancedData {
+                instances,
+                transform,
+                color,
+            } = data;
+
+            let Some(instance_vbo) = instances.buffer() else {
+                return;
+            };
+
+            let index = self.count;
+            if index >= self.ubos.len() {
+                let ubo = device
+                    .create_uniform_buffer(0, "Locals")
+                    .with_data(&[0.0f32; 20])
+                    .build();
+
+                match ubo {
+                    Ok(ubo) => {
+                        self.ubos.push(ubo);
+                        self.uniforms.push([0.0; 20]);
+                    }
+                    Err(err) => {
+                        log::error!("Cannot create the buffer for the instanced draw: {}", err);
+                        return;
+                    }
+                }
+            }
+            self.count += 1;
+
+            let matrix = *projection * mat3_to_mat4(transform);
+            let uniforms = &mut self.uniforms[index];
+            uniforms[..16].copy_from_slice(&matrix.to_cols_array());
+            uniforms[16..].copy_from_slice(&color.rgba());
+
+            process_pipeline(renderer, batch, &self.pipeline, is_rt);
+            renderer.bind_texture_slot(0, 0, instances.texture());
+            renderer.bind_buffers(&[&self.vbo, instance_vbo, &self.ebo, &self.ubos[index]]);
+            renderer.draw_instanced(0, 6, instances.len() as _);
+        }
+    }
+
+    #[inline]
+    pub fn upload_buffers(&mut self, device: &mut Device) {
+        self.ubos
+            .iter()
+            .zip(self.uniforms.iter())
+            .take(self.count)
+            .for_each(|(ubo, uniforms)| device.set_buffer_data(ubo, uniforms));
+    }
+
+    pub fn clear(&mut self) {
+        self.count = 0;
+    }
+}
@@ -1,27 +1,43 @@
 mod batch;
 mod builder;
+mod cache;
+mod camera;
 mod config;
 mod custom_pipeline;
 mod draw;
 mod extension;
 mod images;
 mod manager;
+mod material;
+mod particles;
 mod patterns;
+mod sdf;
 mod shapes;
 mod texts;
+#[cfg(feature = "tilemap")]
+mod tilemap;
 mod transform;
+mod virtual_screen;
 
 mod atlas;
 
 pub use atlas::*;
 pub use builder::*;
+pub use cache::*;
+pub use camera::*;
 pub use config::*;
 pub use custom_pipeline::*;
 pub use draw::*;
 pub use extension::*;
 pub use images::*;
 pub use manager::*;
+pub use material::*;
+pub use particles::*;
 pub use patterns::*;
+pub use sdf::*;
 pub use shapes::*;
 pub use texts::*;
+#[cfg(feature = "tilemap")]
+pub use tilemap::*;
 pub use transform::*;
+pub use virtual_screen::*;
@@ -1,20 +1,27 @@
+use super::cache::*;
 use super::images::*;
 use super::patterns::*;
 use super::shapes::*;
 use super::texts::*;
 use crate::batch::*;
 use crate::draw::*;
+use crate::material::Material;
 use notan_glyph::GlyphBrush;
 use notan_graphics::prelude::*;
-use notan_math::Mat4;
+use notan_math::{Mat4, Rect};
 
 pub struct DrawManager {
     shape_painter: ShapePainter,
     image_painter: ImagePainter,
     pattern_painter: PatternPainter,
     text_painter: TextPainter,
+    cache_painter: CachePainter,
+    instanced_painter: InstancedPainter,
     renderer: Renderer,
     drawing_mask: bool,
+    // None when the scissors set on the renderer are unknown
+    clip: Option<Option<Rect>>,
+    size: (f32, f32),
 }
 
 impl DrawManager {
@@ -23,14 +30,20 @@ impl DrawManager {
         let image_painter = ImagePainter::new(device)?;
         let pattern_painter = PatternPainter::new(device)?;
         let text_painter = TextPainter::new(device)?;
+        let cache_painter = CachePainter::new(device)?;
+        let instanced_painter = InstancedPainter::new(device)?;
         let renderer = device.create_renderer();
         Ok(Self {
             shape_painter,
             image_painter,
             pattern_painter,
             text_painter,
+            cache_painter,
+            instanced_painter,
             renderer,
             drawing_mask: false,
+            clip: Some(None),
+            size: (0.0, 0.0),
         })
     }
 
@@ -91,12 +104,35 @@ fn paint_batch(
     projection: &Mat4,
     is_rt: bool,
 ) {
-    if b.is_mask && !manager.drawing_mask {
+    let is_mask = b.stencil.is_write();
+    if is_mask && !manager.drawing_mask {
         manager.renderer.end();
         manager.drawing_mask = true;
-    } else if !b.is_mask && manager.drawing_mask {
+        manager.clip = Some(None);
+    } else if !is_mask && manager.drawing_mask {
         manager.drawing_mask = false;
+        // begin keeps the last scissors enabled so they must be set again
         manager.renderer.begin(None);
+        manager.clip = None;
+    }
+
+    if manager.clip != Some(b.clip) {
+        let (width, height) = manager.size;
+        let rect = b.clip.unwrap_or(Rect {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+        });
+        let rect = if is_rt {
+            render_texture_scissors(rect, height)
+        } else {
+            rect
+        };
+        manager
+            .renderer
+            .set_scissors(rect.x, rect.y, rect.width, rect.height);
+        manager.clip = Some(b.clip);
     }
 
     match &b.typ {
@@ -118,6 +154,16 @@ fn paint_batch(
                 .text_painter
                 .push(device, glyphs, &mut manager.renderer, b, projection, is_rt)
         }
+        BatchType::Cached { .. } => {
+            manager
+                .cache_painter
+                .push(device, &mut manager.renderer, b, projection, is_rt)
+        }
+        BatchType::Instanced { .. } => {
+            manager
+                .instanced_painter
+                .push(device, &mut manager.renderer, b, projection, is_rt)
+        }
     }
 }
 
@@ -169,6 +215,11 @@ fn process_draw(
     manager.shape_painter.clear();
     manager.pattern_painter.clear();
     manager.text_painter.clear();
+    manager.cache_painter.clear();
+    manager.instanced_painter.clear();
+    manager.drawing_mask = false;
+    manager.clip = Some(None);
+    manager.size = draw.size();
 
     let stencil = draw.needs_to_clean_stencil.then_some(0x00);
     manager.renderer.begin(Some(ClearOptions {
@@ -191,46 +242,53 @@ fn process_draw(
     manager.shape_painter.upload_buffers(device);
     manager.pattern_painter.upload_buffers(device);
     manager.text_painter.upload_buffers(device);
+    manager.cache_painter.upload_buffers(device);
+    manager.instanced_painter.upload_buffers(device);
 }
 
-fn override_pipeline_options(
-    pipeline: &Pipeline,
-    is_mask: bool,
-    masking: bool,
-) -> Option<Pipeline> {
-    if is_mask {
-        let mut pip = pipeline.clone();
-        pip.options.stencil = Some(StencilOptions {
-            stencil_fail: StencilAction::Keep,
-            depth_fail: StencilAction::Keep,
-            pass: StencilAction::Replace,
-            compare: CompareMode::Always,
-            read_mask: 0xff,
-            write_mask: 0xff,
-            reference: 1,
-        });
-        pip.options.depth_stencil.write = false;
-        pip.options.color_mask = ColorMask::NONE;
-        return Some(pip);
+/// The backend flips the scissors using the height of the target, render textures
+/// are drawn flipped so the rect is flipped too to cancel it
+fn render_texture_scissors(rect: Rect, height: f32) -> Rect {
+    Rect {
+        y: height - (rect.y + rect.height),
+        ..rect
     }
+}
 
-    if masking {
-        let mut pip = pipeline.clone();
-        pip.options.stencil = Some(StencilOptions {
-            stencil_fail: StencilAction::Keep,
-            depth_fail: StencilAction::Keep,
-            pass: StencilAction::Replace,
-            compare: CompareMode::Equal,
-            read_mask: 0xff,
-            write_mask: 0x00,
-            reference: 1,
-        });
-        pip.options.depth_stencil.write = true;
-        pip.options.color_mask = ColorMask::ALL;
-        return Some(pip);
+fn override_pipeline_options(pipeline: &Pipeline, stencil: StencilMode) -> Option<Pipeline> {
+    match stencil {
+        StencilMode::Write { reference, action } => {
+            let mut pip = pipeline.clone();
+            pip.options.stencil = Some(StencilOptions {
+                stencil_fail: StencilAction::Keep,
+                depth_fail: StencilAction::Keep,
+                pass: action,
+                compare: CompareMode::Equal,
+                read_mask: 0xff,
+                write_mask: 0xff,
+                reference: reference as _,
+            });
+            pip.options.depth_stencil.write = false;
+            pip.options.color_mask = ColorMask::NONE;
+            Some(pip)
+        }
+        StencilMode::Test(reference) => {
+            let mut pip = pipeline.clone();
+            pip.options.stencil = Some(StencilOptions {
+                stencil_fail: StencilAction::Keep,
+                depth_fail: StencilAction::Keep,
+                pass: StencilAction::Keep,
+                compare: CompareMode::Equal,
+                read_mask: 0xff,
+                write_mask: 0x00,
+                reference: reference as _,
+            });
+            pip.options.depth_stencil.write = true;
+            pip.options.color_mask = ColorMask::ALL;
+            Some(pip)
+        }
+        StencilMode::None => None,
     }
-
-    None
 }
 
 pub(crate) fn process_pipeline(
@@ -240,19 +298,42 @@ pub(crate) fn process_pipeline(
     is_rt: bool,
 ) {
     let pip = batch.pipeline.as_ref().unwrap_or(default_pipeline);
-    let masked = masked_pip(pip, batch.is_mask, batch.masking);
-    let pip_to_use = masked.as_ref().unwrap_or(pip);
-    let blended = blended_pip(pip_to_use, batch.blend_mode, batch.alpha_mode, is_rt);
-    let final_pip = blended.as_ref().unwrap_or(pip_to_use);
-    renderer.set_pipeline(final_pip);
+    set_pipeline(
+        renderer,
+        pip,
+        batch.stencil,
+        batch.blend_mode,
+        batch.alpha_mode,
+        is_rt,
+    );
 
     if let Some(buffers) = &batch.uniform_buffers {
         buffers.iter().for_each(|u| renderer.bind_buffer(u));
     }
+
+    if let Some(material) = &batch.material {
+        Material::bind_textures(material.textures(), renderer);
+    }
+}
+
+/// Set the pipeline applying the mask and blending options
+pub(crate) fn set_pipeline(
+    renderer: &mut Renderer,
+    pip: &Pipeline,
+    stencil: StencilMode,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+    is_rt: bool,
+) {
+    let masked = masked_pip(pip, stencil);
+    let pip_to_use = masked.as_ref().unwrap_or(pip);
+    let blended = blended_pip(pip_to_use, blend_mode, alpha_mode, is_rt);
+    let final_pip = blended.as_ref().unwrap_or(pip_to_use);
+    renderer.set_pipeline(final_pip);
 }
 
-fn masked_pip(pip: &Pipeline, is_mask: bool, masking: bool) -> Option<Pipeline> {
-    override_pipeline_options(pip, is_mask, masking)
+fn masked_pip(pip: &Pipeline, stencil: StencilMode) -> Option<Pipeline> {
+    override_pipeline_options(pip, stencil)
 }
 
 fn blended_pip(
@@ -277,3 +358,69 @@ fn blended_pip(
 
     None
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DrawShapes;
+    use notan_app::empty::EmptyBackend;
+    use notan_app::{BackendSystem, Graphics};
+    use notan_glyph::GlyphBrushBuilder;
+
+    // scissors set after the last begin command
+    fn scissors_after_begin(draw: &Draw, is_rt: bool) -> Vec<(f32, f32, f32, f32)> {
+        let backend = EmptyBackend::new().unwrap();
+        let mut gfx = Graphics::new(backend.get_graphics_backend()).unwrap();
+        let mut glyphs = GlyphBrushBuilder::using_fonts(vec![]).build(&mut gfx);
+        let mut manager = DrawManager::new(&mut gfx.device).unwrap();
+
+        let commands = manager.process_draw(draw, &mut gfx.device, &mut glyphs, is_rt);
+        let begin = commands
+            .iter()
+            .rposition(|cmd| matches!(cmd, Commands::Begin { .. }))
+            .unwrap();
+        commands[begin..]
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Commands::Scissors {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => Some((*x, *y, *width, *height)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unclipped_batch_after_clipped_mask() {
+        let mut mask = Draw::new(400, 300);
+        mask.rect((0.0, 0.0), (50.0, 50.0));
+
+        let mut draw = Draw::new(400, 300);
+        draw.push_clip(10.0, 20.0, 100.0, 50.0);
+        draw.push_mask(&mask);
+        draw.pop_mask();
+        draw.pop_clip();
+        draw.rect((0.0, 0.0), (10.0, 10.0));
+
+        // the scissors of the mask are still enabled after begin
+        assert_eq!(
+            scissors_after_begin(&draw, false),
+            vec![(0.0, 0.0, 400.0, 300.0)]
+        );
+    }
+
+    #[test]
+    fn render_texture_scissors_use_its_height() {
+        let mut draw = Draw::new(400, 2000);
+        draw.push_clip(10.0, 20.0, 100.0, 50.0);
+        draw.rect((0.0, 0.0), (10.0, 10.0));
+
+        assert_eq!(
+            scissors_after_begin(&draw, true),
+            vec![(10.0, 1930.0, 100.0, 50.0)]
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use crate::images::image_pipeline_builder;
+use crate::patterns::pattern_pipeline_builder;
+use crate::shapes::shape_pipeline_builder;
+use notan_app::graphics::*;
+
+/// Custom pipeline with its uniforms and extra textures that can be set per draw element
+/// The material must be created for the type of element where it's used (`create_image_material`,
+/// `create_shape_material` or `create_pattern_material`).
+/// Elements using the same material are batched together. The uniform buffers are shared between
+/// clones of the material, build another material to use different values on the same frame.
+#[derive(Debug, Clone)]
+pub struct Material {
+    pipeline: Pipeline,
+    uniforms: Vec<(u32, Buffer)>,
+    textures: Vec<(u32, Texture)>,
+}
+
+impl Material {
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    pub(crate) fn uniform_buffers(&self) -> Vec<Buffer> {
+        self.uniforms.iter().map(|(_, b)| b.clone()).collect()
+    }
+
+    /// Extra textures with their location on the shader
+    pub fn textures(&self) -> &[(u32, Texture)] {
+        &self.textures
+    }
+
+    /// Returns the uniform buffer on the location given
+    pub fn uniform_buffer(&self, location: u32) -> Option<&Buffer> {
+        self.uniforms
+            .iter()
+            .find_map(|(loc, b)| (*loc == location).then_some(b))
+    }
+
+    /// Updates the values of the uniform buffer on the location given
+    pub fn set_uniforms<T: BufferData>(&self, gfx: &mut Graphics, location: u32, data: T) {
+        match self.uniform_buffer(location) {
+            Some(buffer) => gfx.set_buffer_data(buffer, data),
+            None => {
+                log::warn!(
+                    "Material doesn't have uniforms on the location {}",
+                    location
+                );
+            }
+        }
+    }
+
+    /// Replace or add a texture on the location given
+    pub fn set_texture(&mut self, location: u32, texture: &Texture) {
+        match self.textures.iter_mut().find(|(loc, _)| *loc == location) {
+            Some((_, tex)) => *tex = texture.clone(),
+            None => self.textures.push((location, texture.clone())),
+        }
+    }
+
+    /// Bind the extra textures starting on the slot 1, the slot 0 is used by the element
+    pub(crate) fn bind_textures(textures: &[(u32, Texture)], renderer: &mut Renderer) {
+        textures.iter().enumerate().for_each(|(i, (loc, tex))| {
+            renderer.bind_texture_slot(i as u32 + 1, *loc, tex);
+        });
+    }
+}
+
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        self.pipeline == other.pipeline
+            && self.uniforms == other.uniforms
+            && self.textures == other.textures
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaterialKind {
+    Image,
+    Pattern,
+    Shape,
+}
+
+pub struct MaterialBuilder<'a, 'b> {
+    gfx: &'a mut Graphics,
+    kind: MaterialKind,
+    fragment: &'b ShaderSource<'b>,
+    uniforms: Vec<(u32, String, Vec<u8>)>,
+    textures: Vec<(u32, String, Texture)>,
+}
+
+impl<'a, 'b> MaterialBuilder<'a, 'b> {
+    fn new(gfx: &'a mut Graphics, kind: MaterialKind, fragment: &'b ShaderSource) -> Self {
+        Self {
+            gfx,
+            kind,
+            fragment,
+            uniforms: vec![],
+            textures: vec![],
+        }
+    }
+
+    /// Creates an uniform buffer with the initial values, the location 0 is used by the draw's projection
+    pub fn with_uniforms<T: BufferData>(mut self, location: u32, name: &str, data: T) -> Self {
+        debug_assert!(location != 0, "Uniform location 0 is used by Draw");
+        let mut bytes = vec![];
+        data.save_as_bytes(&mut bytes);
+        self.uniforms.push((location, name.to_string(), bytes));
+        self
+    }
+
+    /// Add a texture to the sampler with the name given, the location 0 is used by the element's texture
+    pub fn with_texture(mut self, location: u32, name: &str, texture: &Texture) -> Self {
+        debug_assert!(location != 0, "Texture location 0 is used by Draw");
+        self.textures
+            .push((location, name.to_string(), texture.clone()));
+        self
+    }
+
+    pub fn build(self) -> Result<Material, String> {
+        let Self {
+            gfx,
+            kind,
+            fragment,
+            uniforms,
+            textures,
+        } = self;
+
+        let builder = match kind {
+            MaterialKind::Image => image_pipeline_builder(gfx, fragment),
+            MaterialKind::Pattern => pattern_pipeline_builder(gfx, fragment),
+            MaterialKind::Shape => shape_pipeline_builder(gfx, fragment),
+        };
+
+        let pipeline = textures
+            .iter()
+            .fold(builder, |builder, (loc, name, _)| {
+                builder.with_texture_location(*loc, name)
+            })
+            .build()?;
+
+        let uniforms = uniforms
+            .iter()
+            .map(|(loc, name, bytes)| {
+                let buffer = gfx
+                    .create_uniform_buffer(*loc, name)
+                    .with_data(bytes.as_slice())
+                    .build()?;
+                Ok((*loc, buffer))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let textures = textures
+            .into_iter()
+            .map(|(loc, _, tex)| (loc, tex))
+            .collect();
+
+        Ok(Material {
+            pipeline,
+            uniforms,
+            textures,
+        })
+    }
+}
+
+pub trait CreateMaterial {
+    /// Creates a material to use with images, animations and nine slices
+    fn create_image_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b>;
+
+    /// Creates a material to use with patterns
+    fn create_pattern_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b>;
+
+    /// Creates a material to use with shapes
+    fn create_shape_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b>;
+}
+
+impl CreateMaterial for Graphics {
+    fn create_image_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b> {
+        MaterialBuilder::new(self, MaterialKind::Image, fragment)
+    }
+
+    fn create_pattern_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b> {
+        MaterialBuilder::new(self, MaterialKind::Pattern, fragment)
+    }
+
+    fn create_shape_material<'a, 'b>(
+        &'a mut self,
+        fragment: &'b ShaderSource,
+    ) -> MaterialBuilder<'a, 'b> {
+        MaterialBuilder::new(self, MaterialKind::Shape, fragment)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::draw::{Draw, ShapeInfo};
+    use notan_app::empty::EmptyBackend;
+    use notan_app::BackendSystem;
+
+    const FRAGMENT: ShaderSource = ShaderSource { sources: &[] };
+
+    fn graphics() -> Graphics {
+        let backend = EmptyBackend::new().unwrap();
+        Graphics::new(backend.get_graphics_backend()).unwrap()
+    }
+
+    fn texture(gfx: &mut Graphics) -> Texture {
+        gfx.create_texture()
+            .from_empty_buffer(10, 10)
+            .build()
+            .unwrap()
+    }
+
+    fn shape(draw: &mut Draw, material: Option<&Material>) {
+        draw.add_shape(&ShapeInfo {
+            transform: None,
+            vertices: &[0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+            indices: &[0],
+            blend_mode: None,
+            alpha_mode: None,
+            material,
+        });
+    }
+
+    #[test]
+    fn material_uniforms_and_textures() {
+        let mut gfx = graphics();
+        let tex1 = texture(&mut gfx);
+        let tex2 = texture(&mut gfx);
+        let mut material = gfx
+            .create_shape_material(&FRAGMENT)
+            .with_uniforms(1, "Params", &[1.0f32, 2.0, 3.0, 4.0])
+            .with_uniforms(2, "Extra", &[0.5f32; 4])
+            .with_texture(1, "u_noise", &tex1)
+            .build()
+            .unwrap();
+
+        assert!(material.uniform_buffer(1).is_some());
+        assert!(material.uniform_buffer(2).is_some());
+        assert!(material.uniform_buffer(3).is_none());
+        assert_eq!(material.uniform_buffers().len(), 2);
+
+        // replacing a texture keeps its location, new locations are added
+        material.set_texture(1, &tex2);
+        material.set_texture(2, &tex1);
+        assert_eq!(material.textures(), &[(1, tex2.clone()), (2, tex1.clone())]);
+
+        // the extra textures are bound after the element's texture
+        let mut renderer = Renderer::new(10, 10);
+        Material::bind_textures(material.textures(), &mut renderer);
+        let binds = renderer
+            .commands()
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Commands::BindTexture { id, slot, location } => Some((*id, *slot, *location)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(binds, [(tex2.id(), 1, 1), (tex1.id(), 2, 2)]);
+    }
+
+    #[test]
+    fn material_batching() {
+        let mut gfx = graphics();
+        let tex = texture(&mut gfx);
+        let material = gfx
+            .create_shape_material(&FRAGMENT)
+            .with_uniforms(1, "Params", &[1.0f32; 4])
+            .with_texture(1, "u_noise", &tex)
+            .build()
+            .unwrap();
+
+        // clones share the pipeline, uniforms and textures so they share the batch
+        let mut draw = Draw::new(800, 600);
+        shape(&mut draw, Some(&material));
+        shape(&mut draw, Some(&material.clone()));
+        assert!(draw.batches.is_empty());
+
+        let batch = draw.current_batch.as_ref().unwrap();
+        assert_eq!(batch.pipeline.as_ref(), Some(material.pipeline()));
+        assert_eq!(batch.uniform_buffers, Some(material.uniform_buffers()));
+        assert_eq!(batch.material.as_ref(), Some(&material));
+        assert_eq!(batch.indices.len(), 2);
+
+        // a different extra texture breaks the batch
+        let mut other = material.clone();
+        other.set_texture(1, &texture(&mut gfx));
+        shape(&mut draw, Some(&other));
+        assert_eq!(draw.batches.len(), 1);
+
+        // and so does an element without material
+        shape(&mut draw, None);
+        assert_eq!(draw.batches.len(), 2);
+        let batch = draw.current_batch.as_ref().unwrap();
+        assert!(batch.material.is_none());
+        assert!(batch.uniform_buffers.is_none());
+    }
+}
@@ -0,0 +1,21 @@
+mod config;
+mod curve;
+mod emitter;
+mod render;
+
+use crate::builder::DrawBuilder;
+use crate::draw::Draw;
+pub use config::*;
+pub use curve::*;
+pub use emitter::*;
+pub use render::*;
+
+pub trait DrawParticles {
+    fn particles<'a>(&mut self, emitter: &'a ParticleEmitter) -> DrawBuilder<'_, Particles<'a>>;
+}
+
+impl DrawParticles for Draw {
+    fn particles<'a>(&mut self, emitter: &'a ParticleEmitter) -> DrawBuilder<'_, Particles<'a>> {
+        DrawBuilder::new(self, Particles::new(emitter))
+    }
+}
@@ -0,0 +1,161 @@
+use super::curve::Curve;
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_math::{vec2, Vec2};
+use std::f32::consts::TAU;
+
+/// Area where the particles are spawned, relative to the emitter's position
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmitterShape {
+    Point,
+    Circle { radius: f32 },
+    Ring { radius: f32 },
+    Rect { width: f32, height: f32 },
+    Line { length: f32 },
+}
+
+/// World particles stay where they were spawned when the emitter moves,
+/// local particles move with the emitter
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParticleSpace {
+    #[default]
+    World,
+    Local,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParticleBlend {
+    #[default]
+    Normal,
+    Add,
+    Multiply,
+    Screen,
+}
+
+impl ParticleBlend {
+    pub fn blend_mode(&self) -> BlendMode {
+        match self {
+            ParticleBlend::Normal => BlendMode::NORMAL,
+            ParticleBlend::Add => BlendMode::ADD,
+            ParticleBlend::Multiply => BlendMode::MULTIPLY,
+            ParticleBlend::Screen => BlendMode::SCREEN,
+        }
+    }
+}
+
+/// Spawns a number of particles at once, repeating it if there is an interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Burst {
+    pub time: f32,
+    pub count: usize,
+    pub interval: Option<f32>,
+}
+
+/// Animation frames using the texture as a grid
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleFrames {
+    pub cols: usize,
+    pub rows: usize,
+    /// Frames per second, if it's none the animation takes the particle's lifetime
+    pub fps: Option<f32>,
+    /// Start each particle with a random frame
+    pub random_start: bool,
+}
+
+/// Description of an emitter, ranges are `(min, max)` values picked randomly for each particle
+/// and angles are in radians. Curves go from the birth (0.0) to the death (1.0) of the particle.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ParticleConfig {
+    pub shape: EmitterShape,
+    pub space: ParticleSpace,
+    /// Particles per second
+    pub rate: f32,
+    pub bursts: Vec<Burst>,
+    /// Time emitting particles, none to emit forever
+    pub duration: Option<f32>,
+    pub max_particles: usize,
+    pub lifetime: (f32, f32),
+    pub direction: f32,
+    pub spread: f32,
+    pub speed: (f32, f32),
+    pub gravity: Vec2,
+    /// Velocity lost per second
+    pub drag: f32,
+    pub size: (f32, f32),
+    /// Multiplies the size
+    pub size_over_life: Curve<f32>,
+    pub rotation: (f32, f32),
+    pub angular_velocity: (f32, f32),
+    /// Added to the rotation
+    pub rotation_over_life: Curve<f32>,
+    pub color_over_life: Curve<Color>,
+    /// Multiplies the alpha of the color
+    pub alpha_over_life: Curve<f32>,
+    pub frames: Option<ParticleFrames>,
+    pub blend: ParticleBlend,
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self {
+            shape: EmitterShape::Point,
+            space: ParticleSpace::World,
+            rate: 10.0,
+            bursts: vec![],
+            duration: None,
+            max_particles: 1000,
+            lifetime: (1.0, 1.0),
+            direction: 0.0,
+            spread: TAU,
+            speed: (50.0, 100.0),
+            gravity: Vec2::ZERO,
+            drag: 0.0,
+            size: (8.0, 8.0),
+            size_over_life: Curve::constant(1.0),
+            rotation: (0.0, 0.0),
+            angular_velocity: (0.0, 0.0),
+            rotation_over_life: Curve::default(),
+            color_over_life: Curve::constant(Color::WHITE),
+            alpha_over_life: Curve::linear(1.0, 0.0),
+            frames: None,
+            blend: ParticleBlend::Normal,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ParticleConfig {
+    pub fn from_json(data: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(data).map_err(|e| e.to_string())
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+}
+
+impl EmitterShape {
+    /// Random point inside the shape using two random values between 0.0 and 1.0
+    pub(crate) fn point(&self, r1: f32, r2: f32) -> Vec2 {
+        match *self {
+            EmitterShape::Point => Vec2::ZERO,
+            EmitterShape::Circle { radius } => {
+                let angle = r1 * TAU;
+                vec2(angle.cos(), angle.sin()) * radius * r2.sqrt()
+            }
+            EmitterShape::Ring { radius } => {
+                let angle = r1 * TAU;
+                vec2(angle.cos(), angle.sin()) * radius
+            }
+            EmitterShape::Rect { width, height } => vec2((r1 - 0.5) * width, (r2 - 0.5) * height),
+            EmitterShape::Line { length } => vec2((r1 - 0.5) * length, 0.0),
+        }
+    }
+}
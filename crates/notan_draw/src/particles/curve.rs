@@ -0,0 +1,86 @@
+use notan_graphics::color::Color;
+use notan_math::Vec2;
+
+/// Values that can be interpolated by a `Curve`
+pub trait Lerp: Copy {
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Vec2::lerp(self, to, t)
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Color::new(
+            self.r.lerp(to.r, t),
+            self.g.lerp(to.g, t),
+            self.b.lerp(to.b, t),
+            self.a.lerp(to.a, t),
+        )
+    }
+}
+
+/// Keyframes interpolated linearly, the time goes from 0.0 (birth) to 1.0 (death)
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Curve<T> {
+    pub keys: Vec<(f32, T)>,
+}
+
+impl<T: Lerp> Curve<T> {
+    pub fn new(keys: &[(f32, T)]) -> Self {
+        let mut keys = keys.to_vec();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { keys }
+    }
+
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    pub fn linear(from: T, to: T) -> Self {
+        Self {
+            keys: vec![(0.0, from), (1.0, to)],
+        }
+    }
+
+    /// Value at the time passed, none if the curve has no keys
+    pub fn sample(&self, t: f32) -> Option<T> {
+        let next = self.keys.iter().position(|(time, _)| *time > t);
+        match next {
+            None => self.keys.last().map(|(_, v)| *v),
+            Some(0) => self.keys.first().map(|(_, v)| *v),
+            Some(i) => {
+                let (t1, v1) = self.keys[i - 1];
+                let (t2, v2) = self.keys[i];
+                Some(v1.lerp(v2, (t - t1) / (t2 - t1)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sample_keys() {
+        let curve = Curve::new(&[(1.0, 0.0), (0.0, 10.0), (0.5, 20.0)]);
+        assert_eq!(curve.sample(-1.0), Some(10.0));
+        assert_eq!(curve.sample(0.25), Some(15.0));
+        assert_eq!(curve.sample(0.75), Some(10.0));
+        assert_eq!(curve.sample(2.0), Some(0.0));
+        assert_eq!(Curve::<f32>::default().sample(0.5), None);
+    }
+}
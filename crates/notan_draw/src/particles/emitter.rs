@@ -0,0 +1,278 @@
+use super::config::{ParticleConfig, ParticleSpace};
+use notan_math::{vec2, Vec2};
+
+/// Particle simulated by a `ParticleEmitter`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    /// World position, or relative to the emitter in local space
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub age: f32,
+    pub lifetime: f32,
+    pub size: f32,
+    pub rotation: f32,
+    pub angular_velocity: f32,
+    pub frame: usize,
+}
+
+impl Particle {
+    /// Life elapsed from 0.0 (birth) to 1.0 (death)
+    pub fn progress(&self) -> f32 {
+        if self.lifetime <= 0.0 {
+            return 1.0;
+        }
+
+        (self.age / self.lifetime).min(1.0)
+    }
+}
+
+/// Spawns and simulates particles following a `ParticleConfig`, draw it with `draw.particles`
+#[derive(Clone, Debug)]
+pub struct ParticleEmitter {
+    config: ParticleConfig,
+    particles: Vec<Particle>,
+    position: Vec2,
+    time: f32,
+    pending: f32,
+    bursts: Vec<Option<f32>>,
+    emitting: bool,
+    seed: u32,
+}
+
+impl ParticleEmitter {
+    pub fn new(config: ParticleConfig) -> Self {
+        let bursts = config.bursts.iter().map(|b| Some(b.time)).collect();
+        Self {
+            config,
+            particles: vec![],
+            position: Vec2::ZERO,
+            time: 0.0,
+            pending: 0.0,
+            bursts,
+            emitting: true,
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    pub fn config(&self) -> &ParticleConfig {
+        &self.config
+    }
+
+    /// Changes the config, the particles alive keep their values
+    pub fn set_config(&mut self, config: ParticleConfig) {
+        self.bursts = config.bursts.iter().map(|b| Some(b.time)).collect();
+        self.config = config;
+    }
+
+    /// Seed used to randomize the particles
+    pub fn set_seed(&mut self, seed: u32) {
+        self.seed = seed.max(1);
+    }
+
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.position = vec2(x, y);
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    pub fn is_emitting(&self) -> bool {
+        self.emitting
+    }
+
+    /// The emitter stopped and all the particles are dead
+    pub fn is_finished(&self) -> bool {
+        !self.emitting && self.particles.is_empty()
+    }
+
+    /// Starts emitting from the beginning, the particles alive are kept
+    pub fn start(&mut self) {
+        self.time = 0.0;
+        self.pending = 0.0;
+        self.bursts = self.config.bursts.iter().map(|b| Some(b.time)).collect();
+        self.emitting = true;
+    }
+
+    /// Stops emitting new particles, the particles alive are kept
+    pub fn stop(&mut self) {
+        self.emitting = false;
+    }
+
+    /// Removes all the particles
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    /// Spawns a number of particles immediately
+    pub fn burst(&mut self, count: usize) {
+        (0..count).for_each(|_| self.spawn());
+    }
+
+    /// Advances the simulation, spawning and killing particles
+    pub fn update(&mut self, delta: f32) {
+        self.update_particles(delta);
+
+        if !self.emitting {
+            return;
+        }
+
+        let start = self.time;
+        self.time += delta;
+
+        // particles are only emitted during the duration
+        let end = match self.config.duration {
+            Some(duration) if self.time >= duration => {
+                self.emitting = false;
+                duration
+            }
+            _ => self.time,
+        };
+
+        self.pending += self.config.rate.max(0.0) * (end - start).max(0.0);
+        let count = self.pending as usize;
+        self.pending -= count as f32;
+        self.burst(count);
+
+        let mut count = 0;
+        self.bursts
+            .iter_mut()
+            .zip(&self.config.bursts)
+            .for_each(|(next, burst)| {
+                while let Some(time) = next.filter(|t| *t <= end) {
+                    count += burst.count;
+                    *next = burst.interval.filter(|i| *i > 0.0).map(|i| time + i);
+                }
+            });
+        self.burst(count);
+    }
+
+    fn update_particles(&mut self, delta: f32) {
+        let gravity = self.config.gravity;
+        let drag = 1.0 / (1.0 + self.config.drag.max(0.0) * delta);
+        self.particles.retain_mut(|p| {
+            p.age += delta;
+            if p.age >= p.lifetime {
+                return false;
+            }
+
+            p.velocity = (p.velocity + gravity * delta) * drag;
+            p.position += p.velocity * delta;
+            p.rotation += p.angular_velocity * delta;
+            true
+        });
+    }
+
+    fn spawn(&mut self) {
+        if self.particles.len() >= self.config.max_particles {
+            return;
+        }
+
+        let (r1, r2) = (self.random(), self.random());
+        let offset = self.config.shape.point(r1, r2);
+        let origin = match self.config.space {
+            ParticleSpace::World => self.position,
+            ParticleSpace::Local => Vec2::ZERO,
+        };
+
+        let angle = self.config.direction + (self.random() - 0.5) * self.config.spread;
+        let speed = self.random_range(self.config.speed);
+        let frame = match self.config.frames {
+            Some(frames) if frames.random_start => {
+                let total = frames.cols * frames.rows;
+                ((self.random() * total as f32) as usize).min(total.saturating_sub(1))
+            }
+            _ => 0,
+        };
+
+        let particle = Particle {
+            position: origin + offset,
+            velocity: vec2(angle.cos(), angle.sin()) * speed,
+            age: 0.0,
+            lifetime: self.random_range(self.config.lifetime),
+            size: self.random_range(self.config.size),
+            rotation: self.random_range(self.config.rotation),
+            angular_velocity: self.random_range(self.config.angular_velocity),
+            frame,
+        };
+
+        self.particles.push(particle);
+    }
+
+    // xorshift, random value between 0.0 and 1.0
+    fn random(&mut self) -> f32 {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.seed = x;
+        (x >> 8) as f32 / (1 << 24) as f32
+    }
+
+    fn random_range(&mut self, (min, max): (f32, f32)) -> f32 {
+        min + (max - min) * self.random()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::particles::{Burst, EmitterShape};
+
+    #[test]
+    fn emit_and_kill() {
+        let mut emitter = ParticleEmitter::new(ParticleConfig {
+            shape: EmitterShape::Rect {
+                width: 10.0,
+                height: 10.0,
+            },
+            rate: 10.0,
+            duration: Some(1.0),
+            lifetime: (0.5, 0.5),
+            bursts: vec![Burst {
+                time: 0.0,
+                count: 5,
+                interval: Some(0.4),
+            }],
+            ..Default::default()
+        });
+
+        emitter.set_position(100.0, 100.0);
+
+        // 10 per second and a burst of 5 at 0.0
+        emitter.update(0.25);
+        assert_eq!(emitter.len(), 2 + 5);
+        assert!(emitter.particles().iter().all(|p| {
+            let d = p.position - vec2(100.0, 100.0);
+            d.x.abs() <= 5.0 + 100.0 * 0.25 && d.y.abs() <= 5.0 + 100.0 * 0.25
+        }));
+
+        // 10 per second and another burst at 0.4
+        emitter.update(0.3);
+        assert_eq!(emitter.len(), 7 + 3 + 5);
+
+        // the first particles die, another burst at 0.8
+        emitter.update(0.3);
+        assert_eq!(emitter.len(), 8 + 3 + 5);
+
+        // the duration ends at 1.0 but the last particles are alive
+        emitter.update(1.0);
+        assert!(!emitter.is_emitting());
+        assert!(!emitter.is_finished());
+
+        emitter.update(0.5);
+        assert!(emitter.is_finished());
+    }
+}
@@ -0,0 +1,200 @@
+use super::config::{ParticleFrames, ParticleSpace};
+use super::emitter::{Particle, ParticleEmitter};
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, ImageInfo, ShapeInfo};
+use crate::transform::DrawTransform;
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_graphics::Texture;
+use notan_math::{vec2, Mat3, Rect, Vec2};
+
+/// Draws the particles of an emitter in one draw call, as colored squares or using a texture
+pub struct Particles<'a> {
+    emitter: &'a ParticleEmitter,
+    texture: Option<&'a Texture>,
+    color: Color,
+    alpha: f32,
+    matrix: Option<Mat3>,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+}
+
+impl<'a> Particles<'a> {
+    pub fn new(emitter: &'a ParticleEmitter) -> Self {
+        Self {
+            emitter,
+            texture: None,
+            color: Color::WHITE,
+            alpha: 1.0,
+            matrix: None,
+            blend_mode: None,
+            alpha_mode: None,
+        }
+    }
+
+    /// Texture used for each particle, the config's frames split it in a grid
+    pub fn texture(&mut self, texture: &'a Texture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Tint multiplied by the color of the particles
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Overrides the blend mode of the config
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+}
+
+impl DrawTransform for Particles<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for Particles<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let Self {
+            emitter,
+            texture,
+            color: tint,
+            alpha,
+            matrix,
+            blend_mode,
+            alpha_mode,
+        } = self;
+
+        if emitter.is_empty() {
+            return;
+        }
+
+        let config = emitter.config();
+        let origin = match config.space {
+            ParticleSpace::World => Vec2::ZERO,
+            ParticleSpace::Local => emitter.position(),
+        };
+
+        let frames = config.frames.filter(|f| f.cols > 0 && f.rows > 0);
+        let frame = texture.map_or_else(Rect::default, |t| *t.frame());
+        let cell = match frames {
+            Some(f) => vec2(frame.width / f.cols as f32, frame.height / f.rows as f32),
+            None => vec2(frame.width, frame.height),
+        };
+
+        // textures keep their aspect ratio using the size as width
+        let ratio = if cell.x > 0.0 { cell.y / cell.x } else { 1.0 };
+
+        let stride = if texture.is_some() { 8 } else { 6 };
+        let mut vertices = Vec::with_capacity(emitter.len() * stride * 4);
+        let mut indices = Vec::with_capacity(emitter.len() * 6);
+
+        emitter.particles().iter().for_each(|p| {
+            let t = p.progress();
+            let size = p.size * config.size_over_life.sample(t).unwrap_or(1.0);
+            let rotation = p.rotation + config.rotation_over_life.sample(t).unwrap_or(0.0);
+            let c = config.color_over_life.sample(t).unwrap_or(Color::WHITE);
+            let a = c.a * tint.a * alpha * config.alpha_over_life.sample(t).unwrap_or(1.0);
+            let c = Color::new(c.r * tint.r, c.g * tint.g, c.b * tint.b, a);
+
+            let (sin, cos) = rotation.sin_cos();
+            let half = vec2(size, size * ratio) * 0.5;
+            let center = origin + p.position;
+            let corner = |x: f32, y: f32| {
+                let v = vec2(x * half.x, y * half.y);
+                center + vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+            };
+
+            let base = (vertices.len() / stride) as u32;
+            indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 1, base + 3]);
+
+            let corners = [
+                corner(-1.0, -1.0),
+                corner(1.0, -1.0),
+                corner(-1.0, 1.0),
+                corner(1.0, 1.0),
+            ];
+
+            match texture {
+                Some(texture) => {
+                    let uvs = frame_uvs(texture, frame, cell, frames.map(|f| (f, p)));
+                    corners.iter().zip(uvs).for_each(|(pos, (u, v))| {
+                        vertices.extend_from_slice(&[pos.x, pos.y, u, v, c.r, c.g, c.b, c.a]);
+                    });
+                }
+                None => corners.iter().for_each(|pos| {
+                    vertices.extend_from_slice(&[pos.x, pos.y, c.r, c.g, c.b, c.a]);
+                }),
+            }
+        });
+
+        let blend_mode = blend_mode.or(Some(config.blend.blend_mode()));
+        match texture {
+            Some(texture) => draw.add_image(&ImageInfo {
+                texture,
+                transform: matrix.as_ref(),
+                vertices: &vertices,
+                indices: &indices,
+                blend_mode,
+                alpha_mode,
+                material: None,
+            }),
+            None => draw.add_shape(&ShapeInfo {
+                transform: matrix.as_ref(),
+                vertices: &vertices,
+                indices: &indices,
+                blend_mode,
+                alpha_mode,
+                material: None,
+            }),
+        }
+    }
+}
+
+// uvs of the corners for the current frame of the particle
+fn frame_uvs(
+    texture: &Texture,
+    frame: Rect,
+    cell: Vec2,
+    frames: Option<(ParticleFrames, &Particle)>,
+) -> [(f32, f32); 4] {
+    let (x, y) = match frames {
+        Some((f, p)) => {
+            let total = f.cols * f.rows;
+            let elapsed = match f.fps {
+                Some(fps) => (p.age * fps) as usize,
+                None => ((p.progress() * total as f32) as usize).min(total - 1),
+            };
+            let index = (p.frame + elapsed) % total;
+            let col = index % f.cols;
+            let row = index / f.cols;
+            (frame.x + col as f32 * cell.x, frame.y + row as f32 * cell.y)
+        }
+        None => (frame.x, frame.y),
+    };
+
+    let (bw, bh) = (texture.base_width(), texture.base_height());
+    let (u1, u2) = (x / bw, (x + cell.x) / bw);
+    let (v1, v2) = (y / bh, (y + cell.y) / bh);
+    let (v1, v2) = if texture.is_render_texture() {
+        (v2, v1)
+    } else {
+        (v1, v2)
+    };
+
+    [(u1, v1), (u2, v1), (u1, v2), (u2, v2)]
+}
@@ -31,7 +31,7 @@ const PATTERN_VERTEX: ShaderSource = vertex_shader! {
 };
 
 //language=glsl
-const PATTERN_FRAGMENT: ShaderSource = fragment_shader! {
+pub(crate) const PATTERN_FRAGMENT: ShaderSource = fragment_shader! {
     r#"
     #version 450
     precision mediump float;
@@ -64,13 +64,19 @@ pub fn create_pattern_pipeline(
     fragment: Option<&ShaderSource>,
 ) -> Result<Pipeline, String> {
     let fragment = fragment.unwrap_or(&PATTERN_FRAGMENT);
+    pattern_pipeline_builder(device, fragment).build()
+}
+
+pub(crate) fn pattern_pipeline_builder<'a>(
+    device: &'a mut Device,
+    fragment: &'a ShaderSource,
+) -> PipelineBuilder<'a, 'a> {
     device
         .create_pipeline()
         .from(&PATTERN_VERTEX, fragment)
         .with_vertex_info(&vertex_info())
         .with_color_blend(BlendMode::NORMAL)
         .with_texture_location(0, "u_texture")
-        .build()
 }
 
 pub(crate) struct PatternPainter {
@@ -1,5 +1,6 @@
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ImageInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
@@ -17,6 +18,7 @@ pub struct Pattern<'a> {
     alpha: f32,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     flip: (bool, bool),
 }
 
@@ -33,6 +35,7 @@ impl<'a> Pattern<'a> {
             scale: (1.0, 1.0),
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             flip: (false, false),
         }
     }
@@ -77,6 +80,12 @@ impl<'a> Pattern<'a> {
         self
     }
 
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
+
     pub fn flip_x(&mut self, flip: bool) -> &mut Self {
         self.flip.0 = flip;
         self
@@ -107,6 +116,7 @@ impl DrawProcess for Pattern<'_> {
             offset: (ox, oy),
             blend_mode,
             alpha_mode,
+            material,
             flip: (flip_x, flip_y),
         } = self;
 
@@ -170,6 +180,7 @@ impl DrawProcess for Pattern<'_> {
             indices: &[0, 1, 2, 2, 1, 3],
             blend_mode,
             alpha_mode,
+            material,
         });
     }
 }
@@ -0,0 +1,20 @@
+mod distance;
+mod font;
+mod style;
+mod text;
+
+use crate::builder::DrawBuilder;
+use crate::draw::Draw;
+pub use font::*;
+pub use style::*;
+pub use text::*;
+
+pub trait DrawSdfText {
+    fn sdf_text<'a>(&mut self, font: &'a SdfFont, text: &'a str) -> DrawBuilder<'_, SdfText<'a>>;
+}
+
+impl DrawSdfText for Draw {
+    fn sdf_text<'a>(&mut self, font: &'a SdfFont, text: &'a str) -> DrawBuilder<'_, SdfText<'a>> {
+        DrawBuilder::new(self, SdfText::new(font, text))
+    }
+}
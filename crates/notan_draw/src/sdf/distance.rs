@@ -0,0 +1,152 @@
+const INF: f32 = 1e20;
+
+/// Generates a signed distance field from a coverage bitmap using the Felzenszwalb's algorithm
+/// The values are mapped to 0.0..1.0 where 0.5 is the edge and `spread` is the max distance in pixels
+pub(crate) fn signed_distance_field(
+    coverage: &[f32],
+    width: usize,
+    height: usize,
+    spread: f32,
+) -> Vec<f32> {
+    debug_assert_eq!(coverage.len(), width * height);
+
+    let inside = coverage.iter().map(|c| *c >= 0.5).collect::<Vec<_>>();
+
+    // squared distances to the nearest pixel inside and outside of the shape
+    let mut to_inside = inside
+        .iter()
+        .map(|is_in| if *is_in { 0.0 } else { INF })
+        .collect::<Vec<_>>();
+    let mut to_outside = inside
+        .iter()
+        .map(|is_in| if *is_in { INF } else { 0.0 })
+        .collect::<Vec<_>>();
+
+    edt_2d(&mut to_inside, width, height);
+    edt_2d(&mut to_outside, width, height);
+
+    inside
+        .iter()
+        .enumerate()
+        .map(|(i, is_in)| {
+            // the edge is between the pixel centers
+            let dist = if *is_in {
+                -(to_outside[i].sqrt() - 0.5)
+            } else {
+                to_inside[i].sqrt() - 0.5
+            };
+
+            (0.5 - dist / (2.0 * spread)).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+fn edt_2d(grid: &mut [f32], width: usize, height: usize) {
+    let len = width.max(height);
+    let mut f = vec![0.0; len];
+    let mut d = vec![0.0; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.0; len + 1];
+
+    // columns
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+
+        edt_1d(&f[..height], &mut d, &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+
+    // rows
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        edt_1d(&f[..width], &mut d, &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+}
+
+// Squared distance transform of a sampled function using the lower envelope of parabolas
+fn edt_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = -INF;
+    z[1] = INF;
+
+    for q in 1..n {
+        // z[0] is -INF so k never goes below 0
+        let mut s = intersection(f, q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(f, q, v[k]);
+        }
+
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = INF;
+    }
+
+    k = 0;
+    for (q, dist) in d.iter_mut().enumerate().take(n) {
+        let qf = q as f32;
+        while z[k + 1] < qf {
+            k += 1;
+        }
+
+        let p = v[k] as f32;
+        *dist = (qf - p) * (qf - p) + f[v[k]];
+    }
+}
+
+// Intersection of the parabolas rooted at q and p
+#[inline]
+fn intersection(f: &[f32], q: usize, p: usize) -> f32 {
+    let (qf, pf) = (q as f32, p as f32);
+    ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * qf - 2.0 * pf)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn square_distance_field() {
+        // 3x3 square centered on a 9x9 bitmap
+        let (width, height) = (9, 9);
+        let coverage = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if (3..6).contains(&x) && (3..6).contains(&y) {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let sdf = signed_distance_field(&coverage, width, height, 4.0);
+        let value = |x: usize, y: usize| sdf[y * width + x];
+
+        // inside values are bigger than 0.5 and outside values are lower
+        assert!(value(4, 4) > 0.5);
+        assert!(value(3, 4) > 0.5);
+        assert!(value(2, 4) < 0.5);
+        assert!(value(0, 4) < value(2, 4));
+
+        // the edge is between the pixels
+        let edge = (value(3, 4) + value(2, 4)) * 0.5;
+        assert!((edge - 0.5).abs() < 0.001);
+
+        // symmetric
+        assert_eq!(value(1, 4), value(7, 4));
+        assert_eq!(value(4, 1), value(4, 7));
+    }
+}
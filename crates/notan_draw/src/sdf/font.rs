@@ -0,0 +1,541 @@
+use super::distance::signed_distance_field;
+use super::style::{SdfEffects, SdfStyle};
+use notan_app::graphics::*;
+use notan_glyph::ab_glyph::{Font as _, FontArc, ScaleFont};
+use notan_math::Rect;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_CHARS: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SdfGlyph {
+    pub advance: f32,
+    /// Quad relative to the baseline, none for empty glyphs like spaces
+    pub bounds: Option<Rect>,
+    /// Normalized texture coordinates
+    pub uvs: Rect,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SdfLine {
+    pub glyphs: Vec<(char, f32)>,
+    pub width: f32,
+}
+
+/// Font metrics using em units (1.0 is the size of the font)
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SdfMetrics {
+    pub glyphs: HashMap<char, SdfGlyph>,
+    pub kerning: HashMap<(char, char), f32>,
+    pub line_height: f32,
+    pub ascender: f32,
+}
+
+impl SdfMetrics {
+    pub fn glyph(&self, c: char) -> Option<&SdfGlyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'?'))
+    }
+
+    /// Split the text in lines and positions the glyphs, wrapping the words if they exceed the max width
+    pub fn layout(&self, text: &str, size: f32, max_width: Option<f32>) -> Vec<SdfLine> {
+        let mut lines = vec![];
+
+        text.split('\n').for_each(|paragraph| {
+            let mut line = SdfLine::default();
+            let mut x = 0.0;
+            let mut prev: Option<char> = None;
+
+            paragraph.split_inclusive(' ').for_each(|word| {
+                if let Some(mw) = max_width {
+                    let word_width = self.word_width(word.trim_end_matches(' '), size);
+                    if !line.glyphs.is_empty() && x + word_width > mw {
+                        lines.push(std::mem::take(&mut line));
+                        x = 0.0;
+                        prev = None;
+                    }
+                }
+
+                word.chars().for_each(|c| {
+                    if let Some(p) = prev {
+                        x += self.kerning.get(&(p, c)).copied().unwrap_or(0.0) * size;
+                    }
+
+                    if let Some(g) = self.glyph(c) {
+                        if g.bounds.is_some() {
+                            line.glyphs.push((c, x));
+                        }
+
+                        x += g.advance * size;
+                        if !c.is_whitespace() {
+                            line.width = x;
+                        }
+                    }
+
+                    prev = Some(c);
+                });
+            });
+
+            lines.push(line);
+        });
+
+        lines
+    }
+
+    fn word_width(&self, word: &str, size: f32) -> f32 {
+        let mut prev: Option<char> = None;
+        word.chars().fold(0.0, |width, c| {
+            let kerning = prev
+                .and_then(|p| self.kerning.get(&(p, c)).copied())
+                .unwrap_or(0.0);
+            prev = Some(c);
+            let advance = self.glyph(c).map_or(0.0, |g| g.advance);
+            width + (kerning + advance) * size
+        })
+    }
+}
+
+/// Font using signed distance fields to be drawn at any size without blurring
+#[derive(Clone, Debug)]
+pub struct SdfFont {
+    texture: Texture,
+    metrics: SdfMetrics,
+    style: SdfStyle,
+}
+
+impl SdfFont {
+    /// Texture atlas with the distance fields
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Default style without effects
+    pub fn style(&self) -> &SdfStyle {
+        &self.style
+    }
+
+    /// Line height for the size given
+    pub fn line_height(&self, size: f32) -> f32 {
+        self.metrics.line_height * size
+    }
+
+    /// Returns the width and height of the text
+    pub fn measure(&self, text: &str, size: f32, max_width: Option<f32>) -> (f32, f32) {
+        let lines = self.metrics.layout(text, size, max_width);
+        let width = lines.iter().fold(0.0, |w: f32, l| w.max(l.width));
+        (width, lines.len() as f32 * self.line_height(size))
+    }
+
+    pub(crate) fn metrics(&self) -> &SdfMetrics {
+        &self.metrics
+    }
+}
+
+pub struct SdfFontBuilder<'a> {
+    gfx: &'a mut Graphics,
+    data: &'a [u8],
+    size: f32,
+    spread: f32,
+    chars: Option<&'a str>,
+}
+
+impl<'a> SdfFontBuilder<'a> {
+    fn new(gfx: &'a mut Graphics, data: &'a [u8]) -> Self {
+        Self {
+            gfx,
+            data,
+            size: 48.0,
+            spread: 6.0,
+            chars: None,
+        }
+    }
+
+    /// Size in pixels used to rasterize the glyphs (48.0 by default)
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Max distance in pixels stored around the glyphs (6.0 by default)
+    /// Outlines, glows and shadows are limited by this distance
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Characters to generate, by default the printable ASCII characters
+    pub fn with_chars(mut self, chars: &'a str) -> Self {
+        self.chars = Some(chars);
+        self
+    }
+
+    pub fn build(self) -> Result<SdfFont, String> {
+        let Self {
+            gfx,
+            data,
+            size,
+            spread,
+            chars,
+        } = self;
+
+        let font = FontArc::try_from_vec(data.to_vec()).map_err(|e| e.to_string())?;
+        let mut chars = chars.unwrap_or(DEFAULT_CHARS).chars().collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let (metrics, atlas, width, height) = generate_atlas(&font, &chars, size, spread);
+        let texture = gfx
+            .create_texture()
+            .from_bytes(&atlas, width, height)
+            .with_filter(TextureFilter::Linear, TextureFilter::Linear)
+            .build()?;
+
+        let style = SdfStyle::new(gfx, SdfEffects::default())?;
+        Ok(SdfFont {
+            texture,
+            metrics,
+            style,
+        })
+    }
+}
+
+struct GlyphBitmap {
+    c: char,
+    width: usize,
+    height: usize,
+    data: Vec<f32>,
+}
+
+fn generate_atlas(
+    font: &FontArc,
+    chars: &[char],
+    size: f32,
+    spread: f32,
+) -> (SdfMetrics, Vec<u8>, u32, u32) {
+    let scaled = font.as_scaled(size);
+    let padding = spread.ceil() as usize + 1;
+
+    let mut metrics = SdfMetrics {
+        line_height: (scaled.ascent() - scaled.descent() + scaled.line_gap()) / size,
+        ascender: scaled.ascent() / size,
+        ..Default::default()
+    };
+
+    let mut bitmaps = vec![];
+    chars.iter().for_each(|c| {
+        let id = font.glyph_id(*c);
+        if id.0 == 0 {
+            log::warn!("SdfFont doesn't contain the character '{}'", c);
+            return;
+        }
+
+        let advance = scaled.h_advance(id) / size;
+        let bounds = font.outline_glyph(id.with_scale(size)).map(|outlined| {
+            let b = outlined.px_bounds();
+            let width = b.width() as usize + padding * 2;
+            let height = b.height() as usize + padding * 2;
+
+            let mut coverage = vec![0.0; width * height];
+            outlined.draw(|x, y, v| {
+                let index = (y as usize + padding) * width + x as usize + padding;
+                coverage[index] = v;
+            });
+
+            bitmaps.push(GlyphBitmap {
+                c: *c,
+                width,
+                height,
+                data: signed_distance_field(&coverage, width, height, spread),
+            });
+
+            Rect {
+                x: (b.min.x - padding as f32) / size,
+                y: (b.min.y - padding as f32) / size,
+                width: width as f32 / size,
+                height: height as f32 / size,
+            }
+        });
+
+        metrics.glyphs.insert(
+            *c,
+            SdfGlyph {
+                advance,
+                bounds,
+                uvs: Rect::default(),
+            },
+        );
+    });
+
+    chars.iter().for_each(|a| {
+        chars.iter().for_each(|b| {
+            let kerning = scaled.kern(font.glyph_id(*a), font.glyph_id(*b));
+            if kerning != 0.0 {
+                metrics.kerning.insert((*a, *b), kerning / size);
+            }
+        });
+    });
+
+    // shelf packing sorting the glyphs by height
+    bitmaps.sort_by_key(|b| std::cmp::Reverse(b.height));
+    let area = bitmaps
+        .iter()
+        .map(|b| (b.width + 1) * (b.height + 1))
+        .sum::<usize>();
+    let max_width = bitmaps.iter().map(|b| b.width + 1).max().unwrap_or(1);
+    let atlas_width = ((area as f32).sqrt() as usize)
+        .max(max_width)
+        .next_power_of_two();
+
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+    let positions = bitmaps
+        .iter()
+        .map(|b| {
+            if x + b.width > atlas_width {
+                x = 0;
+                y += row_height + 1;
+                row_height = 0;
+            }
+
+            let pos = (x, y);
+            x += b.width + 1;
+            row_height = row_height.max(b.height);
+            pos
+        })
+        .collect::<Vec<_>>();
+    let atlas_height = (y + row_height).max(1);
+
+    let mut atlas = vec![0; atlas_width * atlas_height * 4];
+    bitmaps.iter().zip(positions).for_each(|(b, (px, py))| {
+        b.data.iter().enumerate().for_each(|(i, v)| {
+            let (bx, by) = (i % b.width, i / b.width);
+            let index = ((py + by) * atlas_width + px + bx) * 4;
+            let value = (v * 255.0).round() as u8;
+            atlas[index..index + 4].copy_from_slice(&[value, value, value, 255]);
+        });
+
+        if let Some(glyph) = metrics.glyphs.get_mut(&b.c) {
+            glyph.uvs = Rect {
+                x: px as f32 / atlas_width as f32,
+                y: py as f32 / atlas_height as f32,
+                width: b.width as f32 / atlas_width as f32,
+                height: b.height as f32 / atlas_height as f32,
+            };
+        }
+    });
+
+    (metrics, atlas, atlas_width as _, atlas_height as _)
+}
+
+#[derive(Deserialize)]
+struct MsdfJson {
+    atlas: MsdfAtlas,
+    metrics: MsdfJsonMetrics,
+    glyphs: Vec<MsdfGlyph>,
+    #[serde(default)]
+    kerning: Vec<MsdfKerning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfAtlas {
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    y_origin: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfJsonMetrics {
+    #[serde(default)]
+    em_size: Option<f32>,
+    line_height: f32,
+    ascender: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfGlyph {
+    unicode: u32,
+    advance: f32,
+    #[serde(default)]
+    plane_bounds: Option<MsdfBounds>,
+    #[serde(default)]
+    atlas_bounds: Option<MsdfBounds>,
+}
+
+#[derive(Deserialize)]
+struct MsdfBounds {
+    left: f32,
+    bottom: f32,
+    right: f32,
+    top: f32,
+}
+
+#[derive(Deserialize)]
+struct MsdfKerning {
+    unicode1: u32,
+    unicode2: u32,
+    advance: f32,
+}
+
+/// Parse the json generated by msdf-atlas-gen
+fn parse_msdf_json(data: &[u8]) -> Result<SdfMetrics, String> {
+    let json: MsdfJson = serde_json::from_slice(data).map_err(|e| e.to_string())?;
+    let em = json.metrics.em_size.unwrap_or(1.0);
+    let (aw, ah) = (json.atlas.width, json.atlas.height);
+    let y_down = json.atlas.y_origin.as_deref() == Some("top");
+
+    let glyphs = json
+        .glyphs
+        .iter()
+        .filter_map(|g| {
+            let c = char::from_u32(g.unicode)?;
+            let (bounds, uvs) = match (&g.plane_bounds, &g.atlas_bounds) {
+                (Some(pb), Some(ab)) => {
+                    let (top, bottom) = if y_down {
+                        (pb.top, pb.bottom)
+                    } else {
+                        (-pb.top, -pb.bottom)
+                    };
+
+                    let (v1, v2) = if y_down {
+                        (ab.top / ah, ab.bottom / ah)
+                    } else {
+                        ((ah - ab.top) / ah, (ah - ab.bottom) / ah)
+                    };
+
+                    let bounds = Rect {
+                        x: pb.left / em,
+                        y: top / em,
+                        width: (pb.right - pb.left) / em,
+                        height: (bottom - top) / em,
+                    };
+
+                    let uvs = Rect {
+                        x: ab.left / aw,
+                        y: v1,
+                        width: (ab.right - ab.left) / aw,
+                        height: v2 - v1,
+                    };
+
+                    (Some(bounds), uvs)
+                }
+                _ => (None, Rect::default()),
+            };
+
+            Some((
+                c,
+                SdfGlyph {
+                    advance: g.advance / em,
+                    bounds,
+                    uvs,
+                },
+            ))
+        })
+        .collect();
+
+    let kerning = json
+        .kerning
+        .iter()
+        .filter_map(|k| {
+            let a = char::from_u32(k.unicode1)?;
+            let b = char::from_u32(k.unicode2)?;
+            Some(((a, b), k.advance / em))
+        })
+        .collect();
+
+    Ok(SdfMetrics {
+        glyphs,
+        kerning,
+        line_height: json.metrics.line_height / em,
+        ascender: json.metrics.ascender.abs() / em,
+    })
+}
+
+pub trait CreateSdfFont {
+    /// Generates the distance fields from a ttf or otf font
+    fn create_sdf_font<'a>(&'a mut self, data: &'a [u8]) -> SdfFontBuilder<'a>;
+
+    /// Loads a precomputed msdf atlas, using the json and the image generated by msdf-atlas-gen
+    fn create_msdf_font(&mut self, json: &[u8], image: &[u8]) -> Result<SdfFont, String>;
+}
+
+impl CreateSdfFont for Graphics {
+    fn create_sdf_font<'a>(&'a mut self, data: &'a [u8]) -> SdfFontBuilder<'a> {
+        SdfFontBuilder::new(self, data)
+    }
+
+    fn create_msdf_font(&mut self, json: &[u8], image: &[u8]) -> Result<SdfFont, String> {
+        let metrics = parse_msdf_json(json)?;
+        let texture = self
+            .create_texture()
+            .from_image(image)
+            .with_filter(TextureFilter::Linear, TextureFilter::Linear)
+            .build()?;
+
+        let style = SdfStyle::new(self, SdfEffects::default())?;
+        Ok(SdfFont {
+            texture,
+            metrics,
+            style,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "atlas": {"type": "msdf", "distanceRange": 4, "size": 32, "width": 64, "height": 64, "yOrigin": "bottom"},
+        "metrics": {"emSize": 1, "lineHeight": 1.25, "ascender": 0.9, "descender": -0.25},
+        "glyphs": [
+            {"unicode": 32, "advance": 0.25},
+            {"unicode": 65, "advance": 0.5,
+                "planeBounds": {"left": 0.0, "bottom": 0.0, "right": 0.5, "top": 0.75},
+                "atlasBounds": {"left": 0.0, "bottom": 32.0, "right": 16.0, "top": 56.0}}
+        ],
+        "kerning": [{"unicode1": 65, "unicode2": 65, "advance": -0.1}]
+    }"#;
+
+    #[test]
+    fn msdf_layout() {
+        let metrics = parse_msdf_json(JSON.as_bytes()).unwrap();
+        let a = metrics.glyphs[&'A'];
+        assert_eq!(
+            a.bounds,
+            Some(Rect {
+                x: 0.0,
+                y: -0.75,
+                width: 0.5,
+                height: 0.75
+            })
+        );
+        assert_eq!(
+            a.uvs,
+            Rect {
+                x: 0.0,
+                y: 0.125,
+                width: 0.25,
+                height: 0.375
+            }
+        );
+
+        // size 10: "AA" is 10 - 1 (kerning) wide, the space is 2.5
+        let lines = metrics.layout("AA AA", 10.0, None);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].glyphs,
+            [('A', 0.0), ('A', 4.0), ('A', 11.5), ('A', 15.5)]
+        );
+        assert_eq!(lines[0].width, 20.5);
+
+        // wrap words and new lines
+        let lines = metrics.layout("AA AA\nA", 10.0, Some(12.0));
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].width, 9.0);
+        assert_eq!(lines[1].glyphs, [('A', 0.0), ('A', 4.0)]);
+        assert_eq!(lines[2].width, 5.0);
+    }
+}
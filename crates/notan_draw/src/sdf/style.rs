@@ -0,0 +1,194 @@
+use crate::material::{CreateMaterial, Material};
+use notan_app::graphics::*;
+use notan_macro::fragment_shader;
+use notan_math::Vec2;
+
+//language=glsl
+const SDF_FRAGMENT: ShaderSource = fragment_shader! {
+    r#"
+    #version 450
+    precision mediump float;
+
+    layout(location = 0) in vec2 v_uvs;
+    layout(location = 1) in vec4 v_color;
+
+    layout(binding = 0) uniform sampler2D u_texture;
+    layout(set = 0, binding = 1) uniform SdfParams {
+        vec4 u_outline_color;
+        vec4 u_glow_color;
+        vec4 u_shadow_color;
+        vec4 u_params; // outline width, glow width, shadow softness
+        vec4 u_shadow_offset;
+    };
+
+    layout(location = 0) out vec4 color;
+
+    // the median of the channels works for sdf (same value on all channels) and msdf
+    float sample_distance(vec2 uvs) {
+        vec3 s = texture(u_texture, uvs).rgb;
+        return max(min(s.r, s.g), min(max(s.r, s.g), s.b));
+    }
+
+    vec4 over(vec4 top, vec4 bottom) {
+        float a = top.a + bottom.a * (1.0 - top.a);
+        if (a <= 0.0) {
+            return vec4(0.0);
+        }
+
+        vec3 rgb = (top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / a;
+        return vec4(rgb, a);
+    }
+
+    void main() {
+        float dist = sample_distance(v_uvs);
+        float smoothing = max(fwidth(dist) * 0.75, 0.0001);
+
+        float outline_width = u_params.x;
+        float glow_width = u_params.y;
+        float shadow_softness = u_params.z;
+        float edge = 0.5 - outline_width;
+
+        float fill = smoothstep(0.5 - smoothing, 0.5 + smoothing, dist);
+        vec4 col = vec4(v_color.rgb, v_color.a * fill);
+
+        if (outline_width > 0.0) {
+            float outline = smoothstep(edge - smoothing, edge + smoothing, dist);
+            col = over(col, vec4(u_outline_color.rgb, u_outline_color.a * v_color.a * outline));
+        }
+
+        if (glow_width > 0.0) {
+            float glow = smoothstep(edge - glow_width, edge, dist);
+            col = over(col, vec4(u_glow_color.rgb, u_glow_color.a * v_color.a * glow));
+        }
+
+        if (u_shadow_color.a > 0.0) {
+            // offset in screen pixels converted to texture coordinates
+            vec2 offset = dFdx(v_uvs) * u_shadow_offset.x + dFdy(v_uvs) * u_shadow_offset.y;
+            float shadow_dist = sample_distance(v_uvs - offset);
+            float softness = max(shadow_softness, smoothing);
+            float shadow = smoothstep(edge - softness, edge + softness, shadow_dist);
+            col = over(col, vec4(u_shadow_color.rgb, u_shadow_color.a * v_color.a * shadow));
+        }
+
+        if (col.a <= 0.0) {
+            discard;
+        }
+
+        color = col;
+    }
+    "#
+};
+
+/// Effects applied to the sdf texts
+/// The widths are relative to the font's spread, 0.5 is the max distance stored on the texture,
+/// the effects bigger than the spread are clipped.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SdfEffects {
+    pub outline: Option<(Color, f32)>,
+    pub glow: Option<(Color, f32)>,
+    /// Color, offset in screen pixels and softness
+    pub shadow: Option<(Color, Vec2, f32)>,
+}
+
+impl SdfEffects {
+    fn uniforms(&self) -> [f32; 20] {
+        let (outline_color, outline_width) = self.outline.unwrap_or((Color::TRANSPARENT, 0.0));
+        let (glow_color, glow_width) = self.glow.unwrap_or((Color::TRANSPARENT, 0.0));
+        let (shadow_color, shadow_offset, shadow_softness) =
+            self.shadow.unwrap_or((Color::TRANSPARENT, Vec2::ZERO, 0.0));
+
+        let [or, og, ob, oa] = outline_color.rgba();
+        let [gr, gg, gb, ga] = glow_color.rgba();
+        let [sr, sg, sb, sa] = shadow_color.rgba();
+
+        #[rustfmt::skip]
+        let data = [
+            or, og, ob, oa,
+            gr, gg, gb, ga,
+            sr, sg, sb, sa,
+            outline_width, glow_width, shadow_softness, 0.0,
+            shadow_offset.x, shadow_offset.y, 0.0, 0.0,
+        ];
+
+        data
+    }
+}
+
+/// Material used to draw sdf texts with outline, glow and shadow effects
+/// Styles can be shared between fonts, use different styles to draw texts with different effects on the same frame
+#[derive(Debug, Clone)]
+pub struct SdfStyle {
+    material: Material,
+    effects: SdfEffects,
+}
+
+impl SdfStyle {
+    pub(crate) fn new(gfx: &mut Graphics, effects: SdfEffects) -> Result<Self, String> {
+        let material = gfx
+            .create_image_material(&SDF_FRAGMENT)
+            .with_uniforms(1, "SdfParams", &effects.uniforms())
+            .build()?;
+
+        Ok(Self { material, effects })
+    }
+
+    pub fn effects(&self) -> &SdfEffects {
+        &self.effects
+    }
+
+    /// Updates the effects of the style
+    pub fn set_effects(&mut self, gfx: &mut Graphics, effects: SdfEffects) {
+        self.effects = effects;
+        self.material.set_uniforms(gfx, 1, &effects.uniforms());
+    }
+
+    pub(crate) fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+pub struct SdfStyleBuilder<'a> {
+    gfx: &'a mut Graphics,
+    effects: SdfEffects,
+}
+
+impl<'a> SdfStyleBuilder<'a> {
+    fn new(gfx: &'a mut Graphics) -> Self {
+        Self {
+            gfx,
+            effects: Default::default(),
+        }
+    }
+
+    /// Outline with a width relative to the spread (0.0..0.5)
+    pub fn with_outline(mut self, color: Color, width: f32) -> Self {
+        self.effects.outline = Some((color, width));
+        self
+    }
+
+    /// Glow with a width relative to the spread (0.0..0.5)
+    pub fn with_glow(mut self, color: Color, width: f32) -> Self {
+        self.effects.glow = Some((color, width));
+        self
+    }
+
+    /// Shadow with an offset in screen pixels and a softness relative to the spread (0.0..0.5)
+    pub fn with_shadow(mut self, color: Color, offset: Vec2, softness: f32) -> Self {
+        self.effects.shadow = Some((color, offset, softness));
+        self
+    }
+
+    pub fn build(self) -> Result<SdfStyle, String> {
+        SdfStyle::new(self.gfx, self.effects)
+    }
+}
+
+pub trait CreateSdfStyle {
+    fn create_sdf_style(&mut self) -> SdfStyleBuilder<'_>;
+}
+
+impl CreateSdfStyle for Graphics {
+    fn create_sdf_style(&mut self) -> SdfStyleBuilder<'_> {
+        SdfStyleBuilder::new(self)
+    }
+}
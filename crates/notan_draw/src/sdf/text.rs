@@ -0,0 +1,203 @@
+use super::font::SdfFont;
+use super::style::SdfStyle;
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, ImageInfo};
+use crate::transform::DrawTransform;
+use notan_glyph::{HorizontalAlign, VerticalAlign};
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_math::Mat3;
+
+/// Text drawn with a `SdfFont`, it keeps sharp edges at any size or scale
+pub struct SdfText<'a> {
+    font: &'a SdfFont,
+    text: &'a str,
+    style: Option<&'a SdfStyle>,
+    matrix: Option<Mat3>,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+    pos: (f32, f32),
+    size: f32,
+    color: Color,
+    alpha: f32,
+    max_width: Option<f32>,
+    h_align: HorizontalAlign,
+    v_align: VerticalAlign,
+}
+
+impl<'a> SdfText<'a> {
+    pub fn new(font: &'a SdfFont, text: &'a str) -> Self {
+        Self {
+            font,
+            text,
+            style: None,
+            matrix: None,
+            blend_mode: None,
+            alpha_mode: None,
+            pos: (0.0, 0.0),
+            size: 16.0,
+            color: Color::WHITE,
+            alpha: 1.0,
+            max_width: None,
+            h_align: HorizontalAlign::Left,
+            v_align: VerticalAlign::Top,
+        }
+    }
+
+    pub fn position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pos = (x, y);
+        self
+    }
+
+    pub fn size(&mut self, size: f32) -> &mut Self {
+        self.size = size;
+        self
+    }
+
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn max_width(&mut self, width: f32) -> &mut Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Style with the outline, glow and shadow effects, by default the font's style is used
+    pub fn style(&mut self, style: &'a SdfStyle) -> &mut Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn h_align_left(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Left;
+        self
+    }
+
+    pub fn h_align_center(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Center;
+        self
+    }
+
+    pub fn h_align_right(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Right;
+        self
+    }
+
+    pub fn v_align_top(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Top;
+        self
+    }
+
+    pub fn v_align_middle(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Center;
+        self
+    }
+
+    pub fn v_align_bottom(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Bottom;
+        self
+    }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+}
+
+impl DrawTransform for SdfText<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for SdfText<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let metrics = self.font.metrics();
+        let lines = metrics.layout(self.text, self.size, self.max_width);
+        let line_height = metrics.line_height * self.size;
+        let ascender = metrics.ascender * self.size;
+
+        let (x, y) = self.pos;
+        let height = lines.len() as f32 * line_height;
+        let top = match self.v_align {
+            VerticalAlign::Top => y,
+            VerticalAlign::Center => y - height * 0.5,
+            VerticalAlign::Bottom => y - height,
+        };
+
+        let [r, g, b, a] = self.color.rgba();
+        let a = a * self.alpha;
+
+        let count = lines.iter().map(|l| l.glyphs.len()).sum::<usize>();
+        let mut vertices = Vec::with_capacity(count * 32);
+        let mut indices = Vec::with_capacity(count * 6);
+
+        lines.iter().enumerate().for_each(|(i, line)| {
+            let left = match self.h_align {
+                HorizontalAlign::Left => x,
+                HorizontalAlign::Center => x - line.width * 0.5,
+                HorizontalAlign::Right => x - line.width,
+            };
+            let baseline = top + i as f32 * line_height + ascender;
+
+            line.glyphs.iter().for_each(|(c, gx)| {
+                let Some(glyph) = metrics.glyph(*c) else {
+                    return;
+                };
+
+                let Some(bounds) = glyph.bounds else {
+                    return;
+                };
+
+                let x1 = left + gx + bounds.x * self.size;
+                let y1 = baseline + bounds.y * self.size;
+                let x2 = x1 + bounds.width * self.size;
+                let y2 = y1 + bounds.height * self.size;
+
+                let u1 = glyph.uvs.x;
+                let v1 = glyph.uvs.y;
+                let u2 = u1 + glyph.uvs.width;
+                let v2 = v1 + glyph.uvs.height;
+
+                let n = (vertices.len() / 8) as u32;
+
+                #[rustfmt::skip]
+                vertices.extend_from_slice(&[
+                    x1, y1, u1, v1, r, g, b, a,
+                    x2, y1, u2, v1, r, g, b, a,
+                    x1, y2, u1, v2, r, g, b, a,
+                    x2, y2, u2, v2, r, g, b, a,
+                ]);
+
+                indices.extend_from_slice(&[n, n + 1, n + 2, n + 2, n + 1, n + 3]);
+            });
+        });
+
+        if indices.is_empty() {
+            return;
+        }
+
+        let style = self.style.unwrap_or_else(|| self.font.style());
+        draw.add_image(&ImageInfo {
+            texture: self.font.texture(),
+            transform: self.matrix.as_ref(),
+            vertices: &vertices,
+            indices: &indices,
+            blend_mode: self.blend_mode,
+            alpha_mode: self.alpha_mode,
+            material: Some(style.material()),
+        });
+    }
+}
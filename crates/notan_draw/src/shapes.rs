@@ -1,4 +1,5 @@
 mod circle;
+mod dash;
 mod ellipse;
 mod geometry;
 mod line;
@@ -6,6 +7,7 @@ mod painter;
 mod path;
 mod point;
 mod polygon;
+mod polyline;
 mod rect;
 mod star;
 mod tess;
@@ -14,6 +16,7 @@ mod triangle;
 pub use crate::builder::DrawBuilder;
 pub use crate::draw::Draw;
 pub use circle::Circle;
+pub use dash::DrawDash;
 pub use ellipse::Ellipse;
 pub use line::Line;
 pub use painter::create_shape_pipeline;
@@ -21,6 +24,7 @@ pub(crate) use painter::*;
 pub use path::Path;
 pub use point::{Point, XAlignment, YAlignment};
 pub use polygon::Polygon;
+pub use polyline::Polyline;
 pub use rect::Rectangle;
 pub use star::Star;
 pub use triangle::Triangle;
@@ -35,6 +39,7 @@ pub trait DrawShapes {
     fn ellipse(&mut self, position: (f32, f32), size: (f32, f32)) -> DrawBuilder<Ellipse>;
     fn star(&mut self, spikes: u8, outer_radius: f32, inner_radius: f32) -> DrawBuilder<Star>;
     fn polygon(&mut self, sides: u8, radius: f32) -> DrawBuilder<Polygon>;
+    fn polyline(&mut self, points: &[(f32, f32)]) -> DrawBuilder<Polyline>;
 }
 
 impl DrawShapes for Draw {
@@ -73,4 +78,8 @@ impl DrawShapes for Draw {
     fn polygon(&mut self, sides: u8, radius: f32) -> DrawBuilder<Polygon> {
         DrawBuilder::new(self, Polygon::new(sides, radius))
     }
+
+    fn polyline(&mut self, points: &[(f32, f32)]) -> DrawBuilder<Polyline> {
+        DrawBuilder::new(self, Polyline::new(points))
+    }
 }
@@ -3,13 +3,14 @@ use super::tess::TessMode;
 use super::tess::*;
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use lyon::tessellation::*;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 
-pub struct Circle {
+pub struct Circle<'a> {
     color: Color,
     pos: (f32, f32),
     radius: f32,
@@ -19,13 +20,14 @@ pub struct Circle {
     tolerance: f32,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
     stroke_color: Option<Color>,
 }
 
-impl Circle {
+impl<'a> Circle<'a> {
     pub fn new(radius: f32) -> Self {
         Self {
             color: Color::WHITE,
@@ -37,6 +39,7 @@ impl Circle {
             tolerance: StrokeOptions::DEFAULT_TOLERANCE,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -96,15 +99,21 @@ impl Circle {
         self.alpha_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Circle {
+impl DrawTransform for Circle<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Circle {
+impl DrawProcess for Circle<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let modes = self.modes;
         modes.iter().enumerate().for_each(|(i, mode)| match mode {
@@ -133,6 +142,7 @@ fn stroke(circle: &Circle, draw: &mut Draw) {
         tolerance,
         blend_mode,
         alpha_mode,
+        material,
         stroke_color,
         ..
     } = *circle;
@@ -153,6 +163,7 @@ fn stroke(circle: &Circle, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
 
@@ -166,6 +177,7 @@ fn fill(circle: &Circle, draw: &mut Draw) {
         tolerance,
         blend_mode,
         alpha_mode,
+        material,
         fill_color,
         ..
     } = *circle;
@@ -184,5 +196,6 @@ fn fill(circle: &Circle, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
@@ -0,0 +1,261 @@
+use lyon::path::iterator::PathIterator;
+use lyon::path::{Event, Path};
+use notan_graphics::color::Color;
+use notan_math::Vec2;
+
+/// Helper methods to set a dash pattern on the strokes
+pub trait DrawDash {
+    /// Returns the object's dash pattern and offset
+    fn dash_pattern(&mut self) -> &mut Option<(Vec<f32>, f32)>;
+
+    /// Alternate lengths of dashes and gaps, odd patterns are repeated
+    fn dash(&mut self, pattern: &[f32]) -> &mut Self {
+        let dash = self.dash_pattern();
+        let offset = dash.as_ref().map_or(0.0, |(_, offset)| *offset);
+        *dash = Some((pattern.to_vec(), offset));
+        self
+    }
+
+    /// Distance where the dash pattern starts
+    fn dash_offset(&mut self, offset: f32) -> &mut Self {
+        let dash = self.dash_pattern();
+        match dash {
+            Some((_, o)) => *o = offset,
+            None => *dash = Some((vec![], offset)),
+        }
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct StrokePoint {
+    pub pos: Vec2,
+    pub color: Color,
+}
+
+impl StrokePoint {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let (a, b) = (self.color, other.color);
+        Self {
+            pos: self.pos.lerp(other.pos, t),
+            color: Color::new(
+                a.r + (b.r - a.r) * t,
+                a.g + (b.g - a.g) * t,
+                a.b + (b.b - a.b) * t,
+                a.a + (b.a - a.a) * t,
+            ),
+        }
+    }
+}
+
+/// Returns the pattern to use, odd patterns are repeated like on svg
+fn valid_pattern(pattern: &[f32]) -> Option<Vec<f32>> {
+    let total: f32 = pattern.iter().sum();
+    let is_valid = total > 0.0 && pattern.iter().all(|v| v.is_finite() && *v >= 0.0);
+    if !is_valid {
+        return None;
+    }
+
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 != 0 {
+        pattern.extend_from_within(..);
+    }
+
+    Some(pattern)
+}
+
+/// Splits the polyline in dashes following the pattern of dash and gap lengths
+/// Zero length dashes are kept as a dash of two equal points to draw dots using caps
+pub(super) fn dash_polyline(
+    points: &[StrokePoint],
+    closed: bool,
+    pattern: &[f32],
+    offset: f32,
+) -> Vec<Vec<StrokePoint>> {
+    let Some(pattern) = valid_pattern(pattern) else {
+        return vec![points.to_vec()];
+    };
+
+    let mut points = points.to_vec();
+    if closed {
+        if let Some(first) = points.first().copied() {
+            points.push(first);
+        }
+    }
+
+    // move to the pattern's entry where the offset lands
+    let total: f32 = pattern.iter().sum();
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut offset = offset.rem_euclid(total);
+    while offset > 0.0 {
+        if offset >= remaining {
+            offset -= remaining;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        } else {
+            remaining -= offset;
+            offset = 0.0;
+        }
+    }
+
+    let mut dashes = vec![];
+    let mut current = vec![];
+    if index % 2 == 0 {
+        current.extend(points.first());
+    }
+
+    points.windows(2).for_each(|w| {
+        let (a, b) = (&w[0], &w[1]);
+        let len = a.pos.distance(b.pos);
+        if len <= 0.0 {
+            return;
+        }
+
+        let mut t = 0.0;
+        while len - t >= remaining {
+            t += remaining;
+            let p = a.lerp(b, t / len);
+            current.push(p);
+            if index % 2 == 0 {
+                dashes.push(std::mem::take(&mut current));
+            }
+
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+
+        remaining -= len - t;
+        if index % 2 == 0 && current.last().map(|p| p.pos) != Some(b.pos) {
+            current.push(*b);
+        }
+    });
+
+    if current.len() > 1 {
+        dashes.push(current);
+    }
+
+    dashes
+}
+
+/// Removes the length given from the end of the polyline
+pub(super) fn trim_end(points: &mut Vec<StrokePoint>, length: f32) {
+    let mut length = length;
+    while points.len() > 1 && length > 0.0 {
+        let last = points.len() - 1;
+        let (a, b) = (points[last - 1], points[last]);
+        let len = a.pos.distance(b.pos);
+        if len > length {
+            points[last] = b.lerp(&a, length / len);
+            return;
+        }
+
+        length -= len;
+        points.pop();
+    }
+}
+
+/// Removes the length given from the start of the polyline
+pub(super) fn trim_start(points: &mut Vec<StrokePoint>, length: f32) {
+    points.reverse();
+    trim_end(points, length);
+    points.reverse();
+}
+
+/// Flattens the path and splits the sub-paths following the dash pattern
+pub(super) fn dash_lyon_path(path: &Path, tolerance: f32, pattern: &[f32], offset: f32) -> Path {
+    let to_point = |p: lyon::math::Point| StrokePoint {
+        pos: Vec2::new(p.x, p.y),
+        color: Color::WHITE,
+    };
+
+    let mut builder = Path::builder();
+    let mut points = vec![];
+    let mut add_dashes = |points: &[StrokePoint], closed: bool| {
+        dash_polyline(points, closed, pattern, offset)
+            .iter()
+            .for_each(|dash| {
+                let mut iter = dash.iter().map(|p| lyon::math::point(p.pos.x, p.pos.y));
+                if let Some(first) = iter.next() {
+                    builder.begin(first);
+                    iter.for_each(|p| {
+                        builder.line_to(p);
+                    });
+                    builder.end(false);
+                }
+            });
+    };
+
+    path.iter().flattened(tolerance).for_each(|evt| match evt {
+        Event::Begin { at } => {
+            points.clear();
+            points.push(to_point(at));
+        }
+        Event::Line { to, .. } => points.push(to_point(to)),
+        Event::End { close, .. } => add_dashes(&points, close),
+        _ => {}
+    });
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(points: &[(f32, f32)]) -> Vec<StrokePoint> {
+        points
+            .iter()
+            .map(|(x, y)| StrokePoint {
+                pos: Vec2::new(*x, *y),
+                color: Color::WHITE,
+            })
+            .collect()
+    }
+
+    fn positions(dashes: &[Vec<StrokePoint>]) -> Vec<Vec<(f32, f32)>> {
+        dashes
+            .iter()
+            .map(|d| d.iter().map(|p| (p.pos.x, p.pos.y)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn dash_corners_and_offset() {
+        let points = line(&[(0.0, 0.0), (20.0, 0.0), (20.0, 20.0)]);
+
+        // dashes keep the corners
+        let dashes = dash_polyline(&points, false, &[15.0, 5.0], 0.0);
+        assert_eq!(
+            positions(&dashes),
+            vec![
+                vec![(0.0, 0.0), (15.0, 0.0)],
+                vec![(20.0, 0.0), (20.0, 15.0)],
+            ]
+        );
+
+        // odd patterns are repeated and the offset moves the pattern
+        let dashes = dash_polyline(&points, false, &[10.0], 5.0);
+        assert_eq!(
+            positions(&dashes),
+            vec![
+                vec![(0.0, 0.0), (5.0, 0.0)],
+                vec![(15.0, 0.0), (20.0, 0.0), (20.0, 5.0)],
+                vec![(20.0, 15.0), (20.0, 20.0)],
+            ]
+        );
+
+        // zero length dashes are dots
+        let dashes = dash_polyline(&points, false, &[0.0, 10.0], 0.0);
+        assert_eq!(dashes.len(), 5);
+        assert!(dashes.iter().all(|d| d.len() == 2 && d[0] == d[1]));
+
+        // invalid patterns keep the polyline
+        let dashes = dash_polyline(&points, false, &[0.0, -1.0], 0.0);
+        assert_eq!(dashes, vec![points.clone()]);
+
+        let mut trimmed = points;
+        trim_end(&mut trimmed, 25.0);
+        assert_eq!(positions(&[trimmed]), vec![vec![(0.0, 0.0), (15.0, 0.0)]]);
+    }
+}
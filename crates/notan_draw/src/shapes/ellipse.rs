@@ -3,13 +3,14 @@ use super::tess::TessMode;
 use super::tess::*;
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use lyon::tessellation::*;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 
-pub struct Ellipse {
+pub struct Ellipse<'a> {
     color: Color,
     pos: (f32, f32),
     size: (f32, f32),
@@ -20,13 +21,14 @@ pub struct Ellipse {
     tolerance: f32,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
     stroke_color: Option<Color>,
 }
 
-impl Ellipse {
+impl<'a> Ellipse<'a> {
     pub fn new(pos: (f32, f32), size: (f32, f32)) -> Self {
         Self {
             color: Color::WHITE,
@@ -39,6 +41,7 @@ impl Ellipse {
             rotation: 0.0,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -103,15 +106,21 @@ impl Ellipse {
         self.alpha_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Ellipse {
+impl DrawTransform for Ellipse<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Ellipse {
+impl DrawProcess for Ellipse<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let modes = self.modes;
         modes.iter().enumerate().for_each(|(i, mode)| match mode {
@@ -141,6 +150,7 @@ fn stroke(ellipse: &Ellipse, draw: &mut Draw) {
         tolerance,
         blend_mode,
         alpha_mode,
+        material,
         stroke_color,
         ..
     } = *ellipse;
@@ -161,6 +171,7 @@ fn stroke(ellipse: &Ellipse, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
 
@@ -175,6 +186,7 @@ fn fill(ellipse: &Ellipse, draw: &mut Draw) {
         tolerance,
         blend_mode,
         alpha_mode,
+        material,
         fill_color,
         ..
     } = *ellipse;
@@ -193,5 +205,6 @@ fn fill(ellipse: &Ellipse, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
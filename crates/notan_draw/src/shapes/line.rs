@@ -1,4 +1,6 @@
+use super::dash::DrawDash;
 use super::path::Path;
+use super::polyline::Polyline;
 use crate::builder::DrawProcess;
 use crate::draw::Draw;
 use crate::transform::DrawTransform;
@@ -12,6 +14,9 @@ pub struct Line {
     stroke_width: f32,
     alpha: f32,
     matrix: Option<Mat3>,
+    dash: Option<(Vec<f32>, f32)>,
+    arrows: (Option<f32>, Option<f32>),
+    round_cap: bool,
 }
 
 impl Line {
@@ -23,6 +28,9 @@ impl Line {
             stroke_width: 1.0,
             alpha: 1.0,
             matrix: None,
+            dash: None,
+            arrows: (None, None),
+            round_cap: false,
         }
     }
 
@@ -40,6 +48,34 @@ impl Line {
         self.alpha = alpha;
         self
     }
+
+    /// Draws dots separated by the spacing
+    pub fn dotted(&mut self, spacing: f32) -> &mut Self {
+        self.dash(&[0.0, spacing]).round_cap()
+    }
+
+    pub fn round_cap(&mut self) -> &mut Self {
+        self.round_cap = true;
+        self
+    }
+
+    /// Draws a filled arrow of the size given at the start of the line
+    pub fn start_arrow(&mut self, size: f32) -> &mut Self {
+        self.arrows.0 = Some(size);
+        self
+    }
+
+    /// Draws a filled arrow of the size given at the end of the line
+    pub fn end_arrow(&mut self, size: f32) -> &mut Self {
+        self.arrows.1 = Some(size);
+        self
+    }
+}
+
+impl DrawDash for Line {
+    fn dash_pattern(&mut self) -> &mut Option<(Vec<f32>, f32)> {
+        &mut self.dash
+    }
 }
 
 impl DrawTransform for Line {
@@ -57,8 +93,42 @@ impl DrawProcess for Line {
             stroke_width,
             alpha,
             matrix,
+            dash,
+            arrows: (start_arrow, end_arrow),
+            round_cap,
         } = self;
 
+        // the stroke options are only available on polylines
+        let is_polyline =
+            dash.is_some() || start_arrow.is_some() || end_arrow.is_some() || round_cap;
+        if is_polyline {
+            let mut line = Polyline::new(&[(x1, y1), (x2, y2)]);
+            line.width(stroke_width).color(color).alpha(alpha);
+
+            if let Some((pattern, offset)) = dash {
+                line.dash(&pattern).dash_offset(offset);
+            }
+
+            if let Some(size) = start_arrow {
+                line.start_arrow(size);
+            }
+
+            if let Some(size) = end_arrow {
+                line.end_arrow(size);
+            }
+
+            if round_cap {
+                line.round_cap();
+            }
+
+            if let Some(m) = matrix {
+                line.transform(m);
+            }
+
+            line.draw_process(draw);
+            return;
+        }
+
         let mut path = Path::new();
         path.move_to(x1, y1)
             .line_to(x2, y2)
@@ -23,7 +23,7 @@ const SHAPES_VERTEX: ShaderSource = vertex_shader! {
 };
 
 //language=glsl
-const SHAPES_FRAGMENT: ShaderSource = fragment_shader! {
+pub(crate) const SHAPES_FRAGMENT: ShaderSource = fragment_shader! {
     r#"
     #version 450
     precision mediump float;
@@ -48,12 +48,18 @@ pub fn create_shape_pipeline(
     fragment: Option<&ShaderSource>,
 ) -> Result<Pipeline, String> {
     let fragment = fragment.unwrap_or(&SHAPES_FRAGMENT);
+    shape_pipeline_builder(device, fragment).build()
+}
+
+pub(crate) fn shape_pipeline_builder<'a>(
+    device: &'a mut Device,
+    fragment: &'a ShaderSource,
+) -> PipelineBuilder<'a, 'a> {
     device
         .create_pipeline()
         .from(&SHAPES_VERTEX, fragment)
         .with_vertex_info(&vertex_info())
         .with_color_blend(BlendMode::NORMAL)
-        .build()
 }
 
 pub(crate) struct ShapePainter {
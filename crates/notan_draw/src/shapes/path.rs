@@ -1,6 +1,8 @@
+use super::dash::{dash_lyon_path, DrawDash};
 use super::tess::{fill_lyon_path, stroke_lyon_path, TessMode};
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use lyon::math::point;
 use lyon::path::path::Builder;
@@ -9,7 +11,7 @@ use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 
-pub struct Path {
+pub struct Path<'a> {
     stroke_options: StrokeOptions,
     fill_options: FillOptions,
     builder: Builder,
@@ -19,19 +21,21 @@ pub struct Path {
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
     stroke_color: Option<Color>,
+    dash: Option<(Vec<f32>, f32)>,
 }
 
-impl Default for Path {
+impl Default for Path<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Path {
+impl<'a> Path<'a> {
     pub fn new() -> Self {
         let stroke_options = StrokeOptions::DEFAULT.with_miter_limit(f32::MAX);
 
@@ -45,10 +49,12 @@ impl Path {
             matrix: None,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
             stroke_color: None,
+            dash: None,
         }
     }
 
@@ -186,9 +192,15 @@ impl Path {
         self.alpha_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawProcess for Path {
+impl DrawProcess for Path<'_> {
     fn draw_process(mut self, draw: &mut Draw) {
         if self.initialized {
             self.builder.end(false);
@@ -210,7 +222,13 @@ impl DrawProcess for Path {
     }
 }
 
-impl DrawTransform for Path {
+impl DrawDash for Path<'_> {
+    fn dash_pattern(&mut self) -> &mut Option<(Vec<f32>, f32)> {
+        &mut self.dash
+    }
+}
+
+impl DrawTransform for Path<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
@@ -224,6 +242,7 @@ fn fill(path: &Path, draw: &mut Draw) {
         matrix,
         blend_mode,
         alpha_mode,
+        material,
         fill_color,
         ..
     } = *path;
@@ -240,6 +259,7 @@ fn fill(path: &Path, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
 
@@ -251,7 +271,9 @@ fn stroke(path: &Path, draw: &mut Draw) {
         matrix,
         blend_mode,
         alpha_mode,
+        material,
         stroke_color,
+        ref dash,
         ..
     } = *path;
 
@@ -259,6 +281,12 @@ fn stroke(path: &Path, draw: &mut Draw) {
     let color = color.with_alpha(color.a * alpha);
 
     let path = path.builder.clone().build();
+    let path = match dash {
+        Some((pattern, offset)) => {
+            dash_lyon_path(&path, stroke_options.tolerance, pattern, *offset)
+        }
+        None => path,
+    };
     let (vertices, indices) = stroke_lyon_path(&path, color, &stroke_options);
 
     draw.add_shape(&ShapeInfo {
@@ -267,5 +295,6 @@ fn stroke(path: &Path, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
@@ -2,6 +2,7 @@ use super::path::Path;
 use super::tess::TessMode;
 use crate::builder::DrawProcess;
 use crate::draw::Draw;
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use crate::{DrawBuilder, DrawShapes};
 use notan_graphics::color::Color;
@@ -9,13 +10,14 @@ use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 use std::f32::consts::PI;
 
-pub struct Polygon {
+pub struct Polygon<'a> {
     color: Color,
     pos: (f32, f32),
     stroke_width: f32,
     alpha: f32,
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
@@ -24,7 +26,7 @@ pub struct Polygon {
     radius: f32,
 }
 
-impl Polygon {
+impl<'a> Polygon<'a> {
     pub fn new(sides: u8, radius: f32) -> Self {
         Self {
             color: Color::WHITE,
@@ -33,6 +35,7 @@ impl Polygon {
             alpha: 1.0,
             matrix: None,
             blend_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -84,15 +87,21 @@ impl Polygon {
         self.blend_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Polygon {
+impl DrawTransform for Polygon<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Polygon {
+impl DrawProcess for Polygon<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let mut path_builder = draw.path();
         draw_polygon(
@@ -108,6 +117,10 @@ impl DrawProcess for Polygon {
             path_builder.blend_mode(bm);
         }
 
+        if let Some(m) = self.material {
+            path_builder.material(m);
+        }
+
         if let Some(m) = self.matrix {
             path_builder.transform(m);
         }
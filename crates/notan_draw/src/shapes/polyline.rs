@@ -0,0 +1,283 @@
+use super::dash::{dash_polyline, trim_end, trim_start, DrawDash, StrokePoint};
+use super::tess::stroke_lyon_path_with_colors;
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
+use crate::transform::DrawTransform;
+use lyon::math::point;
+use lyon::tessellation::*;
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_math::{Mat3, Vec2};
+
+/// Stroke going through a list of points, with optional dash pattern,
+/// per vertex colors and arrow heads
+pub struct Polyline<'a> {
+    points: Vec<(f32, f32)>,
+    colors: Vec<Color>,
+    color: Color,
+    alpha: f32,
+    closed: bool,
+    stroke_options: StrokeOptions,
+    dash: Option<(Vec<f32>, f32)>,
+    arrows: (Option<f32>, Option<f32>),
+    matrix: Option<Mat3>,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
+}
+
+impl<'a> Polyline<'a> {
+    pub fn new(points: &[(f32, f32)]) -> Self {
+        Self {
+            points: points.to_vec(),
+            colors: vec![],
+            color: Color::WHITE,
+            alpha: 1.0,
+            closed: false,
+            stroke_options: StrokeOptions::DEFAULT,
+            dash: None,
+            arrows: (None, None),
+            matrix: None,
+            blend_mode: None,
+            alpha_mode: None,
+            material: None,
+        }
+    }
+
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    /// Color of each point, the points without color use the main color
+    pub fn color_vertex(&mut self, colors: &[Color]) -> &mut Self {
+        self.colors = colors.to_vec();
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn width(&mut self, width: f32) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_line_width(width);
+        self
+    }
+
+    /// Joins the last point with the first one
+    pub fn closed(&mut self) -> &mut Self {
+        self.closed = true;
+        self
+    }
+
+    /// Draws dots separated by the spacing, the dots use round caps
+    pub fn dotted(&mut self, spacing: f32) -> &mut Self {
+        self.dash(&[0.0, spacing]).round_cap()
+    }
+
+    /// Draws a filled arrow of the size given at the start of the line
+    pub fn start_arrow(&mut self, size: f32) -> &mut Self {
+        self.arrows.0 = Some(size);
+        self
+    }
+
+    /// Draws a filled arrow of the size given at the end of the line
+    pub fn end_arrow(&mut self, size: f32) -> &mut Self {
+        self.arrows.1 = Some(size);
+        self
+    }
+
+    pub fn tolerance(&mut self, tolerance: f32) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_tolerance(tolerance);
+        self
+    }
+
+    pub fn round_cap(&mut self) -> &mut Self {
+        self.stroke_options = self
+            .stroke_options
+            .with_start_cap(LineCap::Round)
+            .with_end_cap(LineCap::Round);
+        self
+    }
+
+    pub fn butt_cap(&mut self) -> &mut Self {
+        self.stroke_options = self
+            .stroke_options
+            .with_start_cap(LineCap::Butt)
+            .with_end_cap(LineCap::Butt);
+        self
+    }
+
+    pub fn square_cap(&mut self) -> &mut Self {
+        self.stroke_options = self
+            .stroke_options
+            .with_start_cap(LineCap::Square)
+            .with_end_cap(LineCap::Square);
+        self
+    }
+
+    pub fn miter_join(&mut self) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_line_join(LineJoin::Miter);
+        self
+    }
+
+    pub fn round_join(&mut self) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_line_join(LineJoin::Round);
+        self
+    }
+
+    pub fn bevel_join(&mut self) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_line_join(LineJoin::Bevel);
+        self
+    }
+
+    /// Limit of the miter joins relative to the width, longer miters are beveled (4 by default)
+    pub fn miter_limit(&mut self, limit: f32) -> &mut Self {
+        self.stroke_options = self.stroke_options.with_miter_limit(limit.max(1.0));
+        self
+    }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
+}
+
+impl DrawDash for Polyline<'_> {
+    fn dash_pattern(&mut self) -> &mut Option<(Vec<f32>, f32)> {
+        &mut self.dash
+    }
+}
+
+impl DrawTransform for Polyline<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for Polyline<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let Self {
+            points,
+            colors,
+            color,
+            alpha,
+            closed,
+            stroke_options,
+            dash,
+            arrows: (start_arrow, end_arrow),
+            matrix,
+            blend_mode,
+            alpha_mode,
+            material,
+        } = self;
+
+        let mut points = points
+            .iter()
+            .enumerate()
+            .map(|(i, (x, y))| {
+                let c = colors.get(i).copied().unwrap_or(color);
+                StrokePoint {
+                    pos: Vec2::new(*x, *y),
+                    color: c.with_alpha(c.a * alpha),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if points.len() < 2 {
+            return;
+        }
+
+        // arrows are only used on open lines, the stroke is shortened to keep the tip sharp
+        let mut vertices = vec![];
+        let mut indices = vec![];
+        if !closed {
+            let arrow_start = start_arrow.and_then(|size| {
+                let arrow = arrow_head(points.iter(), size)?;
+                trim_start(&mut points, size);
+                Some(arrow)
+            });
+
+            let arrow_end = end_arrow.and_then(|size| {
+                let arrow = arrow_head(points.iter().rev(), size)?;
+                trim_end(&mut points, size);
+                Some(arrow)
+            });
+
+            arrow_start
+                .into_iter()
+                .chain(arrow_end)
+                .for_each(|(corners, c)| {
+                    let base = (vertices.len() / 6) as u32;
+                    indices.extend_from_slice(&[base, base + 1, base + 2]);
+                    corners.iter().for_each(|p| {
+                        vertices.extend_from_slice(&[p.x, p.y, c.r, c.g, c.b, c.a]);
+                    });
+                });
+        }
+
+        let lines = match &dash {
+            Some((pattern, offset)) => dash_polyline(&points, closed, pattern, *offset),
+            None => vec![points],
+        };
+
+        let mut builder = lyon::path::Path::builder_with_attributes(4);
+        lines.iter().for_each(|line| {
+            let mut iter = line.iter().map(|p| {
+                let c = p.color;
+                (point(p.pos.x, p.pos.y), [c.r, c.g, c.b, c.a])
+            });
+
+            if let Some((first, attrs)) = iter.next() {
+                builder.begin(first, &attrs);
+                iter.for_each(|(p, attrs)| {
+                    builder.line_to(p, &attrs);
+                });
+                builder.end(closed && dash.is_none());
+            }
+        });
+
+        let path = builder.build();
+        let (stroke_vertices, stroke_indices) =
+            stroke_lyon_path_with_colors(&path, &stroke_options);
+
+        let base = (vertices.len() / 6) as u32;
+        indices.extend(stroke_indices.iter().map(|i| i + base));
+        vertices.extend(stroke_vertices);
+
+        draw.add_shape(&ShapeInfo {
+            transform: matrix.as_ref(),
+            vertices: &vertices,
+            indices: &indices,
+            blend_mode,
+            alpha_mode,
+            material,
+        });
+    }
+}
+
+// corners of the arrow with the tip on the first point of the iterator
+fn arrow_head<'a>(
+    mut points: impl Iterator<Item = &'a StrokePoint>,
+    size: f32,
+) -> Option<([Vec2; 3], Color)> {
+    let tip = points.next()?;
+    let next = points.find(|p| p.pos != tip.pos)?;
+    let dir = (next.pos - tip.pos).normalize();
+    let base = tip.pos + dir * size;
+    let side = dir.perp() * size * 0.5;
+    Some(([tip.pos, base + side, base - side], tip.color))
+}
@@ -3,13 +3,14 @@ use super::tess::TessMode;
 use super::tess::*;
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use lyon::tessellation::*;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 
-pub struct Rectangle {
+pub struct Rectangle<'a> {
     colors: [Color; 4],
     pos: (f32, f32),
     size: (f32, f32),
@@ -20,13 +21,14 @@ pub struct Rectangle {
     corner_tolerance: f32,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
     stroke_color: Option<Color>,
 }
 
-impl Rectangle {
+impl<'a> Rectangle<'a> {
     pub fn new(position: (f32, f32), size: (f32, f32)) -> Self {
         Self {
             colors: [Color::WHITE; 4],
@@ -39,6 +41,7 @@ impl Rectangle {
             corner_tolerance: FillOptions::DEFAULT_TOLERANCE,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -130,15 +133,21 @@ impl Rectangle {
         self.alpha_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Rectangle {
+impl DrawTransform for Rectangle<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Rectangle {
+impl DrawProcess for Rectangle<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let modes = self.modes;
         modes.iter().enumerate().for_each(|(i, mode)| match mode {
@@ -168,6 +177,7 @@ fn stroke(quad: &Rectangle, draw: &mut Draw) {
         corner_tolerance,
         blend_mode,
         alpha_mode,
+        material,
         stroke_color,
         ..
     } = *quad;
@@ -193,6 +203,7 @@ fn stroke(quad: &Rectangle, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
 
@@ -207,6 +218,7 @@ fn fill(quad: &Rectangle, draw: &mut Draw) {
         corner_tolerance,
         blend_mode,
         alpha_mode,
+        material,
         fill_color,
         ..
     } = *quad;
@@ -218,6 +230,7 @@ fn fill(quad: &Rectangle, draw: &mut Draw) {
             indices,
             blend_mode,
             alpha_mode,
+            material,
         });
     };
 
@@ -2,6 +2,7 @@ use super::path::Path;
 use super::tess::TessMode;
 use crate::builder::DrawProcess;
 use crate::draw::Draw;
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use crate::{DrawBuilder, DrawShapes};
 use notan_graphics::color::Color;
@@ -9,13 +10,14 @@ use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 use std::f32::consts::PI;
 
-pub struct Star {
+pub struct Star<'a> {
     color: Color,
     pos: (f32, f32),
     stroke_width: f32,
     alpha: f32,
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
@@ -25,7 +27,7 @@ pub struct Star {
     inner_radius: f32,
 }
 
-impl Star {
+impl<'a> Star<'a> {
     pub fn new(spikes: u8, outer_radius: f32, inner_radius: f32) -> Self {
         Self {
             color: Color::WHITE,
@@ -34,6 +36,7 @@ impl Star {
             alpha: 1.0,
             matrix: None,
             blend_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -86,15 +89,21 @@ impl Star {
         self.blend_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Star {
+impl DrawTransform for Star<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Star {
+impl DrawProcess for Star<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let mut path_builder = draw.path();
         draw_star(
@@ -111,6 +120,10 @@ impl DrawProcess for Star {
             path_builder.blend_mode(bm);
         }
 
+        if let Some(m) = self.material {
+            path_builder.material(m);
+        }
+
         if let Some(m) = self.matrix {
             path_builder.transform(m);
         }
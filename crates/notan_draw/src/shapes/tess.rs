@@ -63,3 +63,29 @@ pub(super) fn stroke_lyon_path(
 
     (geometry.vertices.concat(), geometry.indices)
 }
+
+/// Stroke a path with the color stored as the rgba attributes of each point
+pub(super) fn stroke_lyon_path_with_colors(
+    path: &Path,
+    options: &StrokeOptions,
+) -> (Vec<f32>, Vec<u32>) {
+    let mut geometry: VertexBuffers<[f32; 6], u32> = VertexBuffers::new();
+    {
+        STROKE_TESSELLATOR.with(|tessellator| {
+            tessellator
+                .borrow_mut()
+                .tessellate_path(
+                    path,
+                    options,
+                    &mut BuffersBuilder::new(&mut geometry, |mut vertex: StrokeVertex| {
+                        let [x, y] = vertex.position().to_array();
+                        let attrs = vertex.interpolated_attributes();
+                        [x, y, attrs[0], attrs[1], attrs[2], attrs[3]]
+                    }),
+                )
+                .unwrap()
+        });
+    }
+
+    (geometry.vertices.concat(), geometry.indices)
+}
@@ -2,12 +2,13 @@ use super::path::Path;
 use super::tess::TessMode;
 use crate::builder::DrawProcess;
 use crate::draw::{Draw, ShapeInfo};
+use crate::material::Material;
 use crate::transform::DrawTransform;
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
 use notan_math::Mat3;
 
-pub struct Triangle {
+pub struct Triangle<'a> {
     colors: [Color; 3],
     points: [(f32, f32); 3],
     stroke_width: f32,
@@ -15,13 +16,14 @@ pub struct Triangle {
     matrix: Option<Mat3>,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
+    material: Option<&'a Material>,
     modes: [Option<TessMode>; 2],
     mode_index: usize,
     fill_color: Option<Color>,
     stroke_color: Option<Color>,
 }
 
-impl Triangle {
+impl<'a> Triangle<'a> {
     pub fn new(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> Self {
         Self {
             colors: [Color::WHITE; 3],
@@ -31,6 +33,7 @@ impl Triangle {
             matrix: None,
             blend_mode: None,
             alpha_mode: None,
+            material: None,
             modes: [None; 2],
             mode_index: 0,
             fill_color: None,
@@ -87,15 +90,21 @@ impl Triangle {
         self.alpha_mode = Some(mode);
         self
     }
+
+    /// Draw the element using a custom material
+    pub fn material(&mut self, material: &'a Material) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
 }
 
-impl DrawTransform for Triangle {
+impl DrawTransform for Triangle<'_> {
     fn matrix(&mut self) -> &mut Option<Mat3> {
         &mut self.matrix
     }
 }
 
-impl DrawProcess for Triangle {
+impl DrawProcess for Triangle<'_> {
     fn draw_process(self, draw: &mut Draw) {
         let modes = self.modes;
         modes.iter().enumerate().for_each(|(i, mode)| match mode {
@@ -122,6 +131,7 @@ fn stroke(triangle: &Triangle, draw: &mut Draw) {
         matrix,
         blend_mode,
         alpha_mode,
+        material,
         stroke_color,
         ..
     } = *triangle;
@@ -136,6 +146,10 @@ fn stroke(triangle: &Triangle, draw: &mut Draw) {
         path.alpha_mode(abm);
     }
 
+    if let Some(m) = material {
+        path.material(m);
+    }
+
     let color = stroke_color.unwrap_or(ca);
     path.move_to(a.0, a.1)
         .line_to(b.0, b.1)
@@ -160,6 +174,7 @@ fn fill(triangle: &Triangle, draw: &mut Draw) {
         matrix,
         blend_mode,
         alpha_mode,
+        material,
         fill_color,
         ..
     } = *triangle;
@@ -182,5 +197,6 @@ fn fill(triangle: &Triangle, draw: &mut Draw) {
         indices: &indices,
         blend_mode,
         alpha_mode,
+        material,
     });
 }
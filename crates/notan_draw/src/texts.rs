@@ -1,19 +1,30 @@
+mod markup;
 mod painter;
+mod rich_text;
 mod text;
 
 use crate::builder::DrawBuilder;
 use crate::draw::Draw;
-pub use notan_text::{CreateFont, Font};
+pub use markup::*;
+pub use notan_text::{
+    Caret, CreateFont, Font, FontFamilyBuilder, FontStyle, LayoutGlyph, TextLayout, TextLine,
+};
 pub use painter::create_text_pipeline;
 pub(crate) use painter::*;
+pub use rich_text::*;
 pub use text::*;
 
 pub trait DrawTextSection {
     fn text<'a>(&mut self, font: &'a Font, text: &'a str) -> DrawBuilder<TextSection<'a>>;
+    fn rich_text<'a>(&mut self, font: &'a Font, text: &'a str) -> DrawBuilder<RichText<'a>>;
 }
 
 impl DrawTextSection for Draw {
     fn text<'a>(&mut self, font: &'a Font, text: &'a str) -> DrawBuilder<TextSection<'a>> {
         DrawBuilder::new(self, TextSection::new(font, text))
     }
+
+    fn rich_text<'a>(&mut self, font: &'a Font, text: &'a str) -> DrawBuilder<RichText<'a>> {
+        DrawBuilder::new(self, RichText::new(font, text))
+    }
 }
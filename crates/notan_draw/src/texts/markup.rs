@@ -0,0 +1,231 @@
+use notan_graphics::color::Color;
+
+/// Style applied by the markup tags to a span of text
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkupStyle {
+    pub color: Option<Color>,
+    pub size: Option<f32>,
+    pub font: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    /// Amplitude of the wave effect, 0.0 means the default amplitude
+    pub wave: Option<f32>,
+    /// Amplitude of the shake effect, 0.0 means the default amplitude
+    pub shake: Option<f32>,
+    /// Name of the custom effect passed to the glyph hook
+    pub effect: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MarkupNode {
+    Text { text: String, style: MarkupStyle },
+    Icon { name: String, style: MarkupStyle },
+}
+
+/// Parse a text with markup tags into styled nodes
+/// Tags: `[color=#f00]`, `[size=20]`, `[font=name]`, `[b]`, `[i]`, `[wave]`, `[shake]`,
+/// `[fx=name]` and `[icon=name]`. Tags are closed with `[/tag]` or `[/]`, `[[` is a literal `[`.
+pub fn parse_markup(text: &str) -> Result<Vec<MarkupNode>, String> {
+    let mut nodes = vec![];
+    let mut stack: Vec<(String, MarkupStyle)> = vec![];
+    let mut style = MarkupStyle::default();
+    let mut buffer = String::new();
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '[' {
+            buffer.push(c);
+            continue;
+        }
+
+        if let Some((_, '[')) = chars.peek() {
+            chars.next();
+            buffer.push('[');
+            continue;
+        }
+
+        let end = text[i..]
+            .find(']')
+            .ok_or_else(|| format!("Unclosed tag at {}", i))?;
+        let tag = &text[i + 1..i + end];
+        while let Some((n, _)) = chars.peek() {
+            if *n > i + end {
+                break;
+            }
+            chars.next();
+        }
+
+        flush_text(&mut nodes, &mut buffer, &style);
+
+        // closing tags
+        if let Some(name) = tag.strip_prefix('/') {
+            let (open, prev) = stack
+                .pop()
+                .ok_or_else(|| format!("Unexpected closing tag '[{}]'", tag))?;
+            if !name.is_empty() && name != open {
+                return Err(format!(
+                    "Closing tag '[{}]' doesn't match the tag '[{}]'",
+                    tag, open
+                ));
+            }
+
+            style = prev;
+            continue;
+        }
+
+        let (name, value) = match tag.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (tag.trim(), None),
+        };
+
+        if matches!(name, "icon" | "img") {
+            let name = value.ok_or("The tag 'icon' needs a name")?;
+            nodes.push(MarkupNode::Icon {
+                name: name.to_string(),
+                style: style.clone(),
+            });
+            continue;
+        }
+
+        let prev = style.clone();
+        match (name, value) {
+            ("color", Some(v)) => style.color = Some(parse_color(v)?),
+            ("size", Some(v)) => style.size = Some(parse_number(name, v)?),
+            ("font", Some(v)) => style.font = Some(v.to_string()),
+            ("fx", Some(v)) => style.effect = Some(v.to_string()),
+            ("b", None) => style.bold = true,
+            ("i", None) => style.italic = true,
+            ("wave", v) => style.wave = Some(v.map_or(Ok(0.0), |v| parse_number(name, v))?),
+            ("shake", v) => style.shake = Some(v.map_or(Ok(0.0), |v| parse_number(name, v))?),
+            _ => return Err(format!("Invalid tag '[{}]'", tag)),
+        }
+
+        stack.push((name.to_string(), prev));
+    }
+
+    flush_text(&mut nodes, &mut buffer, &style);
+    Ok(nodes)
+}
+
+/// Returns the number of glyphs (without whitespaces) of a markup text, icons count as one glyph
+pub fn markup_glyphs_count(text: &str) -> usize {
+    match parse_markup(text) {
+        Ok(nodes) => nodes
+            .iter()
+            .map(|n| match n {
+                MarkupNode::Text { text, .. } => {
+                    text.chars().filter(|c| !c.is_whitespace()).count()
+                }
+                MarkupNode::Icon { .. } => 1,
+            })
+            .sum(),
+        Err(_) => text.chars().filter(|c| !c.is_whitespace()).count(),
+    }
+}
+
+fn flush_text(nodes: &mut Vec<MarkupNode>, buffer: &mut String, style: &MarkupStyle) {
+    if buffer.is_empty() {
+        return;
+    }
+
+    nodes.push(MarkupNode::Text {
+        text: std::mem::take(buffer),
+        style: style.clone(),
+    });
+}
+
+fn parse_number(tag: &str, value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .map_err(|_| format!("Invalid value '{}' for the tag '{}'", value, tag))
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    let color = match value.to_lowercase().as_str() {
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "orange" => Color::ORANGE,
+        "pink" => Color::PINK,
+        "purple" => Color::PURPLE,
+        "gray" => Color::GRAY,
+        "aqua" => Color::AQUA,
+        "magenta" => Color::MAGENTA,
+        _ => {
+            let hex = value
+                .strip_prefix('#')
+                .ok_or_else(|| format!("Invalid color '{}'", value))?;
+
+            // expand the short format #rgb and #rgba
+            let hex = if hex.len() <= 4 {
+                hex.chars().flat_map(|c| [c, c]).collect::<String>()
+            } else {
+                hex.to_string()
+            };
+
+            let n =
+                u32::from_str_radix(&hex, 16).map_err(|_| format!("Invalid color '{}'", value))?;
+            match hex.len() {
+                6 => Color::from_hex((n << 8) | 0xff),
+                8 => Color::from_hex(n),
+                _ => return Err(format!("Invalid color '{}'", value)),
+            }
+        }
+    };
+
+    Ok(color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_tags() {
+        let nodes =
+            parse_markup("Hi [color=#f00][b]bold [[red][/b] and [icon=coin][/color]!").unwrap();
+
+        let red = MarkupStyle {
+            color: Some(Color::RED),
+            ..Default::default()
+        };
+        let bold_red = MarkupStyle {
+            bold: true,
+            ..red.clone()
+        };
+
+        assert_eq!(
+            nodes,
+            [
+                MarkupNode::Text {
+                    text: "Hi ".to_string(),
+                    style: MarkupStyle::default()
+                },
+                MarkupNode::Text {
+                    text: "bold [red]".to_string(),
+                    style: bold_red
+                },
+                MarkupNode::Text {
+                    text: " and ".to_string(),
+                    style: red.clone()
+                },
+                MarkupNode::Icon {
+                    name: "coin".to_string(),
+                    style: red
+                },
+                MarkupNode::Text {
+                    text: "!".to_string(),
+                    style: MarkupStyle::default()
+                },
+            ]
+        );
+
+        assert_eq!(markup_glyphs_count("[wave=2]a b[/] [icon=x]"), 3);
+        assert!(parse_markup("[b]text[/i]").is_err());
+        assert!(parse_markup("[size=big]text").is_err());
+        assert!(parse_markup("[b text").is_err());
+    }
+}
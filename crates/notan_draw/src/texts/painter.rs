@@ -1,10 +1,18 @@
 use crate::batch::*;
+use crate::draw::GlyphEffect;
 use crate::manager::process_pipeline;
 use notan_glyph::{GlyphBrush, GlyphInstance, GlyphPipeline};
 use notan_graphics::prelude::*;
 use notan_macro::{fragment_shader, vertex_shader};
 use notan_math::Rect;
-use notan_math::{Mat4, Vec3};
+use notan_math::{vec2, Mat2, Mat3, Mat4, Vec2, Vec3};
+
+// floats per vertex: position, uvs and color
+const VERTEX_SIZE: usize = 8;
+
+// floats per vertex of the texts with outline or shadow: the same ones plus uv bounds,
+// outline color, shadow color, texel and effect
+const EFFECT_VERTEX_SIZE: usize = 26;
 
 //language=glsl
 const TEXT_VERTEX: ShaderSource = vertex_shader! {
@@ -52,6 +60,139 @@ const TEXT_FRAGMENT: ShaderSource = fragment_shader! {
     "#
 };
 
+//language=glsl
+const TEXT_EFFECT_VERTEX: ShaderSource = vertex_shader! {
+    r#"
+    #version 450
+    layout(location = 0) in vec2 a_pos;
+    layout(location = 1) in vec2 a_uvs;
+    layout(location = 2) in vec4 a_color;
+    layout(location = 3) in vec4 a_bounds;
+    layout(location = 4) in vec4 a_outline_color;
+    layout(location = 5) in vec4 a_shadow_color;
+    layout(location = 6) in vec2 a_texel;
+    layout(location = 7) in vec4 a_effect;
+
+    layout(location = 0) out vec4 v_color;
+    layout(location = 1) out vec2 v_uvs;
+    layout(location = 2) out vec4 v_bounds;
+    layout(location = 3) out vec4 v_outline_color;
+    layout(location = 4) out vec4 v_shadow_color;
+    layout(location = 5) out vec2 v_texel;
+    layout(location = 6) out vec4 v_effect;
+    layout(set = 0, binding = 0) uniform Locals {
+        mat4 u_projection;
+    };
+
+    void main() {
+        v_color = a_color;
+        v_uvs = a_uvs;
+        v_bounds = a_bounds;
+        v_outline_color = a_outline_color;
+        v_shadow_color = a_shadow_color;
+        v_texel = a_texel;
+        v_effect = a_effect;
+        gl_Position = u_projection * vec4(a_pos, 0.0, 1.0);
+    }
+    "#
+};
+
+//language=glsl
+const TEXT_EFFECT_FRAGMENT: ShaderSource = fragment_shader! {
+    r#"
+    #version 450
+    precision mediump float;
+
+    layout(location = 0) in vec2 v_uvs;
+    layout(location = 1) in vec4 v_color;
+    layout(location = 2) in vec4 v_bounds;
+    layout(location = 3) in vec4 v_outline_color;
+    layout(location = 4) in vec4 v_shadow_color;
+    layout(location = 5) in vec2 v_texel;
+    layout(location = 6) in vec4 v_effect; // outline width, shadow blur, shadow offset
+
+    layout(binding = 0) uniform sampler2D u_texture;
+
+    layout(location = 0) out vec4 color;
+
+    // 16 taps for each effect
+    const int RINGS = 2;
+    const int DIRECTIONS = 8;
+
+    // alpha of the glyph, the quad is bigger than the glyph when it has effects
+    float coverage(vec2 uvs) {
+        vec2 inside = step(v_bounds.xy, uvs) * step(uvs, v_bounds.zw);
+        return texture(u_texture, uvs).r * inside.x * inside.y;
+    }
+
+    // max alpha inside of the radius (in pixels) to dilate the glyph
+    float dilate(vec2 uvs, float radius) {
+        float value = coverage(uvs);
+        for (int ring = 1; ring <= RINGS; ring++) {
+            float dist = radius * float(ring) / float(RINGS);
+            for (int i = 0; i < DIRECTIONS; i++) {
+                float angle = float(i) * 6.2831853 / float(DIRECTIONS);
+                vec2 offset = vec2(cos(angle), sin(angle)) * dist * v_texel;
+                value = max(value, coverage(uvs + offset));
+            }
+        }
+        return value;
+    }
+
+    // weighted average of the alpha inside of the radius (in pixels)
+    float blur(vec2 uvs, float radius) {
+        float value = coverage(uvs);
+        if (radius <= 0.0) {
+            return value;
+        }
+
+        float total = 1.0;
+        for (int ring = 1; ring <= RINGS; ring++) {
+            float weight = 1.0 - float(ring) / float(RINGS + 1);
+            float dist = radius * float(ring) / float(RINGS);
+            for (int i = 0; i < DIRECTIONS; i++) {
+                float angle = float(i) * 6.2831853 / float(DIRECTIONS);
+                vec2 offset = vec2(cos(angle), sin(angle)) * dist * v_texel;
+                value += coverage(uvs + offset) * weight;
+                total += weight;
+            }
+        }
+        return value / total;
+    }
+
+    vec4 over(vec4 top, vec4 bottom) {
+        float a = top.a + bottom.a * (1.0 - top.a);
+        if (a <= 0.0) {
+            return vec4(0.0);
+        }
+
+        vec3 rgb = (top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / a;
+        return vec4(rgb, a);
+    }
+
+    void main() {
+        vec4 col = vec4(v_color.rgb, v_color.a * coverage(v_uvs));
+
+        float outline_width = v_effect.x;
+        if (outline_width > 0.0 && v_outline_color.a > 0.0) {
+            float outline = dilate(v_uvs, outline_width);
+            col = over(col, vec4(v_outline_color.rgb, v_outline_color.a * outline));
+        }
+
+        if (v_shadow_color.a > 0.0) {
+            float shadow = blur(v_uvs - v_effect.zw * v_texel, v_effect.y);
+            col = over(col, vec4(v_shadow_color.rgb, v_shadow_color.a * shadow));
+        }
+
+        if (col.a <= 0.0) {
+            discard;
+        }
+
+        color = col;
+    }
+    "#
+};
+
 fn vertex_info() -> VertexInfo {
     VertexInfo::new()
         .attr(0, VertexFormat::Float32x2)
@@ -59,20 +200,66 @@ fn vertex_info() -> VertexInfo {
         .attr(2, VertexFormat::Float32x4)
 }
 
+fn effect_vertex_info() -> VertexInfo {
+    VertexInfo::new()
+        .attr(0, VertexFormat::Float32x2)
+        .attr(1, VertexFormat::Float32x2)
+        .attr(2, VertexFormat::Float32x4)
+        .attr(3, VertexFormat::Float32x4)
+        .attr(4, VertexFormat::Float32x4)
+        .attr(5, VertexFormat::Float32x4)
+        .attr(6, VertexFormat::Float32x2)
+        .attr(7, VertexFormat::Float32x4)
+}
+
+/// Vertices and indices of the texts drawn with the same vertex layout
+struct TextBuffers {
+    vbo: Buffer,
+    ebo: Buffer,
+    vertices: Vec<f32>,
+    indices: Vec<u32>,
+    dirty: bool,
+}
+
+impl TextBuffers {
+    fn new(device: &mut Device, info: &VertexInfo) -> Result<Self, String> {
+        let vbo = device.create_vertex_buffer().with_info(info).build()?;
+        let ebo = device.create_index_buffer().build()?;
+
+        Ok(Self {
+            vbo,
+            ebo,
+            vertices: vec![],
+            indices: vec![],
+            dirty: false,
+        })
+    }
+
+    fn upload(&mut self, device: &mut Device) {
+        if self.dirty {
+            self.dirty = false;
+            device.set_buffer_data(&self.vbo, &self.vertices);
+            device.set_buffer_data(&self.ebo, &self.indices);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+}
+
 pub(crate) struct TextPainter {
     pub pipeline: Pipeline,
-    pub vbo: Buffer,
-    pub ebo: Buffer,
     pub ubo: Buffer,
 
+    effect_pipeline: Pipeline,
+    buffers: TextBuffers,
+    effect_buffers: TextBuffers,
     count_chars: usize,
-    vertices: Vec<f32>,
-    indices: Vec<u32>,
     uniforms: [f32; 16],
-    count_vertices: usize,
-    count_indices: usize,
     font_vertices: Vec<GlyphInstance>,
-    dirty_buffer: bool,
+    dirty_uniforms: bool,
 }
 
 impl TextPainter {
@@ -80,12 +267,17 @@ impl TextPainter {
         let uniforms = [0.0; 16];
 
         let pipeline = create_text_pipeline(device, None)?;
-        let vbo = device
-            .create_vertex_buffer()
-            .with_info(&vertex_info())
+        let effect_pipeline = device
+            .create_pipeline()
+            .from(&TEXT_EFFECT_VERTEX, &TEXT_EFFECT_FRAGMENT)
+            .with_vertex_info(&effect_vertex_info())
+            .with_color_blend(BlendMode::NORMAL)
+            .with_alpha_blend(BlendMode::OVER)
+            .with_texture_location(0, "u_texture")
             .build()?;
 
-        let ebo = device.create_index_buffer().build()?;
+        let buffers = TextBuffers::new(device, &vertex_info())?;
+        let effect_buffers = TextBuffers::new(device, &effect_vertex_info())?;
 
         let ubo = device
             .create_uniform_buffer(0, "Locals")
@@ -94,24 +286,21 @@ impl TextPainter {
 
         Ok(Self {
             pipeline,
-            vbo,
-            ebo,
             ubo,
 
+            effect_pipeline,
+            buffers,
+            effect_buffers,
             count_chars: 0,
-            vertices: vec![],
-            indices: vec![],
             uniforms,
-            count_vertices: 0,
-            count_indices: 0,
             font_vertices: vec![],
-            dirty_buffer: false,
+            dirty_uniforms: false,
         })
     }
 
     pub fn push(
         &mut self,
-        device: &mut Device,
+        _device: &mut Device,
         glyph_brush: &mut GlyphBrush,
         renderer: &mut Renderer,
         batch: &Batch,
@@ -119,99 +308,87 @@ impl TextPainter {
         is_rt: bool,
     ) {
         if let BatchType::Text { texts } = &batch.typ {
-            process_pipeline(renderer, batch, &self.pipeline, is_rt);
-            let mut vertices = vec![];
-            let mut indices = vec![];
-
+            // the texts of a batch share the layout, the custom pipelines use the one without effects
+            let has_effects = texts.first().is_some_and(TextData::has_effects);
+            let with_effects = has_effects && batch.pipeline.is_none();
+
+            #[cfg(debug_assertions)]
+            if has_effects && !with_effects {
+                log::warn!("Text outline and shadow are not drawn using a custom text pipeline");
+            }
+            let (pipeline, buffers, vertex_size) = if with_effects {
+                (
+                    &self.effect_pipeline,
+                    &mut self.effect_buffers,
+                    EFFECT_VERTEX_SIZE,
+                )
+            } else {
+                (&self.pipeline, &mut self.buffers, VERTEX_SIZE)
+            };
+
+            process_pipeline(renderer, batch, pipeline, is_rt);
+            let offset = buffers.indices.len();
+
+            let mut start = self.count_chars;
             texts.iter().for_each(|d| {
-                let count = d.count;
-                let start = self.count_chars;
-                let end = start + count;
-                if count <= self.font_vertices.len() {
-                    let vert = &self.font_vertices[start..end];
-                    let (flip_x, flip_y) = d.flip;
-                    vert.iter().enumerate().for_each(|(i, g_instance)| {
-                        let GlyphInstance {
-                            left_top: [x1, y1, _],
-                            right_bottom: [x2, y2],
-                            tex_left_top: [u1, v1],
-                            tex_right_bottom: [u2, v2],
-                            color: [r, g, b, a],
-                        } = *g_instance;
-
-                        let a = a * d.alpha;
-
-                        let matrix = d.transform;
-
-                        let (u1, u2) = if flip_x { (u2, u1) } else { (u1, u2) };
-                        let (v1, v2) = if flip_y { (v2, v1) } else { (v1, v2) };
-
-                        let verts = [
-                            [x1, y1, u1, v1],
-                            [x2, y1, u2, v1],
-                            [x1, y2, u1, v2],
-                            [x2, y2, u2, v2],
-                        ];
-
-                        // compute the matrices and push the vertices and attributes
-                        verts.into_iter().for_each(|[x, y, u, v]| {
-                            let xyz = matrix * Vec3::new(x, y, 1.0);
-                            vertices.extend_from_slice(&[xyz.x, xyz.y, u, v, r, g, b, a]);
+                let end = start + d.count;
+                if end <= self.font_vertices.len() {
+                    let effect = with_effects.then(|| TextEffect::new(d));
+                    self.font_vertices[start..end]
+                        .iter()
+                        .enumerate()
+                        .for_each(|(i, g)| {
+                            let fx = d
+                                .effects
+                                .as_ref()
+                                .and_then(|fx| fx.get(i).copied())
+                                .unwrap_or_default();
+
+                            let n = (buffers.vertices.len() / vertex_size) as u32;
+                            match &effect {
+                                Some(effect) => {
+                                    push_effect_glyph(&mut buffers.vertices, g, &fx, d, effect)
+                                }
+                                None => push_glyph(&mut buffers.vertices, g, &fx, d),
+                            }
+
+                            #[rustfmt::skip]
+                            buffers.indices.extend_from_slice(&[
+                                n    , n + 1, n + 2,
+                                n + 2, n + 1, n + 3
+                            ]);
                         });
-
-                        let n = ((start as u32) + (i as u32)) * 4;
-
-                        #[rustfmt::skip]
-                        indices.extend_from_slice(&[
-                            n    , n + 1, n + 2,
-                            n + 2, n + 1, n + 3
-                        ]);
-                    });
                 }
-                self.count_chars = end;
+                start = end;
             });
+            self.count_chars = start;
 
-            let offset = self.count_indices;
-
-            self.indices.extend(&indices);
-            self.count_indices = self.indices.len();
+            let count = buffers.indices.len() - offset;
+            buffers.dirty = true;
 
-            self.vertices.extend(&vertices);
-            self.count_vertices = self.vertices.len();
+            renderer.bind_texture_slot(0, 0, glyph_brush.texture());
+            renderer.bind_buffers(&[&buffers.vbo, &buffers.ebo, &self.ubo]);
+            renderer.draw(offset as _, count as _);
 
             self.uniforms.copy_from_slice(&projection.to_cols_array());
-
-            self.append_to_renderer(
-                device,
-                renderer,
-                glyph_brush.texture(),
-                None,
-                *projection,
-                renderer.size(),
-                None,
-            );
-
-            renderer.draw(offset as _, indices.len() as _);
-            self.dirty_buffer = true;
+            self.dirty_uniforms = true;
         }
     }
 
     #[inline]
     pub fn upload_buffers(&mut self, device: &mut Device) {
-        if self.dirty_buffer {
-            self.dirty_buffer = false;
-            device.set_buffer_data(&self.vbo, &self.vertices);
-            device.set_buffer_data(&self.ebo, &self.indices);
+        self.buffers.upload(device);
+        self.effect_buffers.upload(device);
+        if self.dirty_uniforms {
+            self.dirty_uniforms = false;
             device.set_buffer_data(&self.ubo, &self.uniforms);
         }
     }
 
     pub fn clear(&mut self) {
         self.count_chars = 0;
-        self.count_vertices = 0;
-        self.count_indices = 0;
-        self.vertices.clear();
-        self.indices.clear();
+        self.buffers.clear();
+        self.effect_buffers.clear();
     }
 }
 
@@ -227,7 +404,7 @@ impl GlyphPipeline for TextPainter {
         _region: Option<Rect>,
     ) {
         renderer.bind_texture_slot(0, 0, texture);
-        renderer.bind_buffers(&[&self.vbo, &self.ebo, &self.ubo]);
+        renderer.bind_buffers(&[&self.buffers.vbo, &self.buffers.ebo, &self.ubo]);
     }
 
     fn upload(&mut self, _device: &mut Device, instances: &[GlyphInstance]) {
@@ -236,6 +413,166 @@ impl GlyphPipeline for TextPainter {
     }
 }
 
+fn push_glyph(vertices: &mut Vec<f32>, g: &GlyphInstance, fx: &GlyphEffect, d: &TextData) {
+    let quad = glyph_quad(g, fx, d.flip, d.alpha, Vec2::ZERO);
+    let [r, g, b, a] = quad.color;
+    quad.corners.iter().zip(quad.uvs).for_each(|(pos, [u, v])| {
+        let xyz = d.transform * Vec3::new(pos.x, pos.y, 1.0);
+        vertices.extend_from_slice(&[xyz.x, xyz.y, u, v, r, g, b, a]);
+    });
+}
+
+// one quad per glyph grown to fit the effects, the fragment shader draws the shadow,
+// the outline and the fill together using the params set on the vertices
+fn push_effect_glyph(
+    vertices: &mut Vec<f32>,
+    g: &GlyphInstance,
+    fx: &GlyphEffect,
+    d: &TextData,
+    effect: &TextEffect,
+) {
+    let quad = glyph_quad(g, fx, d.flip, d.alpha, effect.padding);
+    let [o_r, o_g, o_b, o_a] = effect.outline_color;
+    let [s_r, s_g, s_b, s_a] = effect.shadow_color;
+    let [r, g, b, a] = quad.color;
+    let [u1, v1, u2, v2] = quad.bounds;
+    let [tx, ty] = quad.texel.to_array();
+    let [width, blur, ox, oy] = effect.params;
+    let [ox, oy] = shadow_offset(&d.transform, fx.rotation, vec2(ox, oy)).to_array();
+    quad.corners.iter().zip(quad.uvs).for_each(|(pos, [u, v])| {
+        let xyz = d.transform * Vec3::new(pos.x, pos.y, 1.0);
+        #[rustfmt::skip]
+        vertices.extend_from_slice(&[
+            xyz.x, xyz.y, u, v, r, g, b, a,
+            u1, v1, u2, v2,
+            o_r, o_g, o_b, o_a * quad.alpha,
+            s_r, s_g, s_b, s_a * quad.alpha,
+            tx, ty, width, blur, ox, oy,
+        ]);
+    });
+}
+
+// the shadow doesn't rotate or scale with the text or the glyph, the offset is converted
+// to the glyph's space undoing the transformation
+fn shadow_offset(transform: &Mat3, rotation: f32, offset: Vec2) -> Vec2 {
+    let glyph_to_screen = Mat2::from_mat3(*transform) * Mat2::from_angle(rotation);
+    if glyph_to_screen.determinant().abs() <= f32::EPSILON {
+        return Vec2::ZERO;
+    }
+
+    glyph_to_screen.inverse() * offset
+}
+
+/// Outline and shadow values shared by the glyphs of a text
+struct TextEffect {
+    /// Pixels added to each side of the glyphs to fit the effects
+    padding: Vec2,
+    outline_color: [f32; 4],
+    shadow_color: [f32; 4],
+    /// Outline width, shadow blur and shadow offset in pixels
+    params: [f32; 4],
+}
+
+impl TextEffect {
+    fn new(d: &TextData) -> Self {
+        let (outline_color, width) = d.outline.map_or(([0.0; 4], 0.0), |(color, width)| {
+            (color.rgba(), width.max(0.0))
+        });
+        let (shadow_color, offset, blur) = d
+            .shadow
+            .map_or(([0.0; 4], Vec2::ZERO, 0.0), |(color, offset, blur)| {
+                (color.rgba(), offset, blur.max(0.0))
+            });
+
+        Self {
+            padding: Vec2::splat(width.max(offset.length() + blur)),
+            outline_color,
+            shadow_color,
+            params: [width, blur, offset.x, offset.y],
+        }
+    }
+}
+
+struct GlyphQuad {
+    corners: [Vec2; 4],
+    uvs: [[f32; 2]; 4],
+    /// Min and max uvs of the glyph on the atlas
+    bounds: [f32; 4],
+    /// Uvs per pixel
+    texel: Vec2,
+    color: [f32; 4],
+    /// alpha multiplier used by the outline and shadow
+    alpha: f32,
+}
+
+fn glyph_quad(
+    g: &GlyphInstance,
+    fx: &GlyphEffect,
+    flip: (bool, bool),
+    alpha: f32,
+    padding: Vec2,
+) -> GlyphQuad {
+    let GlyphInstance {
+        left_top: [x1, y1, _],
+        right_bottom: [x2, y2],
+        tex_left_top: [u1, v1],
+        tex_right_bottom: [u2, v2],
+        color,
+    } = *g;
+
+    let bounds = [u1.min(u2), v1.min(v2), u1.max(u2), v1.max(v2)];
+
+    let (flip_x, flip_y) = flip;
+    let (u1, u2) = if flip_x { (u2, u1) } else { (u1, u2) };
+    let (v1, v2) = if flip_y { (v2, v1) } else { (v1, v2) };
+
+    let size = vec2(x2 - x1, y2 - y1);
+    let texel = vec2(
+        if size.x > 0.0 {
+            (u2 - u1) / size.x
+        } else {
+            0.0
+        },
+        if size.y > 0.0 {
+            (v2 - v1) / size.y
+        } else {
+            0.0
+        },
+    );
+
+    // the quad grows to fit the effects and the uvs grow with it out of the bounds
+    let (x1, y1, x2, y2) = (
+        x1 - padding.x,
+        y1 - padding.y,
+        x2 + padding.x,
+        y2 + padding.y,
+    );
+    let pad_uvs = padding * texel;
+    let (u1, v1, u2, v2) = (
+        u1 - pad_uvs.x,
+        v1 - pad_uvs.y,
+        u2 + pad_uvs.x,
+        v2 + pad_uvs.y,
+    );
+
+    let center = vec2(x1 + x2, y1 + y2) * 0.5;
+    let rotation = Vec2::from_angle(fx.rotation);
+    let corners = [vec2(x1, y1), vec2(x2, y1), vec2(x1, y2), vec2(x2, y2)]
+        .map(|p| center + rotation.rotate(p - center) + fx.offset);
+
+    let alpha = alpha * fx.alpha;
+    let [r, g, b, a] = fx.color.map_or(color, |c| c.rgba());
+
+    GlyphQuad {
+        corners,
+        uvs: [[u1, v1], [u2, v1], [u1, v2], [u2, v2]],
+        bounds,
+        texel,
+        color: [r, g, b, a * alpha],
+        alpha,
+    }
+}
+
 pub fn create_text_pipeline(
     device: &mut Device,
     fragment: Option<&ShaderSource>,
@@ -250,3 +587,132 @@ pub fn create_text_pipeline(
         .with_texture_location(0, "u_texture")
         .build()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn glyph() -> GlyphInstance {
+        GlyphInstance {
+            left_top: [10.0, 20.0, 0.0],
+            right_bottom: [20.0, 40.0],
+            tex_left_top: [0.1, 0.2],
+            tex_right_bottom: [0.2, 0.4],
+            color: [1.0; 4],
+        }
+    }
+
+    fn text_data(outline: Option<(Color, f32)>, shadow: Option<(Color, Vec2, f32)>) -> TextData {
+        TextData {
+            transform: Mat3::IDENTITY,
+            section: Default::default(),
+            alpha: 1.0,
+            count: 1,
+            flip: (false, false),
+            effects: None,
+            outline,
+            shadow,
+        }
+    }
+
+    #[test]
+    fn glyph_quad_without_effects() {
+        let fx = GlyphEffect {
+            alpha: 0.5,
+            ..Default::default()
+        };
+        let quad = glyph_quad(&glyph(), &fx, (false, true), 0.5, Vec2::ZERO);
+        assert_eq!(
+            quad.corners,
+            [
+                vec2(10.0, 20.0),
+                vec2(20.0, 20.0),
+                vec2(10.0, 40.0),
+                vec2(20.0, 40.0)
+            ]
+        );
+        assert_eq!(quad.uvs, [[0.1, 0.4], [0.2, 0.4], [0.1, 0.2], [0.2, 0.2]]);
+        assert_eq!(quad.bounds, [0.1, 0.2, 0.2, 0.4]);
+        assert_eq!(quad.color, [1.0, 1.0, 1.0, 0.25]);
+        assert_eq!(quad.alpha, 0.25);
+    }
+
+    #[test]
+    fn glyph_quad_padding() {
+        let quad = glyph_quad(
+            &glyph(),
+            &GlyphEffect::default(),
+            (false, false),
+            1.0,
+            vec2(2.0, 2.0),
+        );
+        assert_eq!(quad.corners[0], vec2(8.0, 18.0));
+        assert_eq!(quad.corners[3], vec2(22.0, 42.0));
+
+        // the uvs grow by the same pixels and the bounds stay on the glyph
+        let [[u1, v1], _, _, [u2, v2]] = quad.uvs;
+        let eps = 0.0001;
+        assert!((u1 - 0.08).abs() < eps && (v1 - 0.18).abs() < eps);
+        assert!((u2 - 0.22).abs() < eps && (v2 - 0.42).abs() < eps);
+        assert_eq!(quad.bounds, [0.1, 0.2, 0.2, 0.4]);
+    }
+
+    #[test]
+    fn text_effect_padding() {
+        let effect = TextEffect::new(&text_data(None, None));
+        assert_eq!(effect.padding, Vec2::ZERO);
+
+        let effect = TextEffect::new(&text_data(Some((Color::RED, 2.0)), None));
+        assert_eq!(effect.padding, Vec2::splat(2.0));
+        assert_eq!(effect.params, [2.0, 0.0, 0.0, 0.0]);
+
+        // the shadow needs the offset and the blur around the glyphs
+        let effect = TextEffect::new(&text_data(
+            Some((Color::RED, 2.0)),
+            Some((Color::BLACK, vec2(3.0, 4.0), 1.0)),
+        ));
+        assert_eq!(effect.padding, Vec2::splat(6.0));
+        assert_eq!(effect.params, [2.0, 1.0, 3.0, 4.0]);
+        assert_eq!(effect.shadow_color, Color::BLACK.rgba());
+    }
+
+    #[test]
+    fn shadow_offset_undo_transform() {
+        let offset = vec2(3.0, 4.0);
+        let near = |a: Vec2, b: Vec2| (a - b).length() < 0.0001;
+        assert!(near(shadow_offset(&Mat3::IDENTITY, 0.0, offset), offset));
+
+        // scaled texts keep the same offset on screen
+        let scaled = Mat3::from_scale(vec2(2.0, 2.0));
+        assert!(near(shadow_offset(&scaled, 0.0, offset), offset * 0.5));
+
+        // the rotation of the text and the rotation of the glyph are both undone
+        let half_pi = std::f32::consts::FRAC_PI_2;
+        let rotated = Mat3::from_angle(half_pi);
+        assert!(near(shadow_offset(&rotated, 0.0, offset), vec2(4.0, -3.0)));
+        assert!(near(shadow_offset(&rotated, -half_pi, offset), offset));
+
+        // collapsed transformations don't draw an offset
+        let collapsed = Mat3::from_scale(vec2(0.0, 1.0));
+        assert_eq!(shadow_offset(&collapsed, 0.0, offset), Vec2::ZERO);
+    }
+
+    #[test]
+    fn effect_glyph_vertices() {
+        let outline = text_data(Some((Color::RED, 2.0)), None);
+        let effect = TextEffect::new(&outline);
+        let mut vertices = vec![];
+        let fx = GlyphEffect::default();
+        push_effect_glyph(&mut vertices, &glyph(), &fx, &outline, &effect);
+
+        // one quad grown by the outline with the effect params on each vertex
+        assert_eq!(vertices.len(), EFFECT_VERTEX_SIZE * 4);
+        let first = &vertices[..EFFECT_VERTEX_SIZE];
+        assert_eq!(first[..2], [8.0, 18.0]);
+        assert_eq!(first[8..12], [0.1, 0.2, 0.2, 0.4]);
+        assert_eq!(first[12..16], Color::RED.rgba());
+        assert_eq!(first[22..], [2.0, 0.0, 0.0, 0.0]);
+        let last = &vertices[EFFECT_VERTEX_SIZE * 3..];
+        assert_eq!(last[..2], [22.0, 42.0]);
+    }
+}
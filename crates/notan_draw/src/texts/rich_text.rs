@@ -0,0 +1,482 @@
+use super::markup::{parse_markup, MarkupNode, MarkupStyle};
+use super::text::{visible_glyphs, TextGlyph};
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, GlyphEffect, TextInfo};
+use crate::images::DrawImages;
+use crate::transform::DrawTransform;
+use notan_glyph::{HorizontalAlign, Layout, Section, Text, VerticalAlign};
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_graphics::Texture;
+use notan_math::{vec2, Mat3, Vec2};
+use notan_text::{Font, FontStyle};
+
+/// Text using markup tags to set the style of each span, check `parse_markup` for the tags supported
+pub struct RichText<'a> {
+    text: &'a str,
+    matrix: Option<Mat3>,
+    font: &'a Font,
+    bold_font: Option<&'a Font>,
+    italic_font: Option<&'a Font>,
+    bold_italic_font: Option<&'a Font>,
+    fonts: Vec<(&'a str, &'a Font)>,
+    icons: Vec<(&'a str, &'a Texture)>,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+    pos: (f32, f32),
+    size: f32,
+    color: Color,
+    max_width: Option<f32>,
+    h_align: HorizontalAlign,
+    v_align: VerticalAlign,
+    alpha: f32,
+    time: f32,
+    reveal: Option<usize>,
+    glyph_hook: Option<&'a dyn Fn(&mut TextGlyph)>,
+}
+
+impl<'a> RichText<'a> {
+    pub fn new(font: &'a Font, text: &'a str) -> Self {
+        Self {
+            text,
+            matrix: None,
+            font,
+            bold_font: None,
+            italic_font: None,
+            bold_italic_font: None,
+            fonts: vec![],
+            icons: vec![],
+            blend_mode: None,
+            alpha_mode: None,
+            pos: (0.0, 0.0),
+            size: 16.0,
+            color: Color::WHITE,
+            max_width: None,
+            h_align: HorizontalAlign::Left,
+            v_align: VerticalAlign::Top,
+            alpha: 1.0,
+            time: 0.0,
+            reveal: None,
+            glyph_hook: None,
+        }
+    }
+
+    pub fn position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pos = (x, y);
+        self
+    }
+
+    /// Default size, it can be changed with the tag `[size=20]`
+    pub fn size(&mut self, size: f32) -> &mut Self {
+        self.size = size;
+        self
+    }
+
+    /// Default color, it can be changed with the tag `[color=#ff0000]`
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn max_width(&mut self, width: f32) -> &mut Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Font used by the tag `[b]`, by default the bold variant of the font family
+    pub fn bold_font(&mut self, font: &'a Font) -> &mut Self {
+        self.bold_font = Some(font);
+        self
+    }
+
+    /// Font used by the tag `[i]`, by default the italic variant of the font family
+    pub fn italic_font(&mut self, font: &'a Font) -> &mut Self {
+        self.italic_font = Some(font);
+        self
+    }
+
+    /// Font used by the tags `[b]` and `[i]` together, by default the bold italic variant of the font family
+    pub fn bold_italic_font(&mut self, font: &'a Font) -> &mut Self {
+        self.bold_italic_font = Some(font);
+        self
+    }
+
+    /// Font used by the tag `[font=name]`
+    pub fn add_font(&mut self, name: &'a str, font: &'a Font) -> &mut Self {
+        self.fonts.push((name, font));
+        self
+    }
+
+    /// Texture used by the tag `[icon=name]`, it's displayed using the text size as height
+    pub fn add_icon(&mut self, name: &'a str, texture: &'a Texture) -> &mut Self {
+        self.icons.push((name, texture));
+        self
+    }
+
+    /// Time in seconds used to animate the effects
+    pub fn time(&mut self, time: f32) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    /// Display only the first glyphs (whitespaces are not counted), useful for typewriter effects
+    pub fn reveal(&mut self, glyphs: usize) -> &mut Self {
+        self.reveal = Some(glyphs);
+        self
+    }
+
+    /// Hook called for each glyph to set a custom offset or alpha
+    pub fn on_glyph(&mut self, hook: &'a dyn Fn(&mut TextGlyph)) -> &mut Self {
+        self.glyph_hook = Some(hook);
+        self
+    }
+
+    pub fn h_align_left(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Left;
+        self
+    }
+
+    pub fn h_align_center(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Center;
+        self
+    }
+
+    pub fn h_align_right(&mut self) -> &mut Self {
+        self.h_align = HorizontalAlign::Right;
+        self
+    }
+
+    pub fn v_align_top(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Top;
+        self
+    }
+
+    pub fn v_align_middle(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Center;
+        self
+    }
+
+    pub fn v_align_bottom(&mut self) -> &mut Self {
+        self.v_align = VerticalAlign::Bottom;
+        self
+    }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+
+    fn font_for(&self, style: &MarkupStyle) -> Font {
+        let font_style = FontStyle::new(style.bold, style.italic);
+        if let Some(name) = &style.font {
+            match self.fonts.iter().find(|(n, _)| n == name) {
+                Some((_, font)) => return font.style(font_style),
+                None => log::warn!("RichText font '{}' not found", name),
+            }
+        }
+
+        let styled = match (style.bold, style.italic) {
+            (true, true) => self.bold_italic_font,
+            (true, false) => self.bold_font,
+            (false, true) => self.italic_font,
+            (false, false) => None,
+        };
+
+        match styled {
+            Some(font) => *font,
+            None => self.font.style(font_style),
+        }
+    }
+}
+
+struct Span<'a> {
+    text: String,
+    color: Color,
+    size: f32,
+    font: Font,
+    style: &'a MarkupStyle,
+    icon: Option<(&'a Texture, Vec2)>,
+}
+
+impl DrawTransform for RichText<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for RichText<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let nodes = parse_markup(self.text).unwrap_or_else(|e| {
+            log::warn!("RichText markup error: {}", e);
+            vec![MarkupNode::Text {
+                text: self.text.to_string(),
+                style: MarkupStyle::default(),
+            }]
+        });
+
+        let spans = nodes
+            .iter()
+            .filter_map(|node| match node {
+                MarkupNode::Text { text, style } => Some(Span {
+                    text: text.clone(),
+                    color: style.color.unwrap_or(self.color),
+                    size: style.size.unwrap_or(self.size).max(1.0),
+                    font: self.font_for(style),
+                    style,
+                    icon: None,
+                }),
+                MarkupNode::Icon { name, style } => {
+                    let texture = self.icons.iter().find(|(n, _)| n == name);
+                    let Some((_, texture)) = texture else {
+                        log::warn!("RichText icon '{}' not found", name);
+                        return None;
+                    };
+
+                    // the icon is placed over a transparent glyph scaled to the icon's width
+                    let font = self.font_for(style);
+                    let height = style.size.unwrap_or(self.size).max(1.0);
+                    let width = height * texture.width() / texture.height();
+                    let placeholder = Section::default()
+                        .add_text(Text::new("M").with_scale(height).with_font_id(font));
+                    let advance = draw.glyphs_calculator.bounds(&placeholder).width;
+                    let size = if advance > 0.0 {
+                        height * width / advance
+                    } else {
+                        height
+                    };
+
+                    Some(Span {
+                        text: "M".to_string(),
+                        color: Color::TRANSPARENT,
+                        size,
+                        font,
+                        style,
+                        icon: Some((texture, vec2(width, height))),
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let texts = spans
+            .iter()
+            .map(|s| {
+                let color = s.color.with_alpha(s.color.a * self.alpha);
+                Text::new(&s.text)
+                    .with_color(color.rgba())
+                    .with_scale(s.size)
+                    .with_font_id(s.font)
+            })
+            .collect::<Vec<_>>();
+
+        let mut section = Section::default().with_text(texts).with_layout(
+            Layout::default()
+                .h_align(self.h_align)
+                .v_align(self.v_align),
+        );
+
+        section.screen_position = self.pos;
+        if let Some(mw) = self.max_width {
+            section.bounds.0 = mw;
+        }
+
+        let needs_effects = self.reveal.is_some()
+            || self.glyph_hook.is_some()
+            || spans.iter().any(|s| {
+                s.style.wave.is_some() || s.style.shake.is_some() || s.style.effect.is_some()
+            });
+
+        // span index of each glyph, the layout is only needed by the effects
+        let glyphs = needs_effects.then(|| visible_glyphs(draw, &section));
+        let effects = glyphs.as_ref().map(|glyphs| {
+            glyphs
+                .iter()
+                .enumerate()
+                .map(|(i, (si, c))| glyph_effect(&self, &spans[*si], i, *c))
+                .collect::<Vec<_>>()
+        });
+        let count = glyphs.as_ref().map_or_else(
+            || {
+                spans
+                    .iter()
+                    .map(|s| s.text.chars().filter(|c| !c.is_whitespace()).count())
+                    .sum()
+            },
+            Vec::len,
+        );
+
+        draw.add_text(&TextInfo {
+            count,
+            section: &section,
+            transform: self.matrix.as_ref(),
+            font: self.font,
+            blend_mode: self.blend_mode,
+            alpha_mode: self.alpha_mode,
+            flip: (false, false),
+            glyph_effects: effects.as_deref(),
+            outline: None,
+            shadow: None,
+        });
+
+        if spans.iter().all(|s| s.icon.is_none()) {
+            return;
+        }
+
+        let positions = draw.glyphs_calculator.glyphs(&section);
+        spans.iter().enumerate().for_each(|(si, span)| {
+            let Some((texture, size)) = span.icon else {
+                return;
+            };
+
+            let Some(glyph) = positions.iter().find(|g| g.section_index == si) else {
+                return;
+            };
+
+            let GlyphEffect {
+                offset,
+                rotation,
+                alpha,
+                ..
+            } = glyphs
+                .as_ref()
+                .zip(effects.as_ref())
+                .and_then(|(glyphs, fx)| {
+                    let index = glyphs.iter().position(|(i, _)| *i == si)?;
+                    fx.get(index).copied()
+                })
+                .unwrap_or_default();
+
+            // the bottom of the icon is placed a bit below the baseline
+            let pos = glyph.glyph.position;
+            let x = pos.x + offset.x;
+            let y = pos.y - size.y * 0.8 + offset.y;
+
+            let mut img = draw.image(texture);
+            img.position(x, y)
+                .size(size.x, size.y)
+                .alpha(self.alpha * alpha);
+
+            let rotation = (rotation != 0.0).then(|| {
+                let center = vec2(x + size.x * 0.5, y + size.y * 0.5);
+                Mat3::from_translation(center)
+                    * Mat3::from_angle(rotation)
+                    * Mat3::from_translation(-center)
+            });
+
+            let matrix = match (self.matrix, rotation) {
+                (Some(m), Some(r)) => Some(m * r),
+                (m, r) => m.or(r),
+            };
+
+            if let Some(m) = matrix {
+                img.transform(m);
+            }
+
+            if let Some(bm) = self.blend_mode {
+                img.blend_mode(bm);
+            }
+
+            if let Some(am) = self.alpha_mode {
+                img.alpha_mode(am);
+            }
+        });
+    }
+}
+
+fn glyph_effect(text: &RichText, span: &Span, index: usize, character: char) -> GlyphEffect {
+    let mut glyph = TextGlyph {
+        index,
+        character,
+        effect: span.style.effect.as_deref(),
+        offset: Vec2::ZERO,
+        rotation: 0.0,
+        color: span.color,
+        alpha: 1.0,
+    };
+
+    if let Some(amplitude) = span.style.wave {
+        let amplitude = if amplitude > 0.0 {
+            amplitude
+        } else {
+            span.size * 0.15
+        };
+        glyph.offset.y += (text.time * 6.0 + index as f32 * 0.6).sin() * amplitude;
+    }
+
+    if let Some(amplitude) = span.style.shake {
+        let amplitude = if amplitude > 0.0 {
+            amplitude
+        } else {
+            span.size * 0.06
+        };
+
+        // new random offset 20 times per second
+        let frame = (text.time * 20.0) as u32;
+        let seed = (index as u32).wrapping_mul(7919) ^ frame.wrapping_mul(104_729);
+        glyph.offset += vec2(noise(seed), noise(seed.wrapping_add(1))) * amplitude;
+    }
+
+    if let Some(visible) = text.reveal {
+        if index >= visible {
+            glyph.alpha = 0.0;
+        }
+    }
+
+    if let Some(hook) = text.glyph_hook {
+        hook(&mut glyph);
+    }
+
+    glyph.to_effect(span.color, text.alpha)
+}
+
+// pseudo random value between -1.0 and 1.0
+fn noise(seed: u32) -> f32 {
+    let mut x = seed.wrapping_mul(0x9E37_79B9);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 13;
+    (x as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use notan_app::empty::EmptyBackend;
+    use notan_app::{BackendSystem, Graphics};
+    use notan_text::TextExtension;
+
+    #[test]
+    fn styled_fonts() {
+        let backend = EmptyBackend::new().unwrap();
+        let mut gfx = Graphics::new(backend.get_graphics_backend()).unwrap();
+        let mut ext = TextExtension::new(&mut gfx).unwrap();
+        let data = include_bytes!("../../../../examples/assets/Ubuntu-B.ttf");
+        let [regular, bold, italic, bold_italic] = [(); 4].map(|_| ext.create_font(data).unwrap());
+
+        let style = |bold, italic| MarkupStyle {
+            bold,
+            italic,
+            ..Default::default()
+        };
+
+        let mut text = RichText::new(&regular, "");
+        text.bold_font(&bold).italic_font(&italic);
+        assert_eq!(text.font_for(&style(false, false)), regular);
+        assert_eq!(text.font_for(&style(true, false)), bold);
+        assert_eq!(text.font_for(&style(false, true)), italic);
+
+        // without a bold italic font it uses the variant of the family, not the bold one
+        assert_eq!(text.font_for(&style(true, true)), regular);
+        text.bold_italic_font(&bold_italic);
+        assert_eq!(text.font_for(&style(true, true)), bold_italic);
+    }
+}
@@ -1,16 +1,43 @@
 use crate::builder::DrawProcess;
-use crate::draw::{Draw, TextInfo};
+use crate::draw::{Draw, GlyphEffect, TextInfo};
 use crate::transform::DrawTransform;
 use notan_glyph::{HorizontalAlign, Layout, Section, Text, VerticalAlign};
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::BlendMode;
-use notan_math::Mat3;
-use notan_text::Font;
+use notan_math::{vec2, Mat3, Vec2};
+use notan_text::{Font, FontStyle};
+
+/// Values of a glyph passed to the hooks set with `on_glyph`
+#[derive(Debug)]
+pub struct TextGlyph<'a> {
+    /// Index of the glyph without counting whitespaces
+    pub index: usize,
+    pub character: char,
+    /// Name set with the tag `[fx=name]`
+    pub effect: Option<&'a str>,
+    pub offset: Vec2,
+    /// Rotation in radians around the center of the glyph
+    pub rotation: f32,
+    pub color: Color,
+    pub alpha: f32,
+}
+
+impl TextGlyph<'_> {
+    pub(crate) fn to_effect(&self, color: Color, alpha: f32) -> GlyphEffect {
+        GlyphEffect {
+            offset: self.offset,
+            rotation: self.rotation,
+            color: (self.color != color).then(|| self.color.with_alpha(self.color.a * alpha)),
+            alpha: self.alpha,
+        }
+    }
+}
 
 pub struct TextSection<'a> {
     text: &'a str,
     matrix: Option<Mat3>,
     font: &'a Font,
+    font_style: FontStyle,
     blend_mode: Option<BlendMode>,
     alpha_mode: Option<BlendMode>,
     pos: (f32, f32),
@@ -21,6 +48,10 @@ pub struct TextSection<'a> {
     v_align: VerticalAlign,
     alpha: f32,
     flip: (bool, bool),
+    outline: Option<(Color, f32)>,
+    shadow: Option<(Color, Vec2)>,
+    shadow_blur: f32,
+    glyph_hook: Option<&'a dyn Fn(&mut TextGlyph)>,
 }
 
 impl<'a> TextSection<'a> {
@@ -29,6 +60,7 @@ impl<'a> TextSection<'a> {
             text,
             matrix: None,
             font,
+            font_style: FontStyle::Regular,
             blend_mode: None,
             alpha_mode: None,
             pos: (0.0, 0.0),
@@ -42,6 +74,10 @@ impl<'a> TextSection<'a> {
             // so the effect for text is best achieved using scale(1.0, -1.0).
             // I am keeping this in private for now because can be useful to do some effect
             flip: (false, false),
+            outline: None,
+            shadow: None,
+            shadow_blur: 0.0,
+            glyph_hook: None,
         }
     }
 
@@ -61,6 +97,12 @@ impl<'a> TextSection<'a> {
         self
     }
 
+    /// Variant of the font family used to draw the text
+    pub fn font_style(&mut self, style: FontStyle) -> &mut Self {
+        self.font_style = style;
+        self
+    }
+
     pub fn alpha(&mut self, alpha: f32) -> &mut Self {
         self.alpha = alpha;
         self
@@ -71,6 +113,35 @@ impl<'a> TextSection<'a> {
         self
     }
 
+    /// Outline drawn around the glyphs, the width is in pixels
+    /// Each glyph is drawn with its own outline, so glyphs closer than the width
+    /// can have the outline of the next glyph over them
+    /// It's not drawn if the draw uses a custom text pipeline
+    pub fn outline(&mut self, color: Color, width: f32) -> &mut Self {
+        self.outline = Some((color, width));
+        self
+    }
+
+    /// Drop shadow drawn behind each glyph and its outline, the offset is in screen pixels
+    /// and it's not rotated or scaled with the text
+    /// It's not drawn if the draw uses a custom text pipeline
+    pub fn shadow(&mut self, color: Color, offset: (f32, f32)) -> &mut Self {
+        self.shadow = Some((color, vec2(offset.0, offset.1)));
+        self
+    }
+
+    /// Blur radius in pixels of the shadow
+    pub fn shadow_blur(&mut self, blur: f32) -> &mut Self {
+        self.shadow_blur = blur;
+        self
+    }
+
+    /// Hook called for each glyph to set a custom offset, rotation, color or alpha
+    pub fn on_glyph(&mut self, hook: &'a dyn Fn(&mut TextGlyph)) -> &mut Self {
+        self.glyph_hook = Some(hook);
+        self
+    }
+
     pub fn h_align_left(&mut self) -> &mut Self {
         self.h_align = HorizontalAlign::Left;
         self
@@ -124,6 +195,7 @@ impl DrawProcess for TextSection<'_> {
             text,
             matrix,
             font,
+            font_style,
             blend_mode,
             alpha_mode,
             pos,
@@ -134,6 +206,10 @@ impl DrawProcess for TextSection<'_> {
             v_align,
             alpha,
             flip,
+            outline,
+            shadow,
+            shadow_blur,
+            glyph_hook,
         } = self;
 
         #[cfg(debug_assertions)]
@@ -143,11 +219,9 @@ impl DrawProcess for TextSection<'_> {
             }
         }
 
-        let color = color.with_alpha(color.a * alpha);
-        let count = text.chars().filter(|c| !c.is_whitespace()).count();
-
+        let font = font.style(font_style);
         let g_text = Text::new(text)
-            .with_color(color.rgba())
+            .with_color(color.with_alpha(color.a * alpha).rgba())
             .with_scale(size.max(1.0))
             .with_font_id(font);
 
@@ -160,14 +234,59 @@ impl DrawProcess for TextSection<'_> {
             section.bounds.0 = mw;
         }
 
+        // the layout is only needed to know the glyphs modified by the hook or drawn with effects
+        let needs_glyphs = glyph_hook.is_some() || outline.is_some() || shadow.is_some();
+        let glyphs = needs_glyphs.then(|| visible_glyphs(draw, &section));
+        let effects = glyph_hook.zip(glyphs.as_ref()).map(|(hook, glyphs)| {
+            glyphs
+                .iter()
+                .enumerate()
+                .map(|(index, (_, character))| {
+                    let mut glyph = TextGlyph {
+                        index,
+                        character: *character,
+                        effect: None,
+                        offset: Vec2::ZERO,
+                        rotation: 0.0,
+                        color,
+                        alpha: 1.0,
+                    };
+
+                    hook(&mut glyph);
+                    glyph.to_effect(color, alpha)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let count = glyphs.as_ref().map_or_else(
+            || text.chars().filter(|c| !c.is_whitespace()).count(),
+            Vec::len,
+        );
+
         draw.add_text(&TextInfo {
             count,
             section: &section,
             transform: matrix.as_ref(),
-            font,
+            font: &font,
             blend_mode,
             alpha_mode,
             flip,
+            glyph_effects: effects.as_deref(),
+            outline: outline.map(|(c, width)| (c.with_alpha(c.a * alpha), width)),
+            shadow: shadow.map(|(c, offset)| (c.with_alpha(c.a * alpha), offset, shadow_blur)),
         });
     }
 }
+
+/// Section index and character of each glyph drawn, in the same order used by the painter
+pub(crate) fn visible_glyphs(draw: &mut Draw, section: &Section) -> Vec<(usize, char)> {
+    draw.glyphs_calculator
+        .visible_glyphs(section)
+        .iter()
+        .filter_map(|g| {
+            let text = section.text.get(g.section_index)?.text;
+            let c = text.get(g.byte_index..)?.chars().next()?;
+            Some((g.section_index, c))
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+mod decode;
+mod json;
+mod map;
+mod tile_map;
+mod tmx;
+
+use crate::builder::DrawBuilder;
+use crate::draw::Draw;
+pub use map::*;
+use notan_app::assets::AssetLoader;
+use notan_graphics::Texture;
+pub use tile_map::*;
+
+pub trait DrawTileMap {
+    fn tilemap<'a>(
+        &mut self,
+        map: &'a TiledMap,
+        textures: &'a [Texture],
+    ) -> DrawBuilder<'_, TileMap<'a>>;
+}
+
+impl DrawTileMap for Draw {
+    fn tilemap<'a>(
+        &mut self,
+        map: &'a TiledMap,
+        textures: &'a [Texture],
+    ) -> DrawBuilder<'_, TileMap<'a>> {
+        DrawBuilder::new(self, TileMap::new(map, textures))
+    }
+}
+
+/// Loader to parse Tiled's maps using tmx or tmj (json) files
+pub fn create_tiled_map_loader() -> AssetLoader {
+    AssetLoader::new()
+        .use_parser(parse_tiled_map)
+        .extensions(&["tmx", "tmj"])
+}
+
+fn parse_tiled_map(id: &str, data: Vec<u8>) -> Result<TiledMap, String> {
+    let map = TiledMap::from_bytes(&data)?;
+    log::debug!("Asset '{}' parsed as TiledMap", id);
+    Ok(map)
+}
+
+/// Loader to parse Tiled's external tilesets using tsx or tsj (json) files,
+/// set them on the map with [`TiledMap::set_external_tileset`]
+pub fn create_tiled_tileset_loader() -> AssetLoader {
+    AssetLoader::new()
+        .use_parser(parse_tiled_tileset)
+        .extensions(&["tsx", "tsj"])
+}
+
+fn parse_tiled_tileset(id: &str, data: Vec<u8>) -> Result<TiledTileset, String> {
+    let tileset = TiledTileset::from_bytes(&data)?;
+    log::debug!("Asset '{}' parsed as TiledTileset", id);
+    Ok(tileset)
+}
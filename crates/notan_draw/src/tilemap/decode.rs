@@ -0,0 +1,142 @@
+use super::map::{TiledProperty, TiledTile};
+use base64::Engine;
+use std::io::Read;
+
+/// Decode the tiles from a base64 string using the compression given
+pub(super) fn decode_base64_tiles(
+    data: &str,
+    compression: Option<&str>,
+) -> Result<Vec<TiledTile>, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| e.to_string())?;
+
+    let bytes = match compression {
+        None | Some("") => bytes,
+        Some("zlib") => {
+            let mut out = vec![];
+            flate2::read::ZlibDecoder::new(bytes.as_slice())
+                .read_to_end(&mut out)
+                .map_err(|e| e.to_string())?;
+            out
+        }
+        Some("gzip") => {
+            let mut out = vec![];
+            flate2::read::GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut out)
+                .map_err(|e| e.to_string())?;
+            out
+        }
+        Some(c) => return Err(format!("Unsupported tile layer compression '{c}'")),
+    };
+
+    if bytes.len() % 4 != 0 {
+        return Err("Invalid tile layer data length".to_string());
+    }
+
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| TiledTile(u32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+        .collect())
+}
+
+/// Decode the tiles from a comma separated list
+pub(super) fn decode_csv_tiles(data: &str) -> Result<Vec<TiledTile>, String> {
+    data.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<u32>()
+                .map(TiledTile)
+                .map_err(|e| format!("Invalid tile '{s}': {e}"))
+        })
+        .collect()
+}
+
+/// Tiles of an infinite map's layer chunk
+pub(super) struct TileChunk {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub tiles: Vec<TiledTile>,
+}
+
+/// Merge the chunks in one chunk that covers all of them
+pub(super) fn merge_chunks(chunks: &[TileChunk]) -> Result<TileChunk, String> {
+    if chunks.is_empty() {
+        return Ok(TileChunk {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            tiles: vec![],
+        });
+    }
+
+    let (x1, y1, x2, y2) = chunks.iter().fold(
+        (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+        |(x1, y1, x2, y2), c| {
+            (
+                x1.min(c.x),
+                y1.min(c.y),
+                x2.max(c.x + c.width as i32),
+                y2.max(c.y + c.height as i32),
+            )
+        },
+    );
+
+    let width = (x2 - x1) as u32;
+    let height = (y2 - y1) as u32;
+    let mut tiles = vec![TiledTile(0); (width * height) as usize];
+    for chunk in chunks {
+        if chunk.tiles.len() != (chunk.width * chunk.height) as usize {
+            return Err(format!(
+                "Invalid chunk at ({}, {}), it has {} tiles instead of {}",
+                chunk.x,
+                chunk.y,
+                chunk.tiles.len(),
+                chunk.width * chunk.height
+            ));
+        }
+
+        let x = (chunk.x - x1) as u32;
+        let y = (chunk.y - y1) as u32;
+        chunk
+            .tiles
+            .chunks_exact(chunk.width.max(1) as usize)
+            .enumerate()
+            .for_each(|(row, line)| {
+                let start = ((y + row as u32) * width + x) as usize;
+                tiles[start..start + line.len()].copy_from_slice(line);
+            });
+    }
+
+    Ok(TileChunk {
+        x: x1,
+        y: y1,
+        width,
+        height,
+        tiles,
+    })
+}
+
+/// Parse a property value using the type defined on Tiled
+pub(super) fn parse_property(typ: &str, value: &str) -> Result<TiledProperty, String> {
+    let prop = match typ {
+        "" | "string" => TiledProperty::String(value.to_string()),
+        "int" => TiledProperty::Int(value.parse().map_err(|_| invalid(typ, value))?),
+        "float" => TiledProperty::Float(value.parse().map_err(|_| invalid(typ, value))?),
+        "bool" => TiledProperty::Bool(value.parse().map_err(|_| invalid(typ, value))?),
+        "color" => TiledProperty::Color(value.to_string()),
+        "file" => TiledProperty::File(value.to_string()),
+        "object" => TiledProperty::Object(value.parse().map_err(|_| invalid(typ, value))?),
+        _ => TiledProperty::String(value.to_string()),
+    };
+
+    Ok(prop)
+}
+
+fn invalid(typ: &str, value: &str) -> String {
+    format!("Invalid {typ} property value '{value}'")
+}
@@ -0,0 +1,400 @@
+use super::decode::{decode_base64_tiles, merge_chunks, parse_property, TileChunk};
+use super::map::*;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub(super) fn parse(data: &[u8]) -> Result<TiledMap, String> {
+    let root: JsonMap = serde_json::from_slice(data).map_err(|e| e.to_string())?;
+
+    if root.orientation != "orthogonal" {
+        return Err(format!(
+            "Unsupported map orientation '{}'",
+            root.orientation
+        ));
+    }
+
+    let mut layers = vec![];
+    for layer in &root.layers {
+        parse_layer(layer, (0.0, 0.0), 1.0, true, &mut layers)?;
+    }
+
+    Ok(TiledMap {
+        width: root.width,
+        height: root.height,
+        infinite: root.infinite,
+        tile_width: root.tilewidth,
+        tile_height: root.tileheight,
+        layers,
+        tilesets: root
+            .tilesets
+            .into_iter()
+            .map(parse_tileset)
+            .collect::<Result<_, _>>()?,
+        properties: parse_properties(&root.properties)?,
+    })
+}
+
+fn parse_layer(
+    layer: &JsonLayer,
+    offset: (f32, f32),
+    opacity: f32,
+    visible: bool,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), String> {
+    let offset = (offset.0 + layer.offsetx, offset.1 + layer.offsety);
+    let opacity = opacity * layer.opacity;
+    let visible = visible && layer.visible;
+
+    match layer.typ.as_str() {
+        "tilelayer" => {
+            let compression = layer.compression.as_deref();
+            let chunk = match &layer.chunks {
+                // infinite maps store the tiles in chunks that can be anywhere on the map
+                Some(chunks) => {
+                    let chunks = chunks
+                        .iter()
+                        .map(|c| {
+                            Ok(TileChunk {
+                                x: c.x,
+                                y: c.y,
+                                width: c.width,
+                                height: c.height,
+                                tiles: parse_tiles(&c.data, compression)?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    merge_chunks(&chunks)?
+                }
+                None => TileChunk {
+                    x: 0,
+                    y: 0,
+                    width: layer.width,
+                    height: layer.height,
+                    tiles: parse_tiles(&layer.data, compression)?,
+                },
+            };
+
+            layers.push(TiledLayer::Tiles(TiledTileLayer {
+                id: layer.id,
+                name: layer.name.clone(),
+                origin: (chunk.x, chunk.y),
+                width: chunk.width,
+                height: chunk.height,
+                visible,
+                opacity,
+                offset,
+                tiles: chunk.tiles,
+                properties: parse_properties(&layer.properties)?,
+            }));
+        }
+        "objectgroup" => {
+            layers.push(TiledLayer::Objects(TiledObjectLayer {
+                id: layer.id,
+                name: layer.name.clone(),
+                visible,
+                opacity,
+                offset,
+                objects: layer
+                    .objects
+                    .iter()
+                    .map(parse_object)
+                    .collect::<Result<_, _>>()?,
+                properties: parse_properties(&layer.properties)?,
+            }));
+        }
+        "group" => {
+            for child in &layer.layers {
+                parse_layer(child, offset, opacity, visible, layers)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn parse_tiles(data: &Option<Value>, compression: Option<&str>) -> Result<Vec<TiledTile>, String> {
+    match data {
+        Some(Value::Array(data)) => data
+            .iter()
+            .map(|v| {
+                v.as_u64()
+                    .map(|gid| TiledTile(gid as _))
+                    .ok_or_else(|| format!("Invalid tile '{v}'"))
+            })
+            .collect(),
+        Some(Value::String(data)) => decode_base64_tiles(data, compression),
+        _ => Ok(vec![]),
+    }
+}
+
+pub(super) fn parse_tileset_file(data: &[u8]) -> Result<TiledTileset, String> {
+    let ts: JsonTileset = serde_json::from_slice(data).map_err(|e| e.to_string())?;
+    parse_tileset(ts)
+}
+
+fn parse_tileset(ts: JsonTileset) -> Result<TiledTileset, String> {
+    if let Some(source) = ts.source {
+        return Ok(TiledTileset {
+            first_gid: ts.firstgid,
+            source: Some(source),
+            ..Default::default()
+        });
+    }
+
+    let mut tileset = TiledTileset {
+        first_gid: ts.firstgid,
+        name: ts.name,
+        tile_width: ts.tilewidth,
+        tile_height: ts.tileheight,
+        tile_count: ts.tilecount,
+        columns: ts.columns,
+        spacing: ts.spacing,
+        margin: ts.margin,
+        image: ts.image,
+        image_width: ts.imagewidth,
+        image_height: ts.imageheight,
+        offset: ts.tileoffset.map_or((0.0, 0.0), |o| (o.x, o.y)),
+        properties: parse_properties(&ts.properties)?,
+        ..Default::default()
+    };
+
+    for tile in ts.tiles {
+        if !tile.animation.is_empty() {
+            let frames = tile
+                .animation
+                .iter()
+                .map(|f| TiledFrame {
+                    tile_id: f.tileid,
+                    duration: f.duration,
+                })
+                .collect();
+            tileset.animations.insert(tile.id, frames);
+        }
+
+        if !tile.properties.is_empty() {
+            let props = parse_properties(&tile.properties)?;
+            tileset.tile_properties.insert(tile.id, props);
+        }
+    }
+
+    Ok(tileset)
+}
+
+fn parse_object(obj: &JsonObject) -> Result<TiledObject, String> {
+    let shape = if obj.ellipse {
+        TiledObjectShape::Ellipse
+    } else if obj.point {
+        TiledObjectShape::Point
+    } else if let Some(points) = &obj.polygon {
+        TiledObjectShape::Polygon(points.iter().map(|p| (p.x, p.y)).collect())
+    } else if let Some(points) = &obj.polyline {
+        TiledObjectShape::Polyline(points.iter().map(|p| (p.x, p.y)).collect())
+    } else if let Some(text) = &obj.text {
+        TiledObjectShape::Text(text.text.clone())
+    } else {
+        TiledObjectShape::Rect
+    };
+
+    Ok(TiledObject {
+        id: obj.id,
+        name: obj.name.clone(),
+        class: obj
+            .class
+            .clone()
+            .or_else(|| obj.typ.clone())
+            .unwrap_or_default(),
+        x: obj.x,
+        y: obj.y,
+        width: obj.width,
+        height: obj.height,
+        rotation: obj.rotation,
+        visible: obj.visible,
+        tile: obj.gid.map(TiledTile),
+        shape,
+        properties: parse_properties(&obj.properties)?,
+    })
+}
+
+fn parse_properties(props: &[JsonProperty]) -> Result<TiledProperties, String> {
+    props
+        .iter()
+        .map(|p| {
+            let value = match &p.value {
+                Value::String(s) => s.clone(),
+                v => v.to_string(),
+            };
+            Ok((p.name.clone(), parse_property(&p.typ, &value)?))
+        })
+        .collect()
+}
+
+fn default_orientation() -> String {
+    "orthogonal".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default = "default_orientation")]
+    orientation: String,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    tilesets: Vec<JsonTileset>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    typ: String,
+    #[serde(default)]
+    id: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    #[serde(default = "default_true")]
+    visible: bool,
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    #[serde(default)]
+    offsetx: f32,
+    #[serde(default)]
+    offsety: f32,
+    data: Option<Value>,
+    chunks: Option<Vec<JsonChunk>>,
+    compression: Option<String>,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonChunk {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    data: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonTileset {
+    #[serde(default)]
+    firstgid: u32,
+    source: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    tilecount: u32,
+    #[serde(default)]
+    columns: u32,
+    #[serde(default)]
+    spacing: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    imagewidth: u32,
+    #[serde(default)]
+    imageheight: u32,
+    tileoffset: Option<JsonPoint>,
+    #[serde(default)]
+    tiles: Vec<JsonTile>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonTile {
+    id: u32,
+    #[serde(default)]
+    animation: Vec<JsonFrame>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonFrame {
+    tileid: u32,
+    duration: u32,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonObject {
+    #[serde(default)]
+    id: u32,
+    #[serde(default)]
+    name: String,
+    class: Option<String>,
+    #[serde(rename = "type")]
+    typ: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default = "default_true")]
+    visible: bool,
+    gid: Option<u32>,
+    #[serde(default)]
+    ellipse: bool,
+    #[serde(default)]
+    point: bool,
+    polygon: Option<Vec<JsonPoint>>,
+    polyline: Option<Vec<JsonPoint>>,
+    text: Option<JsonText>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonPoint {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonText {
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonProperty {
+    name: String,
+    #[serde(rename = "type", default)]
+    typ: String,
+    value: Value,
+}
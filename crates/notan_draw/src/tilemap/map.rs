@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+
+pub(crate) const FLIPPED_HORIZONTALLY_FLAG: u32 = 0x80000000;
+pub(crate) const FLIPPED_VERTICALLY_FLAG: u32 = 0x40000000;
+pub(crate) const FLIPPED_DIAGONALLY_FLAG: u32 = 0x20000000;
+pub(crate) const ROTATED_HEXAGONAL_120_FLAG: u32 = 0x10000000;
+const FLAGS: u32 = FLIPPED_HORIZONTALLY_FLAG
+    | FLIPPED_VERTICALLY_FLAG
+    | FLIPPED_DIAGONALLY_FLAG
+    | ROTATED_HEXAGONAL_120_FLAG;
+
+/// Custom property defined on Tiled
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledProperty {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Color(String),
+    File(String),
+    Object(u32),
+}
+
+pub type TiledProperties = HashMap<String, TiledProperty>;
+
+/// A tile reference on a layer, including the flip flags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TiledTile(pub u32);
+
+impl TiledTile {
+    /// Global id of the tile without flags, 0 means empty
+    pub fn gid(&self) -> u32 {
+        self.0 & !FLAGS
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gid() == 0
+    }
+
+    pub fn flip_x(&self) -> bool {
+        self.0 & FLIPPED_HORIZONTALLY_FLAG != 0
+    }
+
+    pub fn flip_y(&self) -> bool {
+        self.0 & FLIPPED_VERTICALLY_FLAG != 0
+    }
+
+    pub fn flip_diagonal(&self) -> bool {
+        self.0 & FLIPPED_DIAGONALLY_FLAG != 0
+    }
+}
+
+/// Frame of an animated tile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TiledFrame {
+    /// Local id of the tile inside the tileset
+    pub tile_id: u32,
+    /// Duration in milliseconds
+    pub duration: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TiledTileset {
+    pub first_gid: u32,
+    /// Path of the external tileset file as it is written on the map, the tileset
+    /// is empty until it's set with [`TiledMap::set_external_tileset`]
+    pub source: Option<String>,
+    pub name: String,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub tile_count: u32,
+    pub columns: u32,
+    pub spacing: u32,
+    pub margin: u32,
+    /// Image path as it is written on the map file, or on the tileset file if it's external
+    pub image: String,
+    pub image_width: u32,
+    pub image_height: u32,
+    pub offset: (f32, f32),
+    /// Animations by local tile id
+    pub animations: HashMap<u32, Vec<TiledFrame>>,
+    /// Properties by local tile id
+    pub tile_properties: HashMap<u32, TiledProperties>,
+    pub properties: TiledProperties,
+}
+
+impl TiledTileset {
+    /// Parses an external tileset from the tsx (xml) or tsj (json) format
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let first = data.iter().find(|b| !b.is_ascii_whitespace());
+        match first {
+            Some(b'<') => super::tmx::parse_tileset_file(data),
+            Some(b'{') => super::json::parse_tileset_file(data),
+            _ => Err("Invalid Tiled tileset format".to_string()),
+        }
+    }
+
+    /// Returns true if it's an external tileset that was not set yet
+    pub fn is_missing(&self) -> bool {
+        self.source.is_some() && self.tile_width == 0
+    }
+
+    /// Returns true if the global id belongs to this tileset
+    pub fn contains(&self, gid: u32) -> bool {
+        gid >= self.first_gid && gid < self.first_gid + self.tile_count
+    }
+
+    /// Returns the local tile id to draw at the time given (in seconds)
+    pub fn animated_tile(&self, local_id: u32, time: f32) -> u32 {
+        match self.animations.get(&local_id) {
+            Some(frames) if !frames.is_empty() => {
+                let total: u32 = frames.iter().map(|f| f.duration).sum();
+                if total == 0 {
+                    return frames[0].tile_id;
+                }
+
+                let mut elapsed = ((time.max(0.0) * 1000.0) as u64 % total as u64) as u32;
+                for frame in frames {
+                    if elapsed < frame.duration {
+                        return frame.tile_id;
+                    }
+                    elapsed -= frame.duration;
+                }
+
+                frames[frames.len() - 1].tile_id
+            }
+            _ => local_id,
+        }
+    }
+
+    /// Returns the pixel rect (x, y, width, height) of the local tile id inside the image
+    pub fn tile_rect(&self, local_id: u32) -> (f32, f32, f32, f32) {
+        let columns = self.columns.max(1);
+        let col = local_id % columns;
+        let row = local_id / columns;
+        let x = self.margin + col * (self.tile_width + self.spacing);
+        let y = self.margin + row * (self.tile_height + self.spacing);
+        (x as _, y as _, self.tile_width as _, self.tile_height as _)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TiledTileLayer {
+    pub id: u32,
+    pub name: String,
+    /// Coordinates of the first tile on the map, infinite maps can use negative coordinates
+    pub origin: (i32, i32),
+    pub width: u32,
+    pub height: u32,
+    pub visible: bool,
+    pub opacity: f32,
+    pub offset: (f32, f32),
+    pub tiles: Vec<TiledTile>,
+    pub properties: TiledProperties,
+}
+
+impl TiledTileLayer {
+    /// Returns the tile at the coordinates given, relative to the layer's origin
+    pub fn tile(&self, x: u32, y: u32) -> Option<TiledTile> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.tiles
+            .get((y * self.width + x) as usize)
+            .copied()
+            .filter(|t| !t.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledObjectShape {
+    Rect,
+    Ellipse,
+    Point,
+    Polygon(Vec<(f32, f32)>),
+    Polyline(Vec<(f32, f32)>),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct TiledObject {
+    pub id: u32,
+    pub name: String,
+    /// Class of the object (named 'type' in older Tiled versions)
+    pub class: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Rotation in degrees
+    pub rotation: f32,
+    pub visible: bool,
+    /// Tile objects use a gid
+    pub tile: Option<TiledTile>,
+    pub shape: TiledObjectShape,
+    pub properties: TiledProperties,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TiledObjectLayer {
+    pub id: u32,
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    pub offset: (f32, f32),
+    pub objects: Vec<TiledObject>,
+    pub properties: TiledProperties,
+}
+
+#[derive(Debug, Clone)]
+pub enum TiledLayer {
+    Tiles(TiledTileLayer),
+    Objects(TiledObjectLayer),
+}
+
+impl TiledLayer {
+    pub fn name(&self) -> &str {
+        match self {
+            TiledLayer::Tiles(l) => &l.name,
+            TiledLayer::Objects(l) => &l.name,
+        }
+    }
+}
+
+/// Orthogonal map created with Tiled (https://www.mapeditor.org)
+/// Group layers are flattened, adding its offset and opacity to the children.
+/// The chunks of the infinite maps are merged in one layer that covers all of them.
+/// External tilesets are kept empty with their source until they're set
+/// with [`TiledMap::set_external_tileset`].
+#[derive(Debug, Clone, Default)]
+pub struct TiledMap {
+    /// Size in tiles, on infinite maps it's the size saved by Tiled
+    pub width: u32,
+    pub height: u32,
+    pub infinite: bool,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub layers: Vec<TiledLayer>,
+    pub tilesets: Vec<TiledTileset>,
+    pub properties: TiledProperties,
+}
+
+impl TiledMap {
+    /// Parses a map from the tmx (xml) or json format
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let first = data.iter().find(|b| !b.is_ascii_whitespace());
+        match first {
+            Some(b'<') => Self::from_tmx(data),
+            Some(b'{') => Self::from_json(data),
+            _ => Err("Invalid Tiled map format".to_string()),
+        }
+    }
+
+    /// Parses a map from the tmx (xml) format
+    pub fn from_tmx(data: &[u8]) -> Result<Self, String> {
+        super::tmx::parse(data)
+    }
+
+    /// Parses a map from the json format
+    pub fn from_json(data: &[u8]) -> Result<Self, String> {
+        super::json::parse(data)
+    }
+
+    /// Sources of the external tilesets that are not set yet
+    pub fn missing_tilesets(&self) -> impl Iterator<Item = &str> {
+        self.tilesets
+            .iter()
+            .filter(|ts| ts.is_missing())
+            .filter_map(|ts| ts.source.as_deref())
+    }
+
+    /// Sets the content of the external tilesets using the source given,
+    /// it can be parsed with [`TiledTileset::from_bytes`] or loaded as an asset
+    /// using the loader returned by [`create_tiled_tileset_loader`](crate::create_tiled_tileset_loader)
+    pub fn set_external_tileset(
+        &mut self,
+        source: &str,
+        tileset: &TiledTileset,
+    ) -> Result<(), String> {
+        let mut found = false;
+        self.tilesets
+            .iter_mut()
+            .filter(|ts| ts.source.as_deref() == Some(source))
+            .for_each(|ts| {
+                *ts = TiledTileset {
+                    first_gid: ts.first_gid,
+                    source: ts.source.take(),
+                    ..tileset.clone()
+                };
+                found = true;
+            });
+
+        if !found {
+            return Err(format!("The map doesn't use the tileset '{source}'"));
+        }
+
+        Ok(())
+    }
+
+    /// Size of the map in pixels
+    pub fn size(&self) -> (f32, f32) {
+        (
+            (self.width * self.tile_width) as _,
+            (self.height * self.tile_height) as _,
+        )
+    }
+
+    /// Returns the index of the tileset that contains the global id
+    pub fn tileset_index(&self, gid: u32) -> Option<usize> {
+        self.tilesets.iter().rposition(|ts| ts.contains(gid))
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&TiledLayer> {
+        self.layers.iter().find(|l| l.name() == name)
+    }
+
+    pub fn tile_layer(&self, name: &str) -> Option<&TiledTileLayer> {
+        self.tile_layers().find(|l| l.name == name)
+    }
+
+    pub fn object_layer(&self, name: &str) -> Option<&TiledObjectLayer> {
+        self.object_layers().find(|l| l.name == name)
+    }
+
+    pub fn tile_layers(&self) -> impl Iterator<Item = &TiledTileLayer> {
+        self.layers.iter().filter_map(|l| match l {
+            TiledLayer::Tiles(l) => Some(l),
+            _ => None,
+        })
+    }
+
+    pub fn object_layers(&self) -> impl Iterator<Item = &TiledObjectLayer> {
+        self.layers.iter().filter_map(|l| match l {
+            TiledLayer::Objects(l) => Some(l),
+            _ => None,
+        })
+    }
+
+    /// Returns all the objects from all the object layers
+    pub fn objects(&self) -> impl Iterator<Item = &TiledObject> {
+        self.object_layers().flat_map(|l| l.objects.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="2" height="2" tilewidth="16" tileheight="16" infinite="0">
+ <tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16" tilecount="4" columns="2">
+  <image source="tiles.png" width="32" height="32"/>
+  <tile id="1">
+   <animation>
+    <frame tileid="1" duration="100"/>
+    <frame tileid="2" duration="100"/>
+   </animation>
+  </tile>
+ </tileset>
+ <group name="group" offsetx="5" opacity="0.5">
+  <layer id="1" name="ground" width="2" height="2">
+   <data encoding="csv">1,2,0,2147483651</data>
+  </layer>
+ </group>
+ <layer id="2" name="zlib" width="2" height="2" visible="0">
+  <data encoding="base64" compression="zlib">eJxjZGBgYGKAAGYGhgYAAMQAhw==</data>
+ </layer>
+ <objectgroup id="3" name="spawns">
+  <object id="1" name="player" type="spawn" x="8" y="12">
+   <properties>
+    <property name="lives" type="int" value="3"/>
+   </properties>
+   <point/>
+  </object>
+  <object id="2" x="0" y="0">
+   <polygon points="0,0 10,0 10,5"/>
+  </object>
+ </objectgroup>
+</map>"#;
+
+    const JSON: &str = r#"{
+ "width": 2, "height": 2, "tilewidth": 16, "tileheight": 16,
+ "orientation": "orthogonal", "infinite": false,
+ "layers": [
+  {"type": "tilelayer", "id": 1, "name": "ground", "width": 2, "height": 2,
+   "opacity": 1, "visible": true, "x": 0, "y": 0, "data": [1, 2, 0, 2147483651]},
+  {"type": "objectgroup", "id": 2, "name": "spawns", "opacity": 1, "visible": true,
+   "objects": [{"id": 1, "name": "player", "class": "spawn", "x": 8, "y": 12,
+    "width": 0, "height": 0, "rotation": 0, "visible": true, "point": true,
+    "properties": [{"name": "lives", "type": "int", "value": 3}]}]}
+ ],
+ "tilesets": [{"firstgid": 1, "name": "tiles", "tilewidth": 16, "tileheight": 16,
+  "tilecount": 4, "columns": 2, "image": "tiles.png", "imagewidth": 32, "imageheight": 32,
+  "tiles": [{"id": 1, "animation": [{"tileid": 1, "duration": 100}, {"tileid": 2, "duration": 100}]}]}]
+}"#;
+
+    fn check_common(map: &TiledMap) {
+        assert_eq!(map.size(), (32.0, 32.0));
+
+        let ground = map.tile_layer("ground").unwrap();
+        assert_eq!(ground.tile(0, 0).map(|t| t.gid()), Some(1));
+        assert_eq!(ground.tile(0, 1), None);
+        let flipped = ground.tile(1, 1).unwrap();
+        assert_eq!(flipped.gid(), 3);
+        assert!(flipped.flip_x());
+        assert!(!flipped.flip_y());
+        assert_eq!(map.tileset_index(flipped.gid()), Some(0));
+
+        let ts = &map.tilesets[0];
+        assert_eq!(ts.tile_rect(3), (16.0, 16.0, 16.0, 16.0));
+        assert_eq!(ts.animated_tile(1, 0.05), 1);
+        assert_eq!(ts.animated_tile(1, 0.15), 2);
+        assert_eq!(ts.animated_tile(1, 0.25), 1);
+        assert_eq!(ts.animated_tile(0, 0.15), 0);
+
+        let player = map.objects().find(|o| o.name == "player").unwrap();
+        assert_eq!(player.class, "spawn");
+        assert_eq!(player.shape, TiledObjectShape::Point);
+        assert_eq!(player.properties.get("lives"), Some(&TiledProperty::Int(3)));
+    }
+
+    #[test]
+    fn parse_tmx() {
+        let map = TiledMap::from_bytes(TMX.as_bytes()).unwrap();
+        check_common(&map);
+
+        let ground = map.tile_layer("ground").unwrap();
+        assert_eq!(ground.offset, (5.0, 0.0));
+        assert_eq!(ground.opacity, 0.5);
+
+        let zlib = map.tile_layer("zlib").unwrap();
+        assert!(!zlib.visible);
+        assert_eq!(zlib.tiles, ground.tiles);
+
+        let polygon = map.objects().find(|o| o.id == 2).unwrap();
+        assert_eq!(
+            polygon.shape,
+            TiledObjectShape::Polygon(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0)])
+        );
+    }
+
+    #[test]
+    fn parse_json() {
+        let map = TiledMap::from_bytes(JSON.as_bytes()).unwrap();
+        check_common(&map);
+    }
+
+    const INFINITE_TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="4" height="4" tilewidth="16" tileheight="16" infinite="1">
+ <tileset firstgid="1" source="tiles.tsx"/>
+ <layer id="1" name="ground" width="4" height="4">
+  <data encoding="csv">
+   <chunk x="-2" y="-1" width="2" height="1">1,2</chunk>
+   <chunk x="0" y="0" width="2" height="2">3,0,0,4</chunk>
+  </data>
+ </layer>
+</map>"#;
+
+    const INFINITE_JSON: &str = r#"{
+ "width": 4, "height": 4, "tilewidth": 16, "tileheight": 16, "infinite": true,
+ "layers": [
+  {"type": "tilelayer", "id": 1, "name": "ground", "width": 4, "height": 4,
+   "chunks": [{"x": -2, "y": -1, "width": 2, "height": 1, "data": [1, 2]},
+    {"x": 0, "y": 0, "width": 2, "height": 2, "data": [3, 0, 0, 4]}]}
+ ],
+ "tilesets": [{"firstgid": 1, "source": "tiles.tsx"}]
+}"#;
+
+    const TSX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="tiles" tilewidth="16" tileheight="16" tilecount="4" columns="2">
+ <image source="tiles.png" width="32" height="32"/>
+</tileset>"#;
+
+    fn check_infinite(map: &mut TiledMap) {
+        assert!(map.infinite);
+
+        let ground = map.tile_layer("ground").unwrap();
+        assert_eq!(ground.origin, (-2, -1));
+        assert_eq!((ground.width, ground.height), (4, 3));
+        let gids = ground.tiles.iter().map(|t| t.gid()).collect::<Vec<_>>();
+        assert_eq!(gids, [1, 2, 0, 0, 0, 0, 3, 0, 0, 0, 0, 4]);
+
+        assert_eq!(map.missing_tilesets().collect::<Vec<_>>(), ["tiles.tsx"]);
+        assert_eq!(map.tileset_index(3), None);
+
+        let tileset = TiledTileset::from_bytes(TSX.as_bytes()).unwrap();
+        assert!(map.set_external_tileset("other.tsx", &tileset).is_err());
+        map.set_external_tileset("tiles.tsx", &tileset).unwrap();
+        assert_eq!(map.missing_tilesets().count(), 0);
+
+        let ts = &map.tilesets[0];
+        assert!(!ts.is_missing());
+        assert_eq!(ts.first_gid, 1);
+        assert_eq!(ts.image, "tiles.png");
+        assert_eq!(map.tileset_index(3), Some(0));
+    }
+
+    #[test]
+    fn parse_infinite_tmx() {
+        let mut map = TiledMap::from_bytes(INFINITE_TMX.as_bytes()).unwrap();
+        check_infinite(&mut map);
+    }
+
+    #[test]
+    fn parse_infinite_json() {
+        let mut map = TiledMap::from_bytes(INFINITE_JSON.as_bytes()).unwrap();
+        check_infinite(&mut map);
+    }
+
+    #[test]
+    fn invalid_tileset() {
+        assert!(TiledTileset::from_bytes(TMX.as_bytes()).is_err());
+        assert!(TiledTileset::from_bytes(b"tileset").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use super::map::{TiledMap, TiledTile, TiledTileLayer};
+use crate::builder::DrawProcess;
+use crate::draw::{Draw, ImageInfo};
+use crate::screen_to_local_position;
+use crate::transform::DrawTransform;
+use notan_graphics::color::Color;
+use notan_graphics::pipeline::BlendMode;
+use notan_graphics::Texture;
+use notan_math::{vec2, Mat3, Vec2};
+
+pub struct TileMap<'a> {
+    map: &'a TiledMap,
+    textures: &'a [Texture],
+    layer: Option<&'a str>,
+    matrix: Option<Mat3>,
+    pos: (f32, f32),
+    time: f32,
+    color: Color,
+    alpha: f32,
+    blend_mode: Option<BlendMode>,
+    alpha_mode: Option<BlendMode>,
+}
+
+impl<'a> TileMap<'a> {
+    /// Textures must follow the same order than the map's tilesets
+    pub fn new(map: &'a TiledMap, textures: &'a [Texture]) -> Self {
+        Self {
+            map,
+            textures,
+            layer: None,
+            matrix: None,
+            pos: (0.0, 0.0),
+            time: 0.0,
+            color: Color::WHITE,
+            alpha: 1.0,
+            blend_mode: None,
+            alpha_mode: None,
+        }
+    }
+
+    /// Draw only the tile layer with this name instead of all the visible layers
+    pub fn layer(&mut self, name: &'a str) -> &mut Self {
+        self.layer = Some(name);
+        self
+    }
+
+    pub fn position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.pos = (x, y);
+        self
+    }
+
+    /// Time in seconds used to calculate the frame of the animated tiles
+    pub fn time(&mut self, time: f32) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f32) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    pub fn alpha_mode(&mut self, mode: BlendMode) -> &mut Self {
+        self.alpha_mode = Some(mode);
+        self
+    }
+}
+
+impl DrawTransform for TileMap<'_> {
+    fn matrix(&mut self) -> &mut Option<Mat3> {
+        &mut self.matrix
+    }
+}
+
+impl DrawProcess for TileMap<'_> {
+    fn draw_process(self, draw: &mut Draw) {
+        let visible = visible_area(draw, self.matrix.as_ref());
+
+        let layers = self
+            .map
+            .tile_layers()
+            .filter(|l| match self.layer {
+                Some(name) => l.name == name,
+                None => l.visible,
+            })
+            .collect::<Vec<_>>();
+
+        #[cfg(debug_assertions)]
+        {
+            if let Some(name) = self.layer {
+                if layers.is_empty() {
+                    log::warn!("Tile layer '{}' not found.", name);
+                }
+            }
+        }
+
+        layers
+            .into_iter()
+            .for_each(|layer| draw_layer(draw, &self, layer, visible));
+    }
+}
+
+/// Returns the local area (min, max) visible on the screen
+fn visible_area(draw: &mut Draw, matrix: Option<&Mat3>) -> (Vec2, Vec2) {
+    let inverse = *draw
+        .inverse_projection
+        .get_or_insert(draw.projection().inverse());
+
+    let view = *draw.transform().matrix() * matrix.copied().unwrap_or(Mat3::IDENTITY);
+    let size: Vec2 = draw.size().into();
+
+    [
+        vec2(0.0, 0.0),
+        vec2(size.x, 0.0),
+        vec2(0.0, size.y),
+        vec2(size.x, size.y),
+    ]
+    .into_iter()
+    .map(|p| screen_to_local_position(p, size, inverse, view))
+    .fold(
+        (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+        |(min, max), p| (min.min(p), max.max(p)),
+    )
+}
+
+fn draw_layer(draw: &mut Draw, tm: &TileMap, layer: &TiledTileLayer, visible: (Vec2, Vec2)) {
+    let map = tm.map;
+    if map.tile_width == 0 || map.tile_height == 0 {
+        return;
+    }
+
+    let tw = map.tile_width as f32;
+    let th = map.tile_height as f32;
+    let origin = vec2(
+        tm.pos.0 + layer.offset.0 + layer.origin.0 as f32 * tw,
+        tm.pos.1 + layer.offset.1 + layer.origin.1 as f32 * th,
+    );
+
+    // tiles bigger than the grid can be visible from outside the area
+    let (max_w, max_h) = map.tilesets.iter().fold((0.0f32, 0.0f32), |(w, h), ts| {
+        (w.max(ts.tile_width as f32), h.max(ts.tile_height as f32))
+    });
+    let extra_x = (max_w / tw).ceil().max(1.0);
+    let extra_y = (max_h / th).ceil().max(1.0);
+
+    let (min, max) = visible;
+    let clamp_x = |v: f32| v.clamp(0.0, layer.width as f32) as u32;
+    let clamp_y = |v: f32| v.clamp(0.0, layer.height as f32) as u32;
+    let x1 = clamp_x(((min.x - origin.x) / tw).floor() - extra_x);
+    let x2 = clamp_x(((max.x - origin.x) / tw).ceil() + 1.0);
+    let y1 = clamp_y(((min.y - origin.y) / th).floor() - 1.0);
+    let y2 = clamp_y(((max.y - origin.y) / th).ceil() + extra_y);
+
+    let color = tm.color;
+    let color = color.with_alpha(color.a * tm.alpha * layer.opacity);
+
+    // geometry grouped by tileset
+    let mut geometry: Vec<(Vec<f32>, Vec<u32>)> = vec![(vec![], vec![]); map.tilesets.len()];
+
+    for y in y1..y2 {
+        for x in x1..x2 {
+            let tile = match layer.tile(x, y) {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let index = match map.tileset_index(tile.gid()) {
+                Some(idx) => idx,
+                None => continue,
+            };
+
+            let texture = match tm.textures.get(index) {
+                Some(tex) => tex,
+                None => continue,
+            };
+
+            let ts = &map.tilesets[index];
+            let local_id = ts.animated_tile(tile.gid() - ts.first_gid, tm.time);
+            let x1 = origin.x + x as f32 * tw + ts.offset.0;
+            let y1 = origin.y + (y + 1) as f32 * th - ts.tile_height as f32 + ts.offset.1;
+            let x2 = x1 + ts.tile_width as f32;
+            let y2 = y1 + ts.tile_height as f32;
+
+            let [tl, tr, bl, br] = tile_uvs(texture, ts.tile_rect(local_id), tile);
+
+            let c = color;
+            let (vertices, indices) = &mut geometry[index];
+            let n = (vertices.len() / 8) as u32;
+
+            #[rustfmt::skip]
+            vertices.extend_from_slice(&[
+                x1, y1, tl.x, tl.y, c.r, c.g, c.b, c.a,
+                x2, y1, tr.x, tr.y, c.r, c.g, c.b, c.a,
+                x1, y2, bl.x, bl.y, c.r, c.g, c.b, c.a,
+                x2, y2, br.x, br.y, c.r, c.g, c.b, c.a,
+            ]);
+
+            #[rustfmt::skip]
+            indices.extend_from_slice(&[
+                n    , n + 1, n + 2,
+                n + 2, n + 1, n + 3
+            ]);
+        }
+    }
+
+    geometry
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, indices))| !indices.is_empty())
+        .for_each(|(i, (vertices, indices))| {
+            let texture = &tm.textures[i];
+            let blend_mode = tm.blend_mode.or_else(|| {
+                if texture.is_render_texture() {
+                    Some(BlendMode::OVER)
+                } else {
+                    None
+                }
+            });
+
+            draw.add_image(&ImageInfo {
+                texture,
+                transform: tm.matrix.as_ref(),
+                vertices,
+                indices,
+                blend_mode,
+                alpha_mode: tm.alpha_mode,
+                material: None,
+            });
+        });
+}
+
+/// Returns the uvs for top-left, top-right, bottom-left and bottom-right corners
+fn tile_uvs(texture: &Texture, rect: (f32, f32, f32, f32), tile: TiledTile) -> [Vec2; 4] {
+    let frame = texture.frame();
+    let (x, y, w, h) = rect;
+    let base_width = texture.base_width();
+    let base_height = texture.base_height();
+    let u1 = (frame.x + x) / base_width;
+    let v1 = (frame.y + y) / base_height;
+    let u2 = (frame.x + x + w) / base_width;
+    let v2 = (frame.y + y + h) / base_height;
+
+    let (v1, v2) = if texture.is_render_texture() {
+        (v2, v1)
+    } else {
+        (v1, v2)
+    };
+
+    let mut uvs = [vec2(u1, v1), vec2(u2, v1), vec2(u1, v2), vec2(u2, v2)];
+
+    // tiled applies the diagonal flip first
+    if tile.flip_diagonal() {
+        uvs.swap(1, 2);
+    }
+
+    if tile.flip_x() {
+        uvs.swap(0, 1);
+        uvs.swap(2, 3);
+    }
+
+    if tile.flip_y() {
+        uvs.swap(0, 2);
+        uvs.swap(1, 3);
+    }
+
+    uvs
+}
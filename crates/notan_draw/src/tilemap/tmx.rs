@@ -0,0 +1,350 @@
+use super::decode::{
+    decode_base64_tiles, decode_csv_tiles, merge_chunks, parse_property, TileChunk,
+};
+use super::map::*;
+use roxmltree::{Document, Node};
+use std::str::FromStr;
+
+/// Accumulated values from the parent group layers
+#[derive(Clone, Copy)]
+struct GroupInfo {
+    offset: (f32, f32),
+    opacity: f32,
+    visible: bool,
+}
+
+pub(super) fn parse(data: &[u8]) -> Result<TiledMap, String> {
+    let text = std::str::from_utf8(data).map_err(|e| e.to_string())?;
+    let doc = Document::parse(text).map_err(|e| e.to_string())?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "map" {
+        return Err("Invalid tmx file, missing 'map' element".to_string());
+    }
+
+    let orientation = root.attribute("orientation").unwrap_or("orthogonal");
+    if orientation != "orthogonal" {
+        return Err(format!("Unsupported map orientation '{orientation}'"));
+    }
+
+    let mut map = TiledMap {
+        width: required(&root, "width")?,
+        height: required(&root, "height")?,
+        infinite: attr::<u32>(&root, "infinite")?.unwrap_or(0) != 0,
+        tile_width: required(&root, "tilewidth")?,
+        tile_height: required(&root, "tileheight")?,
+        ..Default::default()
+    };
+
+    let group = GroupInfo {
+        offset: (0.0, 0.0),
+        opacity: 1.0,
+        visible: true,
+    };
+
+    for node in root.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "properties" => map.properties = parse_properties(&node)?,
+            "tileset" => {
+                let first_gid = required(&node, "firstgid")?;
+                let tileset = match node.attribute("source") {
+                    Some(source) => TiledTileset {
+                        first_gid,
+                        source: Some(source.to_string()),
+                        ..Default::default()
+                    },
+                    None => parse_tileset(&node, first_gid)?,
+                };
+                map.tilesets.push(tileset);
+            }
+            _ => parse_layer(&node, group, &mut map.layers)?,
+        }
+    }
+
+    Ok(map)
+}
+
+fn parse_layer(node: &Node, parent: GroupInfo, layers: &mut Vec<TiledLayer>) -> Result<(), String> {
+    let offset = (
+        parent.offset.0 + attr(node, "offsetx")?.unwrap_or(0.0),
+        parent.offset.1 + attr(node, "offsety")?.unwrap_or(0.0),
+    );
+    let opacity = parent.opacity * attr(node, "opacity")?.unwrap_or(1.0);
+    let visible = parent.visible && attr::<u32>(node, "visible")?.unwrap_or(1) != 0;
+
+    match node.tag_name().name() {
+        "layer" => {
+            let mut layer = TiledTileLayer {
+                id: attr(node, "id")?.unwrap_or(0),
+                name: node.attribute("name").unwrap_or("").to_string(),
+                width: required(node, "width")?,
+                height: required(node, "height")?,
+                visible,
+                opacity,
+                offset,
+                ..Default::default()
+            };
+
+            for child in node.children().filter(Node::is_element) {
+                match child.tag_name().name() {
+                    "properties" => layer.properties = parse_properties(&child)?,
+                    "data" => parse_data(&child, &mut layer)?,
+                    _ => {}
+                }
+            }
+
+            layers.push(TiledLayer::Tiles(layer));
+        }
+        "objectgroup" => {
+            let mut layer = TiledObjectLayer {
+                id: attr(node, "id")?.unwrap_or(0),
+                name: node.attribute("name").unwrap_or("").to_string(),
+                visible,
+                opacity,
+                offset,
+                ..Default::default()
+            };
+
+            for child in node.children().filter(Node::is_element) {
+                match child.tag_name().name() {
+                    "properties" => layer.properties = parse_properties(&child)?,
+                    "object" => layer.objects.push(parse_object(&child)?),
+                    _ => {}
+                }
+            }
+
+            layers.push(TiledLayer::Objects(layer));
+        }
+        "group" => {
+            let group = GroupInfo {
+                offset,
+                opacity,
+                visible,
+            };
+
+            for child in node.children().filter(Node::is_element) {
+                parse_layer(&child, group, layers)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn parse_data(node: &Node, layer: &mut TiledTileLayer) -> Result<(), String> {
+    let encoding = node.attribute("encoding");
+    let compression = node.attribute("compression");
+    if !node.children().any(|n| n.has_tag_name("chunk")) {
+        layer.tiles = parse_tiles(node, encoding, compression)?;
+        return Ok(());
+    }
+
+    // infinite maps store the tiles in chunks that can be anywhere on the map
+    let chunks = node
+        .children()
+        .filter(|n| n.has_tag_name("chunk"))
+        .map(|n| {
+            Ok(TileChunk {
+                x: required(&n, "x")?,
+                y: required(&n, "y")?,
+                width: required(&n, "width")?,
+                height: required(&n, "height")?,
+                tiles: parse_tiles(&n, encoding, compression)?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let chunk = merge_chunks(&chunks)?;
+    layer.origin = (chunk.x, chunk.y);
+    layer.width = chunk.width;
+    layer.height = chunk.height;
+    layer.tiles = chunk.tiles;
+    Ok(())
+}
+
+fn parse_tiles(
+    node: &Node,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> Result<Vec<TiledTile>, String> {
+    let text = node.text().unwrap_or("");
+    match encoding {
+        Some("csv") => decode_csv_tiles(text),
+        Some("base64") => decode_base64_tiles(text, compression),
+        Some(enc) => Err(format!("Unsupported tile layer encoding '{enc}'")),
+        None => node
+            .children()
+            .filter(|n| n.has_tag_name("tile"))
+            .map(|n| Ok(TiledTile(attr(&n, "gid")?.unwrap_or(0))))
+            .collect(),
+    }
+}
+
+pub(super) fn parse_tileset_file(data: &[u8]) -> Result<TiledTileset, String> {
+    let text = std::str::from_utf8(data).map_err(|e| e.to_string())?;
+    let doc = Document::parse(text).map_err(|e| e.to_string())?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "tileset" {
+        return Err("Invalid tsx file, missing 'tileset' element".to_string());
+    }
+
+    parse_tileset(&root, 0)
+}
+
+fn parse_tileset(node: &Node, first_gid: u32) -> Result<TiledTileset, String> {
+    let mut tileset = TiledTileset {
+        first_gid,
+        name: node.attribute("name").unwrap_or("").to_string(),
+        tile_width: required(node, "tilewidth")?,
+        tile_height: required(node, "tileheight")?,
+        tile_count: attr(node, "tilecount")?.unwrap_or(0),
+        columns: attr(node, "columns")?.unwrap_or(0),
+        spacing: attr(node, "spacing")?.unwrap_or(0),
+        margin: attr(node, "margin")?.unwrap_or(0),
+        ..Default::default()
+    };
+
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "properties" => tileset.properties = parse_properties(&child)?,
+            "tileoffset" => {
+                tileset.offset = (
+                    attr(&child, "x")?.unwrap_or(0.0),
+                    attr(&child, "y")?.unwrap_or(0.0),
+                )
+            }
+            "image" => {
+                tileset.image = child.attribute("source").unwrap_or("").to_string();
+                tileset.image_width = attr(&child, "width")?.unwrap_or(0);
+                tileset.image_height = attr(&child, "height")?.unwrap_or(0);
+            }
+            "tile" => {
+                let id: u32 = required(&child, "id")?;
+                for tile_child in child.children().filter(Node::is_element) {
+                    match tile_child.tag_name().name() {
+                        "properties" => {
+                            let props = parse_properties(&tile_child)?;
+                            tileset.tile_properties.insert(id, props);
+                        }
+                        "animation" => {
+                            let frames = tile_child
+                                .children()
+                                .filter(|n| n.has_tag_name("frame"))
+                                .map(|n| {
+                                    Ok(TiledFrame {
+                                        tile_id: required(&n, "tileid")?,
+                                        duration: required(&n, "duration")?,
+                                    })
+                                })
+                                .collect::<Result<Vec<_>, String>>()?;
+                            tileset.animations.insert(id, frames);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // old versions of tiled doesn't include the columns and the tile count
+    if tileset.columns == 0 && tileset.tile_width > 0 {
+        let w = tileset.image_width.saturating_sub(tileset.margin * 2) + tileset.spacing;
+        tileset.columns = w / (tileset.tile_width + tileset.spacing);
+    }
+
+    if tileset.tile_count == 0 && tileset.tile_height > 0 {
+        let h = tileset.image_height.saturating_sub(tileset.margin * 2) + tileset.spacing;
+        tileset.tile_count = tileset.columns * (h / (tileset.tile_height + tileset.spacing));
+    }
+
+    Ok(tileset)
+}
+
+fn parse_object(node: &Node) -> Result<TiledObject, String> {
+    let mut obj = TiledObject {
+        id: attr(node, "id")?.unwrap_or(0),
+        name: node.attribute("name").unwrap_or("").to_string(),
+        class: node
+            .attribute("class")
+            .or_else(|| node.attribute("type"))
+            .unwrap_or("")
+            .to_string(),
+        x: attr(node, "x")?.unwrap_or(0.0),
+        y: attr(node, "y")?.unwrap_or(0.0),
+        width: attr(node, "width")?.unwrap_or(0.0),
+        height: attr(node, "height")?.unwrap_or(0.0),
+        rotation: attr(node, "rotation")?.unwrap_or(0.0),
+        visible: attr::<u32>(node, "visible")?.unwrap_or(1) != 0,
+        tile: attr(node, "gid")?.map(TiledTile),
+        shape: TiledObjectShape::Rect,
+        properties: Default::default(),
+    };
+
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "properties" => obj.properties = parse_properties(&child)?,
+            "ellipse" => obj.shape = TiledObjectShape::Ellipse,
+            "point" => obj.shape = TiledObjectShape::Point,
+            "polygon" => obj.shape = TiledObjectShape::Polygon(parse_points(&child)?),
+            "polyline" => obj.shape = TiledObjectShape::Polyline(parse_points(&child)?),
+            "text" => obj.shape = TiledObjectShape::Text(child.text().unwrap_or("").to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(obj)
+}
+
+fn parse_points(node: &Node) -> Result<Vec<(f32, f32)>, String> {
+    node.attribute("points")
+        .unwrap_or("")
+        .split_whitespace()
+        .map(|p| {
+            let (x, y) = p
+                .split_once(',')
+                .ok_or_else(|| format!("Invalid point '{p}'"))?;
+            let x = x.parse().map_err(|_| format!("Invalid point '{p}'"))?;
+            let y = y.parse().map_err(|_| format!("Invalid point '{p}'"))?;
+            Ok((x, y))
+        })
+        .collect()
+}
+
+fn parse_properties(node: &Node) -> Result<TiledProperties, String> {
+    node.children()
+        .filter(|n| n.has_tag_name("property"))
+        .map(|n| {
+            let name = n.attribute("name").unwrap_or("").to_string();
+            let typ = n.attribute("type").unwrap_or("string");
+            // multiline strings are stored as text
+            let value = n.attribute("value").or_else(|| n.text()).unwrap_or("");
+            Ok((name, parse_property(typ, value)?))
+        })
+        .collect()
+}
+
+fn attr<T: FromStr>(node: &Node, name: &str) -> Result<Option<T>, String> {
+    node.attribute(name)
+        .map(|v| {
+            v.parse::<T>().map_err(|_| {
+                format!(
+                    "Invalid attribute '{}' on '{}': '{}'",
+                    name,
+                    node.tag_name().name(),
+                    v
+                )
+            })
+        })
+        .transpose()
+}
+
+fn required<T: FromStr>(node: &Node, name: &str) -> Result<T, String> {
+    attr(node, name)?.ok_or_else(|| {
+        format!(
+            "Missing attribute '{}' on '{}'",
+            name,
+            node.tag_name().name()
+        )
+    })
+}
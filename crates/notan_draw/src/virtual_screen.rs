@@ -0,0 +1,216 @@
+use crate::camera::ScaleMode;
+use crate::draw::Draw;
+use crate::images::DrawImages;
+use crate::transform::DrawTransform;
+use notan_app::assets::Assets;
+use notan_app::graphics::*;
+use notan_app::{App, AppFlow, Event, Plugin};
+use notan_math::{vec2, Rect, Vec2};
+
+/// Plugin to render the app using a fixed resolution that is scaled to fit the window
+/// The content is rendered to an offscreen target that is presented at the end of the frame,
+/// and the positions of `app.mouse` and `app.touch` are mapped to the virtual resolution.
+/// The events received on the event callback keep the screen coordinates, use `screen_to_virtual` with them.
+pub struct VirtualScreen {
+    width: u32,
+    height: u32,
+    mode: ScaleMode,
+    filter: TextureFilter,
+    bars_color: Color,
+    auto_present: bool,
+    screen_size: Vec2,
+    texture: Option<RenderTexture>,
+}
+
+impl VirtualScreen {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            mode: ScaleMode::Fit,
+            filter: TextureFilter::Linear,
+            bars_color: Color::BLACK,
+            auto_present: true,
+            screen_size: vec2(width as _, height as _),
+            texture: None,
+        }
+    }
+
+    /// Set the scale mode, `ScaleMode::Integer` is recommended for pixel art
+    pub fn with_scale_mode(mut self, mode: ScaleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the filter used to scale the content, `TextureFilter::Nearest` is recommended for pixel art
+    pub fn with_filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Color used for the bars outside the viewport
+    pub fn with_bars_color(mut self, color: Color) -> Self {
+        self.bars_color = color;
+        self
+    }
+
+    /// Present the content to the screen at the end of each frame (enabled by default)
+    /// Disable it to call `present` manually and draw something over it
+    pub fn with_auto_present(mut self, enabled: bool) -> Self {
+        self.auto_present = enabled;
+        self
+    }
+
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+        self.mode = mode;
+    }
+
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.mode
+    }
+
+    /// Virtual resolution
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Offscreen target where the content is rendered
+    pub fn texture(&self) -> &RenderTexture {
+        self.texture
+            .as_ref()
+            .expect("VirtualScreen must be added as a plugin before use it")
+    }
+
+    /// Returns a new Draw using the virtual resolution
+    pub fn create_draw(&self) -> Draw {
+        Draw::new(self.width, self.height)
+    }
+
+    /// Render to the offscreen target
+    pub fn render<G: GfxRenderer>(&self, gfx: &mut Graphics, renderer: &G) {
+        gfx.render_to(self.texture(), renderer);
+    }
+
+    /// Area of the screen (in pixels) where the content is displayed
+    pub fn viewport(&self) -> Rect {
+        let size = vec2(self.width as _, self.height as _);
+        self.mode.viewport(size, self.screen_size)
+    }
+
+    /// Converts a screen position to the virtual resolution
+    pub fn screen_to_virtual(&self, x: f32, y: f32) -> Vec2 {
+        let vp = self.viewport();
+        if vp.width <= 0.0 || vp.height <= 0.0 {
+            return vec2(x, y);
+        }
+
+        vec2(
+            (x - vp.x) * self.width as f32 / vp.width,
+            (y - vp.y) * self.height as f32 / vp.height,
+        )
+    }
+
+    /// Converts a position on the virtual resolution to the screen
+    pub fn virtual_to_screen(&self, x: f32, y: f32) -> Vec2 {
+        let vp = self.viewport();
+        vec2(
+            vp.x + x * vp.width / self.width as f32,
+            vp.y + y * vp.height / self.height as f32,
+        )
+    }
+
+    /// Draw the content on the screen
+    pub fn present(&mut self, gfx: &mut Graphics) {
+        let (width, height) = gfx.size();
+        self.screen_size = vec2(width as _, height as _);
+
+        let texture = match &self.texture {
+            Some(texture) => texture,
+            None => return,
+        };
+
+        let vp = self.viewport();
+        let mut draw = Draw::new(width as _, height as _);
+        draw.clear(self.bars_color);
+        draw.image(texture)
+            .size(vp.width, vp.height)
+            .translate(vp.x, vp.y);
+        gfx.render(&draw);
+    }
+}
+
+impl Plugin for VirtualScreen {
+    fn init(
+        &mut self,
+        app: &mut App,
+        _assets: &mut Assets,
+        gfx: &mut Graphics,
+    ) -> Result<AppFlow, String> {
+        let (width, height) = app.window().size();
+        self.screen_size = vec2(width as _, height as _);
+
+        let texture = gfx
+            .create_render_texture(self.width, self.height)
+            .with_filter(self.filter, self.filter)
+            .build()?;
+
+        self.texture = Some(texture);
+        Ok(AppFlow::Next)
+    }
+
+    fn event(
+        &mut self,
+        app: &mut App,
+        _assets: &mut Assets,
+        event: &Event,
+    ) -> Result<AppFlow, String> {
+        match event {
+            Event::WindowResize { width, height } => {
+                self.screen_size = vec2(*width as _, *height as _);
+            }
+            Event::MouseMove { x, y }
+            | Event::MouseDown { x, y, .. }
+            | Event::MouseUp { x, y, .. } => {
+                let pos = self.screen_to_virtual(*x as _, *y as _);
+                app.mouse.x = pos.x;
+                app.mouse.y = pos.y;
+            }
+            Event::TouchStart { id, x, y } | Event::TouchMove { id, x, y } => {
+                let pos = self.screen_to_virtual(*x, *y);
+                app.touch.set_platform_position(*id, pos.x, pos.y);
+            }
+            _ => {}
+        }
+
+        Ok(AppFlow::Next)
+    }
+
+    fn post_frame(
+        &mut self,
+        _app: &mut App,
+        _assets: &mut Assets,
+        gfx: &mut Graphics,
+    ) -> Result<AppFlow, String> {
+        if self.auto_present {
+            self.present(gfx);
+        }
+
+        Ok(AppFlow::Next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn screen_virtual_conversion() {
+        let mut vs = VirtualScreen::new(320, 180).with_scale_mode(ScaleMode::Integer);
+        vs.screen_size = vec2(1000.0, 800.0);
+
+        // scale x3 centered on the screen
+        assert_eq!(vs.screen_to_virtual(20.0, 130.0), vec2(0.0, 0.0));
+        assert_eq!(vs.screen_to_virtual(500.0, 400.0), vec2(160.0, 90.0));
+        assert_eq!(vs.virtual_to_screen(320.0, 180.0), vec2(980.0, 670.0));
+    }
+}
@@ -46,6 +46,7 @@ pub struct GlowBackend {
     stats: GpuStats,
     current_uniforms: Vec<UniformLocation>,
     target_render_texture: Option<u64>,
+    target_height: u32,
     render_texture_mipmaps: bool,
 }
 
@@ -122,6 +123,7 @@ impl GlowBackend {
             stats,
             current_uniforms: vec![],
             target_render_texture: None,
+            target_height: 0,
             render_texture_mipmaps: false,
         })
     }
@@ -162,6 +164,7 @@ impl GlowBackend {
             }
         };
 
+        self.target_height = height;
         self.viewport(0.0, 0.0, width as _, height as _, dpi);
 
         self.clear(color, depth, stencil);
@@ -185,7 +188,12 @@ impl GlowBackend {
 
     #[inline]
     fn scissors(&mut self, x: f32, y: f32, width: f32, height: f32, dpi: f32) {
-        let canvas_height = ((self.size.1 - (height + y) as u32) as f32 * dpi) as _;
+        // y is flipped using the height of the target, render textures don't use the dpi
+        let (target_height, dpi) = match self.target_render_texture {
+            Some(_) => (self.target_height as f32, 1.0),
+            None => (self.size.1 as f32, dpi),
+        };
+        let canvas_height = ((target_height - (height + y)) * dpi) as _;
         let x = x * dpi;
         let width = width * dpi;
         let height = height * dpi;
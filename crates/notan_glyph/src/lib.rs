@@ -2,6 +2,8 @@ mod builder;
 mod cache;
 mod instance;
 mod pipeline;
+#[cfg(feature = "shaping")]
+mod shaping;
 
 use cache::Cache;
 pub use instance::GlyphInstance;
@@ -14,8 +16,10 @@ pub use glyph_brush::{
     Section, SectionGeometry, SectionGlyph, SectionGlyphIter, SectionText, Text, VerticalAlign,
 };
 pub use pipeline::{DefaultGlyphPipeline, GlyphPipeline};
+#[cfg(feature = "shaping")]
+pub use shaping::{ShapedLayout, ShapingFaces};
 
-use ab_glyph::{Font, FontArc, Rect};
+use ab_glyph::{Font, FontArc, GlyphId, Rect};
 
 use core::hash::BuildHasher;
 use std::borrow::Cow;
@@ -33,6 +37,7 @@ use notan_math::Mat4;
 pub struct GlyphBrush<F = FontArc, H = DefaultSectionHasher> {
     cache: Cache,
     glyph_brush: glyph_brush::GlyphBrush<GlyphInstance, Extra, F, H>,
+    faces: ShapingFaces,
 }
 
 impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
@@ -40,12 +45,22 @@ impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
     /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued). Can be
     /// called multiple times to queue multiple sections for drawing.
     ///
+    /// With the feature `shaping` the section is laid out using [`ShapedLayout`].
+    ///
     /// Benefits from caching, see [caching behaviour](#caching-behaviour).
     #[inline]
     pub fn queue<'a, S>(&mut self, section: S)
     where
         S: Into<Cow<'a, Section<'a>>>,
     {
+        #[cfg(feature = "shaping")]
+        {
+            let section = section.into();
+            let layout = ShapedLayout::new(section.layout, &self.faces);
+            self.glyph_brush.queue_custom_layout(section, &layout)
+        }
+
+        #[cfg(not(feature = "shaping"))]
         self.glyph_brush.queue(section)
     }
 
@@ -105,6 +120,14 @@ impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
     where
         S: Into<Cow<'a, Section<'a>>>,
     {
+        #[cfg(feature = "shaping")]
+        {
+            let section = section.into();
+            let layout = ShapedLayout::new(section.layout, &self.faces);
+            self.glyph_brush.keep_cached_custom_layout(section, &layout)
+        }
+
+        #[cfg(not(feature = "shaping"))]
         self.glyph_brush.keep_cached(section)
     }
 
@@ -123,6 +146,12 @@ impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
         self.glyph_brush.add_font(font)
     }
 
+    /// Sets the faces used to shape the texts with the feature `shaping`,
+    /// they must be parsed from the same fonts added to the brush and in the same order.
+    pub fn set_shaping_faces(&mut self, faces: ShapingFaces) {
+        self.faces = faces;
+    }
+
     /// Returns the texture used to cache the glyphs
     #[inline]
     pub fn texture(&self) -> &Texture {
@@ -281,7 +310,11 @@ impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
         let (cache_width, cache_height) = glyph_brush.texture_dimensions();
         let cache = Cache::new(gfx, cache_width as _, cache_height as _).unwrap();
 
-        GlyphBrush { cache, glyph_brush }
+        GlyphBrush {
+            cache,
+            glyph_brush,
+            faces: ShapingFaces::default(),
+        }
     }
 }
 
@@ -320,6 +353,90 @@ impl<F: Font, H: BuildHasher> GlyphCruncher<F> for GlyphBrush<F, H> {
     }
 }
 
+/// Maps the glyphs of the fallback fonts used by a font to the glyph ids of the font,
+/// the text drawn with each fallback font is shaped using its own face
+pub trait FallbackGlyphs: Send + Sync {
+    /// Id in the font of the glyph `id` of the fallback font `index`
+    fn glyph_id(&self, index: usize, id: GlyphId) -> GlyphId;
+
+    /// Scale applied to the metrics of the fallback font `index` to match the font
+    fn scale(&self, index: usize) -> f32;
+}
+
+/// Faces of the fonts parsed once to shape the texts, it's empty without the feature `shaping`
+#[cfg(not(feature = "shaping"))]
+#[derive(Clone, Debug, Default)]
+pub struct ShapingFaces {}
+
+#[cfg(not(feature = "shaping"))]
+impl ShapingFaces {
+    /// Parses the faces of the fonts given
+    pub fn from_fonts(_fonts: &[FontArc]) -> Self {
+        Self {}
+    }
+
+    /// Parses the face of the font, it uses the next font id
+    pub fn push(&mut self, _font: &FontArc) {}
+
+    /// Parses the face `index` of a font collection, it uses the next font id
+    pub fn push_indexed(&mut self, _font: &FontArc, _index: u32) {}
+
+    /// Parses the face of a font and the faces of its fallback fonts, it uses the next font id
+    pub fn push_with_fallbacks(
+        &mut self,
+        _font: &FontArc,
+        _index: u32,
+        _fallbacks: &[(FontArc, u32)],
+        _glyphs: std::sync::Arc<dyn FallbackGlyphs>,
+    ) {
+    }
+
+    /// Number of fonts added
+    pub fn len(&self) -> usize {
+        0
+    }
+
+    /// Returns true if there are no fonts added
+    pub fn is_empty(&self) -> bool {
+        true
+    }
+}
+
+/// Positioned glyphs of the section laid out like [`GlyphBrush::queue`] does,
+/// with the feature `shaping` using [`ShapedLayout`] and the faces given
+pub fn section_glyphs<F: Font, C: GlyphCruncher<F>>(
+    cruncher: &mut C,
+    section: &Section,
+    faces: &ShapingFaces,
+) -> Vec<SectionGlyph> {
+    #[cfg(feature = "shaping")]
+    let glyphs = cruncher.glyphs_custom_layout(section, &ShapedLayout::new(section.layout, faces));
+
+    #[cfg(not(feature = "shaping"))]
+    let glyphs = {
+        let _ = faces;
+        cruncher.glyphs(section)
+    };
+
+    glyphs.cloned().collect()
+}
+
+/// Bounds of the section laid out like [`GlyphBrush::queue`] does
+pub fn section_bounds<F: Font, C: GlyphCruncher<F>>(
+    cruncher: &mut C,
+    section: &Section,
+    faces: &ShapingFaces,
+) -> Option<Rect> {
+    #[cfg(feature = "shaping")]
+    return cruncher.glyph_bounds_custom_layout(section, &ShapedLayout::new(section.layout, faces));
+
+    #[cfg(not(feature = "shaping"))]
+    {
+        let _ = faces;
+        cruncher.glyph_bounds(section)
+    }
+}
+
 impl<F, H> std::fmt::Debug for GlyphBrush<F, H> {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,718 @@
+use crate::ab_glyph::{point, Font, FontArc, GlyphId, Rect, ScaleFont};
+use crate::FallbackGlyphs;
+use glyph_brush::{
+    BuiltInLineBreaker, FontId, GlyphPositioner, HorizontalAlign, Layout, SectionGeometry,
+    SectionGlyph, SectionText, ToSectionText, VerticalAlign,
+};
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_bidi::BidiInfo;
+use unicode_linebreak::{break_property, linebreaks, BreakClass, BreakOpportunity};
+
+self_cell::self_cell!(
+    /// Face used to shape the texts, kept with the font that owns its data
+    struct ShapingFace {
+        owner: FontArc,
+
+        #[covariant]
+        dependent: Face,
+    }
+);
+
+impl ShapingFace {
+    fn parse(font: &FontArc, index: u32) -> Option<Self> {
+        Self::try_new(font.clone(), |font| {
+            Face::from_slice(font.font_data(), index).ok_or(())
+        })
+        .ok()
+    }
+}
+
+// face of a font and the faces of the fallback fonts used for its missing glyphs
+struct FontFaces {
+    face: ShapingFace,
+    fallbacks: Vec<Option<ShapingFace>>,
+    glyphs: Option<Arc<dyn FallbackGlyphs>>,
+}
+
+/// Faces of the fonts parsed once to shape the texts, indexed by the font id.
+/// Cloning it shares the faces already parsed.
+#[derive(Clone, Default)]
+pub struct ShapingFaces {
+    fonts: Vec<Option<Arc<FontFaces>>>,
+}
+
+impl ShapingFaces {
+    /// Parses the faces of the fonts given
+    pub fn from_fonts(fonts: &[FontArc]) -> Self {
+        let mut faces = Self::default();
+        fonts.iter().for_each(|font| faces.push(font));
+        faces
+    }
+
+    /// Parses the face of the font, it uses the next font id
+    pub fn push(&mut self, font: &FontArc) {
+        self.push_indexed(font, 0);
+    }
+
+    /// Parses the face `index` of a font collection, it uses the next font id
+    pub fn push_indexed(&mut self, font: &FontArc, index: u32) {
+        self.push_faces(font, index, &[], None);
+    }
+
+    /// Parses the face of a font and the faces of its fallback fonts, given with the index of
+    /// their face. Each fallback font shapes the text that the previous fonts can't draw,
+    /// and `glyphs` maps its glyphs to the ids of the font. It uses the next font id.
+    pub fn push_with_fallbacks(
+        &mut self,
+        font: &FontArc,
+        index: u32,
+        fallbacks: &[(FontArc, u32)],
+        glyphs: Arc<dyn FallbackGlyphs>,
+    ) {
+        self.push_faces(font, index, fallbacks, Some(glyphs));
+    }
+
+    fn push_faces(
+        &mut self,
+        font: &FontArc,
+        index: u32,
+        fallbacks: &[(FontArc, u32)],
+        glyphs: Option<Arc<dyn FallbackGlyphs>>,
+    ) {
+        let faces = ShapingFace::parse(font, index).map(|face| {
+            let fallbacks = fallbacks
+                .iter()
+                .map(|(font, index)| ShapingFace::parse(font, *index))
+                .collect();
+
+            Arc::new(FontFaces {
+                face,
+                fallbacks,
+                glyphs,
+            })
+        });
+
+        self.fonts.push(faces);
+    }
+
+    /// Number of fonts added
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Returns true if there are no fonts added
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    /// Faces of the font id if they were parsed from the same font data
+    fn get(&self, id: usize, data: &[u8]) -> Option<Faces<'_>> {
+        self.fonts
+            .get(id)
+            .and_then(Option::as_ref)
+            .filter(|font| std::ptr::eq(font.face.borrow_owner().font_data(), data))
+            .map(|font| Faces {
+                faces: std::iter::once(Some(font.face.borrow_dependent()))
+                    .chain(
+                        font.fallbacks
+                            .iter()
+                            .map(|face| face.as_ref().map(|face| face.borrow_dependent())),
+                    )
+                    .collect(),
+                glyphs: font.glyphs.as_deref(),
+            })
+    }
+}
+
+impl std::fmt::Debug for ShapingFaces {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapingFaces")
+            .field("len", &self.fonts.len())
+            .finish()
+    }
+}
+
+/// Layout using rustybuzz to shape the text, it supports ligatures, kerning,
+/// complex scripts, bidirectional text and line breaking using the unicode rules.
+/// The alignment and the wrap mode are taken from the inner layout.
+/// The fonts without a face in [`ShapingFaces`] are parsed on each layout using their first face.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShapedLayout<'a> {
+    layout: Layout<BuiltInLineBreaker>,
+    faces: Option<&'a ShapingFaces>,
+}
+
+impl From<Layout<BuiltInLineBreaker>> for ShapedLayout<'_> {
+    fn from(layout: Layout<BuiltInLineBreaker>) -> Self {
+        Self {
+            layout,
+            faces: None,
+        }
+    }
+}
+
+// the faces belong to the fonts, the same fonts give the same layout
+impl Hash for ShapedLayout<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.layout.hash(state);
+    }
+}
+
+impl PartialEq for ShapedLayout<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.layout == other.layout
+    }
+}
+
+impl<'a> ShapedLayout<'a> {
+    /// Layout using the faces already parsed
+    pub fn new(layout: Layout<BuiltInLineBreaker>, faces: &'a ShapingFaces) -> Self {
+        Self {
+            layout,
+            faces: Some(faces),
+        }
+    }
+
+    fn params(&self) -> (HorizontalAlign, VerticalAlign, bool) {
+        match self.layout {
+            Layout::SingleLine {
+                h_align, v_align, ..
+            } => (h_align, v_align, false),
+            Layout::Wrap {
+                h_align, v_align, ..
+            } => (h_align, v_align, true),
+        }
+    }
+}
+
+struct Span<'a> {
+    range: Range<usize>,
+    section: SectionText<'a>,
+    index: usize,
+}
+
+// faces used to shape a font, the first one is the font's face and the rest its fallbacks
+struct Faces<'a> {
+    faces: Vec<Option<&'a Face<'a>>>,
+    glyphs: Option<&'a dyn FallbackGlyphs>,
+}
+
+impl Faces<'_> {
+    fn covers(&self, index: usize, c: char) -> bool {
+        self.faces[index].is_some_and(|face| face.glyph_index(c).is_some())
+    }
+
+    /// Splits the range by the face that covers each char, the marks and the joined chars
+    /// keep the face of the previous char to be shaped in the same cluster
+    fn runs(&self, text: &str, range: Range<usize>) -> Vec<(usize, Range<usize>)> {
+        if self.faces.len() == 1 {
+            return vec![(0, range)];
+        }
+
+        let mut runs: Vec<(usize, Range<usize>)> = vec![];
+        let mut joined = false;
+        text[range.clone()].char_indices().for_each(|(i, c)| {
+            let start = range.start + i;
+            let end = start + c.len_utf8();
+            let class = break_property(c as u32);
+            let current = runs.last().map(|(index, _)| *index);
+            let index = match current {
+                Some(index)
+                    if matches!(
+                        class,
+                        BreakClass::CombiningMark
+                            | BreakClass::ZeroWidthJoiner
+                            | BreakClass::EmojiModifier
+                    ) || (joined && self.covers(index, c)) =>
+                {
+                    index
+                }
+                _ => (0..self.faces.len())
+                    .find(|index| self.covers(*index, c))
+                    .or(current)
+                    .unwrap_or(0),
+            };
+            joined = class == BreakClass::ZeroWidthJoiner;
+
+            match runs.last_mut() {
+                Some((last, run)) if *last == index => run.end = end,
+                _ => runs.push((index, start..end)),
+            }
+        });
+
+        runs
+    }
+}
+
+struct Shaper<'a, F> {
+    text: String,
+    spans: Vec<Span<'a>>,
+    fonts: &'a [F],
+    faces: Vec<Option<Faces<'a>>>,
+}
+
+struct ShapedGlyph {
+    span: usize,
+    byte_index: usize,
+    id: GlyphId,
+    x: f32,
+    y: f32,
+    advance: f32,
+}
+
+#[derive(Default)]
+struct LineMetrics {
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+}
+
+impl<'a, F: Font> Shaper<'a, F> {
+    /// Shapes the range splitting it by sections, the glyphs are returned in visual order
+    fn shape(&self, range: Range<usize>, rtl: bool) -> Vec<ShapedGlyph> {
+        let mut pieces = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter_map(|(i, span)| {
+                let start = span.range.start.max(range.start);
+                let end = span.range.end.min(range.end);
+                (start < end).then_some((i, start..end))
+            })
+            .collect::<Vec<_>>();
+
+        if rtl {
+            pieces.reverse();
+        }
+
+        let mut x = 0.0;
+        let mut glyphs = vec![];
+        pieces.into_iter().for_each(|(i, piece)| {
+            let span = &self.spans[i];
+            let font_id = span.section.font_id.0;
+            let Some(faces) = self.faces.get(font_id).and_then(Option::as_ref) else {
+                return;
+            };
+
+            let scaled = self.fonts[font_id].as_scaled(span.section.scale);
+            let (sx, sy) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+
+            let mut runs = faces.runs(&self.text, piece);
+            if rtl {
+                runs.reverse();
+            }
+
+            runs.into_iter().for_each(|(index, run)| {
+                let Some(face) = faces.faces[index] else {
+                    return;
+                };
+
+                // the fallback glyphs use the ids and the size given by the font
+                let fallback = faces.glyphs.filter(|_| index > 0);
+                let scale = fallback.map_or(1.0, |glyphs| glyphs.scale(index - 1));
+
+                let mut buffer = UnicodeBuffer::new();
+                buffer.push_str(&self.text[run.clone()]);
+                buffer.set_direction(if rtl {
+                    Direction::RightToLeft
+                } else {
+                    Direction::LeftToRight
+                });
+                buffer.guess_segment_properties();
+
+                let output = rustybuzz::shape(face, &[], buffer);
+                output
+                    .glyph_infos()
+                    .iter()
+                    .zip(output.glyph_positions())
+                    .for_each(|(info, pos)| {
+                        let byte_index = run.start + info.cluster as usize;
+                        let id = GlyphId(info.glyph_id as u16);
+                        let id = match fallback {
+                            Some(glyphs) if id.0 != 0 => glyphs.glyph_id(index - 1, id),
+                            _ => id,
+                        };
+                        let advance = pos.x_advance as f32 * sx * scale;
+
+                        glyphs.push(ShapedGlyph {
+                            span: i,
+                            byte_index: byte_index - span.range.start,
+                            id,
+                            x: x + pos.x_offset as f32 * sx * scale,
+                            y: -pos.y_offset as f32 * sy * scale,
+                            advance,
+                        });
+                        x += advance;
+                    });
+            });
+        });
+
+        glyphs
+    }
+
+    /// Advance of the text before each byte, the paragraphs are shaped once by direction runs
+    fn advances(&self, bidi: &BidiInfo) -> Vec<f32> {
+        let mut widths = vec![0.0; self.text.len() + 1];
+        bidi.paragraphs.iter().for_each(|para| {
+            let mut start = para.range.start;
+            while start < para.range.end {
+                let rtl = bidi.levels[start].is_rtl();
+                let end = (start..para.range.end)
+                    .find(|i| bidi.levels[*i].is_rtl() != rtl)
+                    .unwrap_or(para.range.end);
+
+                self.shape(start..end, rtl).iter().for_each(|g| {
+                    let index = self.spans[g.span].range.start + g.byte_index;
+                    widths[index + 1] += g.advance;
+                });
+                start = end;
+            }
+        });
+
+        let mut total = 0.0;
+        widths.iter_mut().for_each(|w| {
+            total += *w;
+            *w = total;
+        });
+        widths
+    }
+
+    fn metrics(&self, range: &Range<usize>) -> LineMetrics {
+        self.spans
+            .iter()
+            .filter(|span| {
+                span.range.contains(&range.start)
+                    || (span.range.start < range.end && range.start < span.range.end)
+            })
+            .fold(LineMetrics::default(), |m, span| {
+                let scaled = self.fonts[span.section.font_id.0].as_scaled(span.section.scale);
+                LineMetrics {
+                    ascent: m.ascent.max(scaled.ascent()),
+                    descent: m.descent.min(scaled.descent()),
+                    line_gap: m.line_gap.max(scaled.line_gap()),
+                }
+            })
+    }
+
+    /// Splits the text in lines (logical order) using the unicode line breaking rules
+    fn lines(&self, bidi: &BidiInfo, max_width: f32) -> Vec<Range<usize>> {
+        let advances = self.advances(bidi);
+        let width = |range: Range<usize>| advances[range.end] - advances[range.start];
+
+        let mut lines = vec![];
+        let mut line_start = 0;
+        let mut line_width = 0.0;
+        let mut last = 0;
+
+        linebreaks(&self.text).for_each(|(pos, opportunity)| {
+            let content_end = last + self.text[last..pos].trim_end().len();
+            let content = width(last..content_end);
+
+            if line_width + content > max_width && last > line_start {
+                lines.push(line_start..last);
+                line_start = last;
+                line_width = 0.0;
+            }
+
+            line_width += content + width(content_end..pos);
+
+            if opportunity == BreakOpportunity::Mandatory {
+                lines.push(line_start..pos);
+                line_start = pos;
+                line_width = 0.0;
+            }
+
+            last = pos;
+        });
+
+        lines
+    }
+}
+
+impl GlyphPositioner for ShapedLayout<'_> {
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let mut text = String::new();
+        let spans = sections
+            .iter()
+            .enumerate()
+            .map(|(index, s)| {
+                let section = s.to_section_text();
+                let start = text.len();
+                text.push_str(section.text);
+                Span {
+                    range: start..text.len(),
+                    section,
+                    index,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if text.is_empty() {
+            return vec![];
+        }
+
+        // the fonts without parsed faces are parsed only if the text uses them
+        let cached = |i: usize, f: &F| self.faces.and_then(|faces| faces.get(i, f.font_data()));
+        let parsed = fonts
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let used = spans.iter().any(|s| s.section.font_id.0 == i);
+                (used && cached(i, f).is_none())
+                    .then(|| Face::from_slice(f.font_data(), 0))
+                    .flatten()
+            })
+            .collect::<Vec<_>>();
+        let faces = fonts
+            .iter()
+            .zip(&parsed)
+            .enumerate()
+            .map(|(i, (f, face))| {
+                cached(i, f).or_else(|| {
+                    face.as_ref().map(|face| Faces {
+                        faces: vec![Some(face)],
+                        glyphs: None,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // fonts without raw data can't be shaped
+        let can_shape = spans
+            .iter()
+            .all(|s| faces.get(s.section.font_id.0).is_some_and(|f| f.is_some()));
+        if !can_shape {
+            return self.layout.calculate_glyphs(fonts, geometry, sections);
+        }
+
+        let shaper = Shaper {
+            text,
+            spans,
+            fonts,
+            faces,
+        };
+
+        let (h_align, v_align, wrap) = self.params();
+        let max_width = if wrap {
+            geometry.bounds.0
+        } else {
+            f32::INFINITY
+        };
+
+        let bidi = BidiInfo::new(&shaper.text, None);
+        let (screen_x, screen_y) = geometry.screen_position;
+
+        let mut caret_y = 0.0;
+        let mut lines = vec![];
+        shaper.lines(&bidi, max_width).into_iter().for_each(|line| {
+            let metrics = shaper.metrics(&line);
+            let end = line.start + shaper.text[line.clone()].trim_end().len();
+
+            let glyphs = bidi
+                .paragraphs
+                .iter()
+                .find(|p| p.range.contains(&line.start))
+                .filter(|_| end > line.start)
+                .map(|para| {
+                    let (levels, runs) = bidi.visual_runs(para, line.start..end);
+                    let mut x = 0.0;
+                    runs.into_iter()
+                        .flat_map(|run| {
+                            let rtl = levels[run.start].is_rtl();
+                            let mut glyphs = shaper.shape(run, rtl);
+                            glyphs.iter_mut().for_each(|g| g.x += x);
+                            x += glyphs.iter().map(|g| g.advance).sum::<f32>();
+                            glyphs
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            let width = glyphs.iter().map(|g| g.advance).sum::<f32>();
+            let baseline = caret_y + metrics.ascent;
+            caret_y += metrics.ascent - metrics.descent + metrics.line_gap;
+            lines.push((glyphs, width, baseline));
+        });
+
+        let top = match v_align {
+            VerticalAlign::Top => screen_y,
+            VerticalAlign::Center => screen_y - caret_y * 0.5,
+            VerticalAlign::Bottom => screen_y - caret_y,
+        };
+
+        lines
+            .into_iter()
+            .flat_map(|(glyphs, width, baseline)| {
+                let left = match h_align {
+                    HorizontalAlign::Left => screen_x,
+                    HorizontalAlign::Center => screen_x - width * 0.5,
+                    HorizontalAlign::Right => screen_x - width,
+                };
+
+                let spans = &shaper.spans;
+                glyphs.into_iter().map(move |g| {
+                    let span = &spans[g.span];
+                    let position = point(left + g.x, top + baseline + g.y);
+                    SectionGlyph {
+                        section_index: span.index,
+                        byte_index: g.byte_index,
+                        glyph: g.id.with_scale_and_position(span.section.scale, position),
+                        font_id: FontId(span.section.font_id.0),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.layout.bounds_rect(geometry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ab_glyph::{FontRef, PxScale};
+
+    const FONT: &[u8] = include_bytes!("../../../examples/assets/Ubuntu-B.ttf");
+    const PIXEL: &[u8] = include_bytes!("../../../examples/assets/kenney_pixel-webfont.ttf");
+
+    fn layout(text: &str, layout: Layout<BuiltInLineBreaker>) -> Vec<SectionGlyph> {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let section = SectionText {
+            text,
+            scale: PxScale::from(20.0),
+            font_id: FontId(0),
+        };
+
+        ShapedLayout::from(layout).calculate_glyphs(
+            &[font],
+            &SectionGeometry {
+                screen_position: (0.0, 0.0),
+                bounds: (80.0, f32::INFINITY),
+            },
+            &[section],
+        )
+    }
+
+    #[test]
+    fn parsed_faces() {
+        let font = FontArc::try_from_slice(FONT).unwrap();
+        let shape = |layout: &ShapedLayout| {
+            let section = SectionText {
+                text: "office waffle",
+                scale: PxScale::from(20.0),
+                font_id: FontId(0),
+            };
+            layout.calculate_glyphs(
+                std::slice::from_ref(&font),
+                &SectionGeometry::default(),
+                &[section],
+            )
+        };
+
+        // the parsed face lays out the same glyphs than the one parsed on each layout
+        let faces = ShapingFaces::from_fonts(std::slice::from_ref(&font));
+        assert!(faces.get(0, font.font_data()).is_some());
+        let parsed = shape(&ShapedLayout::default());
+        assert_eq!(shape(&ShapedLayout::new(Layout::default(), &faces)), parsed);
+
+        // faces from other fonts are not used
+        let other = FontArc::try_from_vec(FONT.to_vec()).unwrap();
+        assert!(faces.get(0, other.font_data()).is_none());
+    }
+
+    struct TestFallback;
+
+    impl FallbackGlyphs for TestFallback {
+        fn glyph_id(&self, _index: usize, id: GlyphId) -> GlyphId {
+            GlyphId(id.0 + 1000)
+        }
+
+        fn scale(&self, _index: usize) -> f32 {
+            2.0
+        }
+    }
+
+    #[test]
+    fn fallback_runs() {
+        let pixel = FontArc::try_from_slice(PIXEL).unwrap();
+        let ubuntu = FontArc::try_from_slice(FONT).unwrap();
+        let mut faces = ShapingFaces::default();
+        faces.push_with_fallbacks(&pixel, 0, &[(ubuntu.clone(), 0)], Arc::new(TestFallback));
+
+        let section = SectionText {
+            text: "aЖ\u{311}b",
+            scale: PxScale::from(20.0),
+            font_id: FontId(0),
+        };
+        let glyphs = ShapedLayout::new(Layout::default_single_line(), &faces).calculate_glyphs(
+            std::slice::from_ref(&pixel),
+            &SectionGeometry::default(),
+            &[section],
+        );
+
+        // the missing char and its mark are shaped with the fallback face
+        let ids = glyphs.iter().map(|g| g.glyph.id).collect::<Vec<_>>();
+        let fallback = |c: char| GlyphId(ubuntu.glyph_id(c).0 + 1000);
+        assert_eq!(
+            ids,
+            [
+                pixel.glyph_id('a'),
+                fallback('Ж'),
+                fallback('\u{311}'),
+                pixel.glyph_id('b')
+            ]
+        );
+
+        // the fallback advances are scaled to the font
+        let scaled = pixel.as_scaled(20.0);
+        let advance = scaled.h_advance(pixel.glyph_id('a'))
+            + ubuntu.h_advance_unscaled(ubuntu.glyph_id('Ж')) * scaled.h_scale_factor() * 2.0;
+        assert!((glyphs[3].glyph.position.x - advance).abs() < 0.01);
+    }
+
+    #[test]
+    fn face_index() {
+        let font = FontArc::try_from_slice(FONT).unwrap();
+        assert!(ShapingFace::parse(&font, 0).is_some());
+        assert!(ShapingFace::parse(&font, 1).is_none());
+    }
+
+    #[test]
+    fn bidi_reorder_and_wrap() {
+        // the hebrew word is drawn right to left after the latin one
+        let glyphs = layout("abc אבג", Layout::default_single_line());
+        let indices = glyphs.iter().map(|g| g.byte_index).collect::<Vec<_>>();
+        assert_eq!(indices, [0, 1, 2, 3, 8, 6, 4]);
+
+        let xs = glyphs
+            .iter()
+            .map(|g| g.glyph.position.x)
+            .collect::<Vec<_>>();
+        assert!(xs.windows(2).all(|w| w[0] < w[1]));
+
+        // wrapped by words
+        let glyphs = layout("hello world hello", Layout::default_wrap());
+        let lines = glyphs.iter().fold(vec![], |mut ys: Vec<f32>, g| {
+            if !ys.contains(&g.glyph.position.y) {
+                ys.push(g.glyph.position.y);
+            }
+            ys
+        });
+        assert!(lines.len() > 1);
+        assert!(glyphs.iter().all(|g| g.glyph.position.x < 80.0));
+    }
+}
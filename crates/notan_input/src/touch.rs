@@ -62,6 +62,16 @@ impl Touch {
         *self.down.get(&id).unwrap_or(&0.0)
     }
 
+    /// Overrides the position of the touch using the id set by the backend (useful to map coordinates)
+    pub fn set_platform_position(&mut self, platform_id: u64, x: f32, y: f32) {
+        if let Some(&index) = self.pointer_index.get(&platform_id) {
+            if let Some(pointer) = &mut self.pointers[index] {
+                pointer.x = x;
+                pointer.y = y;
+            }
+        }
+    }
+
     #[inline]
     pub(crate) fn clear(&mut self) {
         self.pressed.clear();
@@ -1,39 +1,152 @@
-use crate::decoder::frames_from_bytes;
+use crate::capture::{input_devices, AudioCapture};
+use crate::decoder::{audio_data_from_bytes, frames_from_pcm, AudioData};
+use crate::effect::{Effects, EffectsControl};
+use crate::fade::{Fade, FadeControl, Ramp};
+use crate::generator::GeneratorSignal;
+use crate::offline::{encode_wav, Clock, MixerSignal, OfflineMixer};
+use crate::playback::{FramesPlayback, PlaybackState};
+use crate::spatial::{Mono, Pan, PanControl, SpatialMotion};
+use crate::stream::StreamSignal;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::BufferSize;
 use hashbrown::HashMap;
-use notan_audio::AudioBackend;
-use oddio::{Cycle, Frames, FramesSignal, Gain, GainControl, Handle, Mixer, Stop, StopControl};
+use notan_audio::{
+    AudioBackend, AudioEffect, AudioGeneratorFactory, AudioListener, AudioSourceKind, LoopRegion,
+    SpatialOptions,
+};
+use oddio::{
+    Gain, GainControl, Handle, Mixer, MixerControl, Signal, SpatialBuffered, SpatialScene,
+    SpatialSceneControl, Speed, SpeedControl, Stop, StopControl,
+};
+use std::cell::RefCell;
 use std::sync::Arc;
 
 #[cfg(target_arch = "wasm32")]
 use crate::webaudio::DummyAudioBackend;
 
-type FrameHandle = Handle<Stop<Gain<FramesSignal<[f32; 2]>>>>;
-type CycleHandle = Handle<Stop<Gain<Cycle<[f32; 2]>>>>;
+// sample rate of the backends without audio device
+const NULL_SAMPLE_RATE: u32 = 44100;
+
+// seconds of audio sampled ahead by the spatial sounds
+const SPATIAL_BUFFER_DURATION: f32 = 0.1;
+
+type SoundSignal = Gain<Fade<Effects<Speed<Box<dyn Signal<Frame = [f32; 2]> + Send>>>>>;
+type StereoHandle = Handle<Stop<Pan<SoundSignal>>>;
+type SpatialHandle = Handle<SpatialBuffered<Stop<Mono<SoundSignal>>>>;
+type SceneHandle = Handle<Stop<SpatialScene>>;
+type MixerHandle = Handle<Gain<Effects<Mixer<[f32; 2]>>>>;
+type BusHandle = Handle<Stop<Gain<Effects<Mixer<[f32; 2]>>>>>;
 
 struct AudioInfo {
     handle: AudioHandle,
     volume: f32,
+    pan: f32,
+    effects: Vec<AudioEffect>,
+    spatial: Option<SpatialOptions>,
+    bus: Option<u64>,
+    playback: Arc<PlaybackState>,
+    rate: f32,
+    /// Fade level followed by the backends that don't mix the sounds
+    fade: Ramp,
+}
+
+impl AudioInfo {
+    /// Updates the volume and the position relative to the listener
+    fn update(&mut self, listener: &AudioListener, discontinuity: bool) {
+        match (&mut self.handle, &self.spatial) {
+            (AudioHandle::Spatial(h), Some(options)) => {
+                let motion = SpatialMotion::new(options, listener);
+                h.control::<Gain<_>, _>()
+                    .set_gain(spatial_gain(self.volume, motion.attenuation));
+                h.control::<SpatialBuffered<_>, _>().set_motion(
+                    motion.position.into(),
+                    motion.velocity.into(),
+                    discontinuity || !options.doppler,
+                );
+            }
+            (handle, _) => handle.as_gain().set_gain(volume_as_gain(self.volume)),
+        }
+    }
+}
+
+/// Volume, mute, pause and effects of a bus
+#[derive(Clone, Debug)]
+pub(crate) struct BusState {
+    pub volume: f32,
+    pub muted: bool,
+    pub paused: bool,
+    pub effects: Vec<AudioEffect>,
+}
+
+impl Default for BusState {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            paused: false,
+            effects: vec![],
+        }
+    }
+}
+
+impl BusState {
+    fn gain(&self) -> f32 {
+        let volume = if self.muted { 0.0 } else { self.volume };
+        volume_as_gain(volume)
+    }
+}
+
+struct BusInfo {
+    handle: BusHandle,
+    scene: SceneHandle,
+    state: BusState,
 }
 
 enum AudioHandle {
-    Frame(FrameHandle),
-    Cycle(CycleHandle),
+    Stereo(StereoHandle),
+    Spatial(SpatialHandle),
 }
 
 impl AudioHandle {
     fn as_stop(&mut self) -> StopControl {
         match self {
-            AudioHandle::Frame(h) => h.control::<Stop<_>, _>(),
-            AudioHandle::Cycle(h) => h.control::<Stop<_>, _>(),
+            AudioHandle::Stereo(h) => h.control::<Stop<_>, _>(),
+            AudioHandle::Spatial(h) => h.control::<Stop<_>, _>(),
         }
     }
 
     fn as_gain(&mut self) -> GainControl {
         match self {
-            AudioHandle::Frame(h) => h.control::<Gain<_>, _>(),
-            AudioHandle::Cycle(h) => h.control::<Gain<_>, _>(),
+            AudioHandle::Stereo(h) => h.control::<Gain<_>, _>(),
+            AudioHandle::Spatial(h) => h.control::<Gain<_>, _>(),
+        }
+    }
+
+    fn as_fade(&mut self) -> FadeControl<'_> {
+        match self {
+            AudioHandle::Stereo(h) => h.control::<Fade<_>, _>(),
+            AudioHandle::Spatial(h) => h.control::<Fade<_>, _>(),
+        }
+    }
+
+    fn as_effects(&mut self) -> EffectsControl<'_> {
+        match self {
+            AudioHandle::Stereo(h) => h.control::<Effects<_>, _>(),
+            AudioHandle::Spatial(h) => h.control::<Effects<_>, _>(),
+        }
+    }
+
+    fn as_speed(&mut self) -> SpeedControl<'_> {
+        match self {
+            AudioHandle::Stereo(h) => h.control::<Speed<_>, _>(),
+            AudioHandle::Spatial(h) => h.control::<Speed<_>, _>(),
+        }
+    }
+
+    fn as_pan(&mut self) -> Option<PanControl<'_>> {
+        match self {
+            AudioHandle::Stereo(h) => Some(h.control::<Pan<_>, _>()),
+            AudioHandle::Spatial(_) => None,
         }
     }
 }
@@ -64,13 +177,60 @@ impl OddioBackend {
         })
     }
 
+    /// Creates a backend without audio device, the sounds are not mixed but their positions
+    /// follow the time passed so they pause, stop and finish like on a device
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn null() -> Self {
+        Self {
+            inner: BackendImpl::Oddio(InnerBackend::offline(
+                NULL_SAMPLE_RATE,
+                Some(Clock::system()),
+            )),
+        }
+    }
+
+    /// Creates a backend without audio device that only advances when the mix is rendered
+    pub fn offline(sample_rate: u32) -> Self {
+        Self {
+            inner: BackendImpl::Oddio(InnerBackend::offline(sample_rate.max(1), None)),
+        }
+    }
+
+    /// Renders the next seconds of the mix of an offline backend
+    pub fn render(&mut self, seconds: f32) -> Result<Vec<[f32; 2]>, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.render(seconds),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(_) => Err("Only offline audio backends can render.".to_string()),
+        }
+    }
+
+    /// Renders the next seconds of the mix of an offline backend as a 16 bits WAV file
+    pub fn render_wav(&mut self, seconds: f32) -> Result<Vec<u8>, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => {
+                let frames = inner.render(seconds)?;
+                Ok(encode_wav(&frames, inner.sample_rate))
+            }
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(_) => Err("Only offline audio backends can render.".to_string()),
+        }
+    }
+
     #[cfg(target_arch = "wasm32")]
     pub fn enable(&mut self) -> Result<(), String> {
         let inner = if let BackendImpl::Dummy(dummy) = &mut self.inner {
             let mut inner = InnerBackend::new()?;
             std::mem::swap(&mut inner.sources, &mut dummy.sources);
             inner.source_id_count = dummy.id_count;
+            inner.bus_id_count = dummy.bus_id_count;
+            dummy
+                .buses
+                .iter()
+                .for_each(|(id, state)| inner.add_bus(*id, state.clone()));
             inner.set_global_volume(dummy.volume);
+            inner.set_global_effects(&dummy.effects);
+            inner.set_listener(dummy.listener);
             Some(inner)
         } else {
             None
@@ -104,20 +264,76 @@ impl AudioBackend for OddioBackend {
     }
 
     #[inline]
-    fn create_source(&mut self, bytes: &[u8]) -> Result<u64, String> {
+    fn create_source(&mut self, bytes: &[u8], kind: AudioSourceKind) -> Result<u64, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.create_source(bytes, kind),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.create_source(bytes, kind),
+        }
+    }
+
+    #[inline]
+    fn create_source_from_pcm(
+        &mut self,
+        samples: &[f32],
+        channels: u16,
+        sample_rate: u32,
+    ) -> Result<u64, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => {
+                inner.create_source_from_pcm(samples, channels, sample_rate)
+            }
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => {
+                inner.create_source_from_pcm(samples, channels, sample_rate)
+            }
+        }
+    }
+
+    #[inline]
+    fn create_source_from_generator(
+        &mut self,
+        factory: AudioGeneratorFactory,
+    ) -> Result<u64, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.create_source_from_generator(factory),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.create_source_from_generator(factory),
+        }
+    }
+
+    #[inline]
+    fn play_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+    ) -> Result<u64, String> {
         match &mut self.inner {
-            BackendImpl::Oddio(inner) => inner.create_source(bytes),
+            BackendImpl::Oddio(inner) => inner.play_sound(source, volume, repeat, bus),
             #[cfg(target_arch = "wasm32")]
-            BackendImpl::Dummy(inner) => inner.create_source(bytes),
+            BackendImpl::Dummy(inner) => inner.play_sound(source, volume, repeat, bus),
         }
     }
 
     #[inline]
-    fn play_sound(&mut self, source: u64, volume: f32, repeat: bool) -> Result<u64, String> {
+    fn play_spatial_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+        options: SpatialOptions,
+    ) -> Result<u64, String> {
         match &mut self.inner {
-            BackendImpl::Oddio(inner) => inner.play_sound(source, volume, repeat),
+            BackendImpl::Oddio(inner) => {
+                inner.play_spatial_sound(source, volume, repeat, bus, options)
+            }
             #[cfg(target_arch = "wasm32")]
-            BackendImpl::Dummy(inner) => inner.play_sound(source, volume, repeat),
+            BackendImpl::Dummy(inner) => {
+                inner.play_spatial_sound(source, volume, repeat, bus, options)
+            }
         }
     }
 
@@ -185,23 +401,352 @@ impl AudioBackend for OddioBackend {
     }
 
     #[inline]
-    fn clean(&mut self, sources: &[u64], sounds: &[u64]) {
+    fn fade(&mut self, sound: u64, from: Option<f32>, to: f32, duration: f32, stop: bool) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.fade(sound, from, to, duration, stop),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.fade(sound, from, to, duration, stop),
+        }
+    }
+
+    #[inline]
+    fn set_effects(&mut self, sound: u64, effects: &[AudioEffect]) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_effects(sound, effects),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_effects(sound, effects),
+        }
+    }
+
+    #[inline]
+    fn effects(&self, sound: u64) -> Vec<AudioEffect> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.effects(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.effects(sound),
+        }
+    }
+
+    #[inline]
+    fn set_global_effects(&mut self, effects: &[AudioEffect]) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_global_effects(effects),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_global_effects(effects),
+        }
+    }
+
+    #[inline]
+    fn global_effects(&self) -> Vec<AudioEffect> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.global_effects(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.global_effects(),
+        }
+    }
+
+    #[inline]
+    fn duration(&self, sound: u64) -> Option<f32> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.duration(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.duration(sound),
+        }
+    }
+
+    #[inline]
+    fn position(&self, sound: u64) -> f32 {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.position(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.position(sound),
+        }
+    }
+
+    #[inline]
+    fn seek(&mut self, sound: u64, position: f32) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.seek(sound, position),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.seek(sound, position),
+        }
+    }
+
+    #[inline]
+    fn set_playback_rate(&mut self, sound: u64, rate: f32) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_playback_rate(sound, rate),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_playback_rate(sound, rate),
+        }
+    }
+
+    #[inline]
+    fn playback_rate(&self, sound: u64) -> f32 {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.playback_rate(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.playback_rate(sound),
+        }
+    }
+
+    #[inline]
+    fn set_loop_region(&mut self, sound: u64, region: Option<LoopRegion>) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_loop_region(sound, region),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_loop_region(sound, region),
+        }
+    }
+
+    #[inline]
+    fn loop_region(&self, sound: u64) -> Option<LoopRegion> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.loop_region(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.loop_region(sound),
+        }
+    }
+
+    #[inline]
+    fn set_pan(&mut self, sound: u64, pan: f32) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_pan(sound, pan),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_pan(sound, pan),
+        }
+    }
+
+    #[inline]
+    fn pan(&self, sound: u64) -> f32 {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.pan(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.pan(sound),
+        }
+    }
+
+    #[inline]
+    fn set_spatial_options(&mut self, sound: u64, options: SpatialOptions) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_spatial_options(sound, options),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_spatial_options(sound, options),
+        }
+    }
+
+    #[inline]
+    fn spatial_options(&self, sound: u64) -> Option<SpatialOptions> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.spatial_options(sound),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.spatial_options(sound),
+        }
+    }
+
+    #[inline]
+    fn set_listener(&mut self, listener: AudioListener) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_listener(listener),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_listener(listener),
+        }
+    }
+
+    #[inline]
+    fn listener(&self) -> AudioListener {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.listener(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.listener(),
+        }
+    }
+
+    #[inline]
+    fn create_bus(&mut self) -> Result<u64, String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.create_bus(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.create_bus(),
+        }
+    }
+
+    #[inline]
+    fn set_bus_volume(&mut self, bus: u64, volume: f32) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_bus_volume(bus, volume),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_bus_volume(bus, volume),
+        }
+    }
+
+    #[inline]
+    fn bus_volume(&self, bus: u64) -> f32 {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.bus_volume(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.bus_volume(bus),
+        }
+    }
+
+    #[inline]
+    fn set_bus_muted(&mut self, bus: u64, muted: bool) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.set_bus_muted(bus, muted),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.set_bus_muted(bus, muted),
+        }
+    }
+
+    #[inline]
+    fn is_bus_muted(&self, bus: u64) -> bool {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.is_bus_muted(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.is_bus_muted(bus),
+        }
+    }
+
+    #[inline]
+    fn pause_bus(&mut self, bus: u64) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.pause_bus(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.pause_bus(bus),
+        }
+    }
+
+    #[inline]
+    fn resume_bus(&mut self, bus: u64) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.resume_bus(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.resume_bus(bus),
+        }
+    }
+
+    #[inline]
+    fn is_bus_paused(&self, bus: u64) -> bool {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.is_bus_paused(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.is_bus_paused(bus),
+        }
+    }
+
+    #[inline]
+    fn set_bus_effects(&mut self, bus: u64, effects: &[AudioEffect]) {
         match &mut self.inner {
-            BackendImpl::Oddio(inner) => inner.clean(sources, sounds),
+            BackendImpl::Oddio(inner) => inner.set_bus_effects(bus, effects),
             #[cfg(target_arch = "wasm32")]
-            BackendImpl::Dummy(inner) => inner.clean(sources, sounds),
+            BackendImpl::Dummy(inner) => inner.set_bus_effects(bus, effects),
         }
     }
+
+    #[inline]
+    fn bus_effects(&self, bus: u64) -> Vec<AudioEffect> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.bus_effects(bus),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.bus_effects(bus),
+        }
+    }
+
+    #[inline]
+    fn input_devices(&self) -> Vec<String> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.input_devices(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.input_devices(),
+        }
+    }
+
+    #[inline]
+    fn start_capture(&mut self, device: Option<&str>) -> Result<(), String> {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.start_capture(device),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.start_capture(device),
+        }
+    }
+
+    #[inline]
+    fn stop_capture(&mut self) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.stop_capture(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.stop_capture(),
+        }
+    }
+
+    #[inline]
+    fn is_capturing(&self) -> bool {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.is_capturing(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.is_capturing(),
+        }
+    }
+
+    #[inline]
+    fn capture_sample_rate(&self) -> Option<u32> {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.capture_sample_rate(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.capture_sample_rate(),
+        }
+    }
+
+    #[inline]
+    fn read_capture(&mut self, samples: &mut Vec<f32>) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.read_capture(samples),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.read_capture(samples),
+        }
+    }
+
+    #[inline]
+    fn capture_level(&self) -> f32 {
+        match &self.inner {
+            BackendImpl::Oddio(inner) => inner.capture_level(),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.capture_level(),
+        }
+    }
+
+    #[inline]
+    fn clean(&mut self, sources: &[u64], sounds: &[u64], buses: &[u64]) {
+        match &mut self.inner {
+            BackendImpl::Oddio(inner) => inner.clean(sources, sounds, buses),
+            #[cfg(target_arch = "wasm32")]
+            BackendImpl::Dummy(inner) => inner.clean(sources, sounds, buses),
+        }
+    }
+}
+
+/// Destination of the mix
+enum AudioOutput {
+    // the stream plays while it's alive
+    Device { _stream: cpal::Stream },
+    Offline(RefCell<OfflineMixer>),
 }
 
 pub struct InnerBackend {
     source_id_count: u64,
     sound_id_count: u64,
-    mixer_handle: Handle<Gain<Mixer<[f32; 2]>>>,
-    _stream: cpal::Stream,
-    sources: HashMap<u64, Arc<Frames<[f32; 2]>>>,
+    bus_id_count: u64,
+    mixer_handle: MixerHandle,
+    scene_handle: SceneHandle,
+    sample_rate: u32,
+    output: AudioOutput,
+    capture: Option<AudioCapture>,
+    sources: HashMap<u64, AudioData>,
     sounds: HashMap<u64, AudioInfo>,
+    buses: HashMap<u64, BusInfo>,
     volume: f32,
+    effects: Vec<AudioEffect>,
+    listener: AudioListener,
 }
 
 impl InnerBackend {
@@ -228,9 +773,7 @@ impl InnerBackend {
             config
         );
 
-        let gain = Gain::new(Mixer::new());
-        let (mixer_handle, mixer) = oddio::split(gain);
-
+        let (mixer_handle, mixer) = split_mixer();
         let stream = device
             .build_output_stream(
                 &config,
@@ -247,15 +790,100 @@ impl InnerBackend {
 
         stream.play().map_err(|e| format!("{e:?}"))?;
 
-        Ok(Self {
+        Ok(Self::with_output(
+            mixer_handle,
+            sample_rate.0,
+            AudioOutput::Device { _stream: stream },
+        ))
+    }
+
+    fn offline(sample_rate: u32, clock: Option<Clock>) -> Self {
+        let (mixer_handle, mixer) = split_mixer();
+        let mixer = OfflineMixer::new(mixer, sample_rate, clock);
+        Self::with_output(
+            mixer_handle,
+            sample_rate,
+            AudioOutput::Offline(RefCell::new(mixer)),
+        )
+    }
+
+    fn with_output(mut mixer_handle: MixerHandle, sample_rate: u32, output: AudioOutput) -> Self {
+        let scene_handle = mixer_handle
+            .control::<Mixer<_>, _>()
+            .play(SpatialScene::new());
+
+        Self {
             source_id_count: 0,
             sound_id_count: 0,
+            bus_id_count: 0,
             mixer_handle,
-            _stream: stream,
+            scene_handle,
+            sample_rate,
+            output,
+            capture: None,
             sources: Default::default(),
             sounds: Default::default(),
+            buses: Default::default(),
             volume: 1.0,
-        })
+            effects: vec![],
+            listener: Default::default(),
+        }
+    }
+
+    fn render(&mut self, seconds: f32) -> Result<Vec<[f32; 2]>, String> {
+        match &self.output {
+            AudioOutput::Offline(mixer) if !mixer.borrow().uses_clock() => {
+                let frames = (seconds.max(0.0) * self.sample_rate as f32).round() as usize;
+                Ok(mixer.borrow_mut().render(frames))
+            }
+            _ => Err("Only offline audio backends can render.".to_string()),
+        }
+    }
+
+    // the backends without device that follow the clock advance the sounds without mixing them
+    fn sync(&mut self) {
+        let AudioOutput::Offline(mixer) = &self.output else {
+            return;
+        };
+
+        let mut mixer = mixer.borrow_mut();
+        let Some(elapsed) = mixer.elapsed() else {
+            return;
+        };
+
+        let buses = &self.buses;
+        self.sounds.values_mut().for_each(|s| {
+            if let Some(position) = s.playback.take_seek() {
+                s.playback.set_position(position);
+            }
+
+            let bus_paused = s
+                .bus
+                .and_then(|id| buses.get(&id))
+                .is_some_and(|b| b.state.paused);
+            let stop = s.handle.as_stop();
+            if bus_paused || stop.is_paused() || stop.is_stopped() {
+                return;
+            }
+
+            s.fade.advance(elapsed as f32);
+            if s.fade.is_stopping() {
+                stop.stop();
+                return;
+            }
+
+            let playback = &s.playback;
+            let position = playback.wrap(playback.position() + elapsed * s.rate.max(0.0) as f64);
+            match playback.duration() {
+                Some(duration) if position >= duration && playback.loop_bounds().is_none() => {
+                    playback.set_position(duration);
+                    stop.stop();
+                }
+                _ => playback.set_position(position),
+            }
+        });
+
+        mixer.update_finished();
     }
 
     fn set_global_volume(&mut self, volume: f32) {
@@ -269,43 +897,148 @@ impl InnerBackend {
         self.volume
     }
 
-    fn create_source(&mut self, bytes: &[u8]) -> Result<u64, String> {
-        let frames = frames_from_bytes(bytes)?;
+    fn create_source(&mut self, bytes: &[u8], kind: AudioSourceKind) -> Result<u64, String> {
+        let data = audio_data_from_bytes(bytes, kind)?;
+        Ok(self.add_source(data))
+    }
+
+    fn create_source_from_pcm(
+        &mut self,
+        samples: &[f32],
+        channels: u16,
+        sample_rate: u32,
+    ) -> Result<u64, String> {
+        let frames = frames_from_pcm(samples, channels, sample_rate)?;
+        Ok(self.add_source(AudioData::Static(frames)))
+    }
+
+    fn create_source_from_generator(
+        &mut self,
+        factory: AudioGeneratorFactory,
+    ) -> Result<u64, String> {
+        Ok(self.add_source(AudioData::Generator(factory)))
+    }
 
+    fn add_source(&mut self, data: AudioData) -> u64 {
         let id = self.source_id_count;
-        self.sources.insert(id, frames);
+        self.sources.insert(id, data);
 
         self.source_id_count += 1;
 
-        Ok(id)
+        id
     }
 
-    fn play_sound(&mut self, source: u64, volume: f32, repeat: bool) -> Result<u64, String> {
+    fn play_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+    ) -> Result<u64, String> {
+        self.play(source, volume, repeat, bus, None)
+    }
+
+    fn play_spatial_sound(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+        options: SpatialOptions,
+    ) -> Result<u64, String> {
+        self.play(source, volume, repeat, bus, Some(options))
+    }
+
+    fn play(
+        &mut self,
+        source: u64,
+        volume: f32,
+        repeat: bool,
+        bus: Option<u64>,
+        spatial: Option<SpatialOptions>,
+    ) -> Result<u64, String> {
+        self.sync();
         let volume = volume.clamp(0.0, 1.0);
-        let frames = self
+        let data = self
             .sources
             .get(&source)
             .ok_or_else(|| "Invalid audio source id.".to_string())?;
 
-        let handle = if repeat {
-            let mut signal = Gain::new(Cycle::new(frames.clone()));
-            signal.set_gain(volume_as_gain(volume));
-            let handle = self.mixer_handle.control::<Mixer<_>, _>().play(signal);
-            AudioHandle::Cycle(handle)
-        } else {
-            let mut signal = Gain::new(FramesSignal::from(frames.clone()));
-            signal.set_gain(volume_as_gain(volume));
-            let handle = self.mixer_handle.control::<Mixer<_>, _>().play(signal);
-            AudioHandle::Frame(handle)
+        let playback = Arc::new(PlaybackState::new(data.duration(), repeat));
+        let signal = Speed::new(create_signal(data, playback.clone())?);
+        let mut signal = Gain::new(Fade::new(Effects::new(signal)));
+        let handle = match &spatial {
+            Some(options) => {
+                let motion = SpatialMotion::new(options, &self.listener);
+                let max_distance = SpatialMotion::max_distance(options, &self.listener);
+                let rate = self.sample_rate;
+                signal.set_gain(spatial_gain(volume, motion.attenuation));
+                let handle = self.scene(bus)?.play_buffered(
+                    Mono::new(signal),
+                    oddio::SpatialOptions {
+                        position: motion.position.into(),
+                        velocity: motion.velocity.into(),
+                        // the attenuation is done by the gain using the distance model
+                        radius: f32::MAX,
+                    },
+                    max_distance,
+                    rate,
+                    SPATIAL_BUFFER_DURATION,
+                );
+                AudioHandle::Spatial(handle)
+            }
+            None => {
+                signal.set_gain(volume_as_gain(volume));
+                let handle = self.mixer(bus)?.play(Pan::new(signal));
+                AudioHandle::Stereo(handle)
+            }
         };
 
         let id = self.sound_id_count;
-        self.sounds.insert(id, AudioInfo { handle, volume });
+        self.sounds.insert(
+            id,
+            AudioInfo {
+                handle,
+                volume,
+                pan: 0.0,
+                effects: vec![],
+                spatial,
+                bus,
+                playback,
+                rate: 1.0,
+                fade: Ramp::default(),
+            },
+        );
         self.sound_id_count += 1;
         Ok(id)
     }
 
+    fn mixer(&mut self, bus: Option<u64>) -> Result<MixerControl<'_, [f32; 2]>, String> {
+        match bus {
+            Some(id) => Ok(self
+                .buses
+                .get_mut(&id)
+                .ok_or_else(|| "Invalid audio bus id.".to_string())?
+                .handle
+                .control::<Mixer<_>, _>()),
+            None => Ok(self.mixer_handle.control::<Mixer<_>, _>()),
+        }
+    }
+
+    fn scene(&mut self, bus: Option<u64>) -> Result<SpatialSceneControl<'_>, String> {
+        match bus {
+            Some(id) => Ok(self
+                .buses
+                .get_mut(&id)
+                .ok_or_else(|| "Invalid audio bus id.".to_string())?
+                .scene
+                .control::<SpatialScene, _>()),
+            None => Ok(self.scene_handle.control::<SpatialScene, _>()),
+        }
+    }
+
     fn pause(&mut self, sound: u64) {
+        self.sync();
         match self.sounds.get_mut(&sound) {
             None => log::warn!("Cannot pause sound, invalid id: {}", sound),
             Some(s) => s.handle.as_stop().pause(),
@@ -313,6 +1046,7 @@ impl InnerBackend {
     }
 
     fn resume(&mut self, sound: u64) {
+        self.sync();
         match self.sounds.get_mut(&sound) {
             None => log::warn!("Cannot resume sound, invalid id: {}", sound),
             Some(s) => s.handle.as_stop().resume(),
@@ -328,6 +1062,7 @@ impl InnerBackend {
 
     #[allow(clippy::wrong_self_convention)]
     fn is_stopped(&mut self, sound: u64) -> bool {
+        self.sync();
         match self.sounds.get_mut(&sound) {
             None => false,
             Some(s) => s.handle.as_stop().is_stopped(),
@@ -336,6 +1071,7 @@ impl InnerBackend {
 
     #[allow(clippy::wrong_self_convention)]
     fn is_paused(&mut self, sound: u64) -> bool {
+        self.sync();
         match self.sounds.get_mut(&sound) {
             None => false,
             Some(s) => s.handle.as_stop().is_paused(),
@@ -343,11 +1079,12 @@ impl InnerBackend {
     }
 
     fn set_volume(&mut self, sound: u64, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
         match self.sounds.get_mut(&sound) {
             None => log::warn!("Cannot set volume for sound: {}", sound),
             Some(s) => {
                 s.volume = volume;
-                s.handle.as_gain().set_gain(volume_as_gain(volume));
+                s.update(&self.listener, false);
             }
         }
     }
@@ -359,7 +1096,274 @@ impl InnerBackend {
         }
     }
 
-    fn clean(&mut self, sources: &[u64], sounds: &[u64]) {
+    fn fade(&mut self, sound: u64, from: Option<f32>, to: f32, duration: f32, stop: bool) {
+        let from = from.map(|volume| volume.clamp(0.0, 1.0));
+        let to = to.clamp(0.0, 1.0);
+        match self.sounds.get_mut(&sound) {
+            None => log::warn!("Cannot fade sound, invalid id: {}", sound),
+            Some(s) => {
+                s.handle.as_fade().fade(from, to, duration, stop);
+                s.fade.start(from, to, duration, stop);
+            }
+        }
+    }
+
+    fn set_effects(&mut self, sound: u64, effects: &[AudioEffect]) {
+        match self.sounds.get_mut(&sound) {
+            None => log::warn!("Cannot set effects for sound, invalid id: {}", sound),
+            Some(s) => {
+                s.handle.as_effects().set_effects(effects, self.sample_rate);
+                s.effects = effects.to_vec();
+            }
+        }
+    }
+
+    fn effects(&self, sound: u64) -> Vec<AudioEffect> {
+        self.sounds
+            .get(&sound)
+            .map_or_else(Vec::new, |s| s.effects.clone())
+    }
+
+    fn set_global_effects(&mut self, effects: &[AudioEffect]) {
+        self.mixer_handle
+            .control::<Effects<_>, _>()
+            .set_effects(effects, self.sample_rate);
+        self.effects = effects.to_vec();
+    }
+
+    fn global_effects(&self) -> Vec<AudioEffect> {
+        self.effects.clone()
+    }
+
+    fn duration(&self, sound: u64) -> Option<f32> {
+        let s = self.sounds.get(&sound)?;
+        s.playback.duration().map(|d| d as f32)
+    }
+
+    fn position(&self, sound: u64) -> f32 {
+        self.sounds
+            .get(&sound)
+            .map_or(0.0, |s| s.playback.position() as f32)
+    }
+
+    fn seek(&mut self, sound: u64, position: f32) {
+        match self.sounds.get(&sound) {
+            None => log::warn!("Cannot seek sound, invalid id: {}", sound),
+            Some(s) => s.playback.request_seek(position as f64),
+        }
+    }
+
+    fn set_playback_rate(&mut self, sound: u64, rate: f32) {
+        self.sync();
+        match self.sounds.get_mut(&sound) {
+            None => log::warn!("Cannot set playback rate for sound: {}", sound),
+            Some(s) => {
+                s.rate = rate;
+                s.handle.as_speed().set_speed(rate);
+            }
+        }
+    }
+
+    fn playback_rate(&self, sound: u64) -> f32 {
+        self.sounds.get(&sound).map_or(1.0, |s| s.rate)
+    }
+
+    fn set_loop_region(&mut self, sound: u64, region: Option<LoopRegion>) {
+        match self.sounds.get(&sound) {
+            None => log::warn!("Cannot set loop region for sound: {}", sound),
+            Some(s) => s.playback.set_loop_region(region),
+        }
+    }
+
+    fn loop_region(&self, sound: u64) -> Option<LoopRegion> {
+        self.sounds.get(&sound)?.playback.loop_region()
+    }
+
+    fn set_pan(&mut self, sound: u64, pan: f32) {
+        match self.sounds.get_mut(&sound) {
+            None => log::warn!("Cannot set pan for sound: {}", sound),
+            Some(s) => match s.handle.as_pan() {
+                Some(mut control) => {
+                    s.pan = pan;
+                    control.set_pan(pan);
+                }
+                None => log::warn!("Cannot set pan for spatial sound: {}", sound),
+            },
+        }
+    }
+
+    fn pan(&self, sound: u64) -> f32 {
+        self.sounds.get(&sound).map_or(0.0, |s| s.pan)
+    }
+
+    fn set_spatial_options(&mut self, sound: u64, options: SpatialOptions) {
+        match self.sounds.get_mut(&sound) {
+            Some(s) if s.spatial.is_some() => {
+                s.spatial = Some(options);
+                s.update(&self.listener, false);
+            }
+            _ => log::warn!("Cannot set spatial options for sound: {}", sound),
+        }
+    }
+
+    fn spatial_options(&self, sound: u64) -> Option<SpatialOptions> {
+        self.sounds.get(&sound).and_then(|s| s.spatial)
+    }
+
+    fn set_listener(&mut self, listener: AudioListener) {
+        self.listener = listener;
+        self.sounds
+            .values_mut()
+            .filter(|s| s.spatial.is_some())
+            .for_each(|s| s.update(&listener, false));
+    }
+
+    fn listener(&self) -> AudioListener {
+        self.listener
+    }
+
+    fn create_bus(&mut self) -> Result<u64, String> {
+        let id = self.bus_id_count;
+        self.add_bus(id, BusState::default());
+        self.bus_id_count += 1;
+        Ok(id)
+    }
+
+    fn add_bus(&mut self, id: u64, state: BusState) {
+        let mut signal = Gain::new(Effects::new(Mixer::new()));
+        signal.set_gain(state.gain());
+        let mut handle = self.mixer_handle.control::<Mixer<_>, _>().play(signal);
+        if state.paused {
+            handle.control::<Stop<_>, _>().pause();
+        }
+        if !state.effects.is_empty() {
+            handle
+                .control::<Effects<_>, _>()
+                .set_effects(&state.effects, self.sample_rate);
+        }
+
+        let scene = handle.control::<Mixer<_>, _>().play(SpatialScene::new());
+        self.buses.insert(
+            id,
+            BusInfo {
+                handle,
+                scene,
+                state,
+            },
+        );
+    }
+
+    fn set_bus_volume(&mut self, bus: u64, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match self.buses.get_mut(&bus) {
+            None => log::warn!("Cannot set volume for bus: {}", bus),
+            Some(b) => {
+                b.state.volume = volume;
+                let gain = b.state.gain();
+                b.handle.control::<Gain<_>, _>().set_gain(gain);
+            }
+        }
+    }
+
+    fn bus_volume(&self, bus: u64) -> f32 {
+        self.buses.get(&bus).map_or(0.0, |b| b.state.volume)
+    }
+
+    fn set_bus_muted(&mut self, bus: u64, muted: bool) {
+        match self.buses.get_mut(&bus) {
+            None => log::warn!("Cannot mute bus, invalid id: {}", bus),
+            Some(b) => {
+                b.state.muted = muted;
+                let gain = b.state.gain();
+                b.handle.control::<Gain<_>, _>().set_gain(gain);
+            }
+        }
+    }
+
+    fn is_bus_muted(&self, bus: u64) -> bool {
+        self.buses.get(&bus).is_some_and(|b| b.state.muted)
+    }
+
+    fn pause_bus(&mut self, bus: u64) {
+        self.sync();
+        match self.buses.get_mut(&bus) {
+            None => log::warn!("Cannot pause bus, invalid id: {}", bus),
+            Some(b) => {
+                b.state.paused = true;
+                b.handle.control::<Stop<_>, _>().pause();
+            }
+        }
+    }
+
+    fn resume_bus(&mut self, bus: u64) {
+        self.sync();
+        match self.buses.get_mut(&bus) {
+            None => log::warn!("Cannot resume bus, invalid id: {}", bus),
+            Some(b) => {
+                b.state.paused = false;
+                b.handle.control::<Stop<_>, _>().resume();
+            }
+        }
+    }
+
+    fn is_bus_paused(&self, bus: u64) -> bool {
+        self.buses.get(&bus).is_some_and(|b| b.state.paused)
+    }
+
+    fn set_bus_effects(&mut self, bus: u64, effects: &[AudioEffect]) {
+        let rate = self.sample_rate;
+        match self.buses.get_mut(&bus) {
+            None => log::warn!("Cannot set effects for bus, invalid id: {}", bus),
+            Some(b) => {
+                b.handle
+                    .control::<Effects<_>, _>()
+                    .set_effects(effects, rate);
+                b.state.effects = effects.to_vec();
+            }
+        }
+    }
+
+    fn bus_effects(&self, bus: u64) -> Vec<AudioEffect> {
+        self.buses
+            .get(&bus)
+            .map_or_else(Vec::new, |b| b.state.effects.clone())
+    }
+
+    fn input_devices(&self) -> Vec<String> {
+        input_devices()
+    }
+
+    fn start_capture(&mut self, device: Option<&str>) -> Result<(), String> {
+        // the previous capture stops when it's dropped
+        self.capture = None;
+        self.capture = Some(AudioCapture::new(device)?);
+        Ok(())
+    }
+
+    fn stop_capture(&mut self) {
+        self.capture = None;
+    }
+
+    fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    fn capture_sample_rate(&self) -> Option<u32> {
+        self.capture.as_ref().map(|c| c.sample_rate())
+    }
+
+    fn read_capture(&mut self, samples: &mut Vec<f32>) {
+        if let Some(capture) = &self.capture {
+            capture.read(samples);
+        }
+    }
+
+    fn capture_level(&self) -> f32 {
+        self.capture.as_ref().map_or(0.0, |c| c.level())
+    }
+
+    fn clean(&mut self, sources: &[u64], sounds: &[u64], buses: &[u64]) {
+        self.sync();
         sources.iter().for_each(|id| {
             self.sources.remove(id);
         });
@@ -368,14 +1372,47 @@ impl InnerBackend {
             self.sounds.remove(id);
         });
 
+        buses.iter().for_each(|id| {
+            if let Some(mut bus) = self.buses.remove(id) {
+                bus.handle.control::<Stop<_>, _>().stop();
+            }
+
+            // the sounds are removed with the bus
+            self.sounds
+                .values_mut()
+                .filter(|s| s.bus == Some(*id))
+                .for_each(|s| s.handle.as_stop().stop());
+        });
+
         log::trace!(
-            "Audio resources cleaned: Sources({:?}) - Sounds({:?})",
+            "Audio resources cleaned: Sources({:?}) - Sounds({:?}) - Buses({:?})",
             sources,
             sounds,
+            buses,
         );
     }
 }
 
+fn split_mixer() -> (MixerHandle, MixerSignal) {
+    oddio::split(Gain::new(Effects::new(Mixer::new())))
+}
+
+fn create_signal(
+    data: &AudioData,
+    playback: Arc<PlaybackState>,
+) -> Result<Box<dyn Signal<Frame = [f32; 2]> + Send>, String> {
+    Ok(match data {
+        AudioData::Static(frames) => Box::new(FramesPlayback::new(frames.clone(), playback)),
+        AudioData::Streaming(bytes) => Box::new(StreamSignal::new(bytes.clone(), playback)?),
+        AudioData::Generator(factory) => Box::new(GeneratorSignal::new(factory(), playback)),
+    })
+}
+
+// the attenuation is applied on top of the volume in decibels
+fn spatial_gain(volume: f32, attenuation: f32) -> f32 {
+    volume_as_gain(volume) + 20.0 * attenuation.max(0.00001).log10()
+}
+
 // convert [0.0 - 1.0] to [-100.0 - 0.0]
 // with headphones I can hear -90, so I opted to to -100
 fn volume_as_gain(volume: f32) -> f32 {
@@ -385,7 +1422,9 @@ fn volume_as_gain(volume: f32) -> f32 {
 
 #[cfg(test)]
 mod test {
-    use super::volume_as_gain;
+    use super::*;
+    use notan_audio::Audio;
+    use std::rc::Rc;
 
     #[test]
     fn test_volume_as_gain() {
@@ -393,4 +1432,150 @@ mod test {
         assert_eq!(volume_as_gain(0.5), -50.0);
         assert_eq!(volume_as_gain(1.0), 0.0);
     }
+
+    #[test]
+    fn offline_render() {
+        let mut backend = OddioBackend::offline(1000);
+        let source = backend
+            .create_source_from_pcm(&[0.5; 100], 1, 1000)
+            .unwrap();
+        let sound = backend.play_sound(source, 1.0, false, None).unwrap();
+
+        let frames = backend.render(0.05).unwrap();
+        assert_eq!(frames.len(), 50);
+        assert!(frames.iter().all(|f| f[0] > 0.3 && f[0] == f[1]));
+        assert!(!backend.is_stopped(sound));
+
+        // the sound finishes after its duration
+        let wav = backend.render_wav(0.1).unwrap();
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + 100 * 4);
+        assert!(backend.is_stopped(sound));
+        assert!(backend.render(0.01).unwrap().iter().all(|f| *f == [0.0; 2]));
+
+        assert!(OddioBackend::null().render(0.01).is_err());
+    }
+
+    // backend without device whose clock only advances when the test moves it
+    fn manual_null() -> OddioBackend {
+        OddioBackend {
+            inner: BackendImpl::Oddio(InnerBackend::offline(
+                NULL_SAMPLE_RATE,
+                Some(Clock::Manual(0.0)),
+            )),
+        }
+    }
+
+    fn advance(backend: &OddioBackend, seconds: f64) {
+        match &backend.inner {
+            BackendImpl::Oddio(inner) => match &inner.output {
+                AudioOutput::Offline(mixer) => mixer.borrow_mut().advance_clock(seconds),
+                AudioOutput::Device { .. } => unreachable!(),
+            },
+        }
+    }
+
+    #[test]
+    fn null_clock() {
+        let mut backend = manual_null();
+        let source = backend.create_source_from_pcm(&[0.5; 50], 1, 1000).unwrap();
+        let sound = backend.play_sound(source, 1.0, false, None).unwrap();
+        let repeated = backend.play_sound(source, 1.0, true, None).unwrap();
+        let paused = backend.play_sound(source, 1.0, false, None).unwrap();
+        backend.pause(paused);
+
+        // the sounds follow the time passed without being mixed
+        advance(&backend, 0.08);
+        assert!(backend.is_stopped(sound));
+        assert_eq!(backend.position(sound), 0.05);
+        assert!(!backend.is_stopped(repeated));
+        assert!((backend.position(repeated) - 0.03).abs() < 0.001);
+        assert!(backend.is_paused(paused));
+        assert_eq!(backend.position(paused), 0.0);
+    }
+
+    #[test]
+    fn fade_out_and_stop() {
+        // the mixed sounds stop when the fade ends
+        let mut backend = OddioBackend::offline(1000);
+        let source = backend.create_source_from_pcm(&[0.5; 50], 1, 1000).unwrap();
+        let sound = backend.play_sound(source, 1.0, true, None).unwrap();
+        backend.fade(sound, None, 0.0, 0.1, true);
+        backend.render(0.05).unwrap();
+        assert!(!backend.is_stopped(sound));
+        backend.render(0.06).unwrap();
+        assert!(backend.is_stopped(sound));
+
+        // without mixing the fades follow the clock and free the voices
+        let backend = Rc::new(RefCell::new(manual_null()));
+        let mut audio = Audio::new(backend.clone()).unwrap();
+        audio.set_max_voices(Some(2));
+        let source = audio.create_source_from_pcm(&[0.5; 50], 1, 1000).unwrap();
+        let from = audio.play_sound(&source, 1.0, true);
+        let to = audio.play_sound(&source, 1.0, true);
+        audio.crossfade(&from, &to, 0.1);
+
+        advance(&backend.borrow(), 0.05);
+        assert!(!audio.is_stopped(&from));
+        advance(&backend.borrow(), 0.06);
+        assert!(audio.is_stopped(&from));
+        assert!(!audio.is_stopped(&to));
+
+        audio.clean();
+        assert_eq!(audio.active_voices(), 1);
+    }
+
+    #[test]
+    fn offline_buses() {
+        let mut backend = OddioBackend::offline(1000);
+        let bus = backend.create_bus().unwrap();
+
+        // the sound of the bus plays on the left and the other one on the right
+        let left = backend
+            .create_source_from_pcm(&[0.5, 0.0].repeat(100), 2, 1000)
+            .unwrap();
+        let right = backend
+            .create_source_from_pcm(&[0.0, 0.5].repeat(100), 2, 1000)
+            .unwrap();
+        let bus_sound = backend.play_sound(left, 1.0, true, Some(bus)).unwrap();
+        let sound = backend.play_sound(right, 1.0, true, None).unwrap();
+
+        let last_frame = |backend: &mut OddioBackend| {
+            let frames = backend.render(0.2).unwrap();
+            frames[frames.len() - 1]
+        };
+        let near = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+        let [l, r] = last_frame(&mut backend);
+        assert!(near(l, 0.5) && near(r, 0.5));
+
+        backend.set_bus_volume(bus, 0.5);
+        let [l, r] = last_frame(&mut backend);
+        assert!(l > 0.0 && l < 0.1 && near(r, 0.5));
+
+        backend.set_bus_muted(bus, true);
+        let [l, r] = last_frame(&mut backend);
+        assert!(near(l, 0.0) && near(r, 0.5));
+
+        backend.set_bus_muted(bus, false);
+        backend.set_bus_volume(bus, 1.0);
+        backend.pause_bus(bus);
+        let [l, r] = last_frame(&mut backend);
+        assert!(l == 0.0 && near(r, 0.5));
+
+        // the effects of the bus are only applied to its sounds
+        backend.resume_bus(bus);
+        let limiter = [AudioEffect::limiter(-12.0)];
+        backend.set_bus_effects(bus, &limiter);
+        assert_eq!(backend.bus_effects(bus), limiter);
+        let [l, r] = last_frame(&mut backend);
+        assert!(near(l, 0.25) && near(r, 0.5));
+
+        // dropping the bus stops its sounds
+        backend.clean(&[], &[], &[bus]);
+        let [l, r] = last_frame(&mut backend);
+        assert!(l == 0.0 && near(r, 0.5));
+        assert!(backend.is_stopped(bus_sound));
+        assert!(!backend.is_stopped(sound));
+    }
 }
@@ -0,0 +1,193 @@
+use crate::ring::{ring_buffer, RingConsumer, RingProducer};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// seconds of samples kept when they are not read
+const MAX_CAPTURE_SECONDS: usize = 2;
+
+// seconds of samples an input callback can bring without allocating
+const CALLBACK_SECONDS: usize = 1;
+
+#[derive(Default)]
+struct CaptureShared {
+    /// RMS level of the last block captured as f32 bits
+    level: AtomicU32,
+    /// New samples lost because the buffer was full since the last read
+    dropped: AtomicUsize,
+}
+
+/// Writes the captured samples from the input callback without blocking
+struct CaptureWriter {
+    samples: RingProducer<f32>,
+    shared: Arc<CaptureShared>,
+    mono: Vec<f32>,
+}
+
+impl CaptureWriter {
+    /// Mixes the interleaved data to mono, the new samples that don't fit are dropped
+    /// keeping the ones not read yet
+    fn push(&mut self, data: &[f32], channels: usize) {
+        let channels = channels.max(1);
+        let frames = data.chunks_exact(channels);
+        if frames.len() == 0 {
+            return;
+        }
+
+        self.mono.clear();
+        self.mono
+            .extend(frames.map(|frame| frame.iter().sum::<f32>() / channels as f32));
+        let written = self.samples.push(&self.mono);
+        let dropped = self.mono.len() - written;
+        if dropped > 0 {
+            self.shared.dropped.fetch_add(dropped, Ordering::Relaxed);
+        }
+
+        let sum = self.mono.iter().map(|s| s * s).sum::<f32>();
+        let level = (sum / self.mono.len() as f32).sqrt();
+        self.shared.level.store(level.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Captures the audio of an input device while it's alive
+pub(crate) struct AudioCapture {
+    _stream: cpal::Stream,
+    samples: RingConsumer<f32>,
+    shared: Arc<CaptureShared>,
+    sample_rate: u32,
+}
+
+impl AudioCapture {
+    pub fn new(device: Option<&str>) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let device = match device {
+            Some(name) => host
+                .input_devices()
+                .map_err(|e| format!("{e:?}"))?
+                .find(|d| d.name().is_ok_and(|n| n == name))
+                .ok_or_else(|| format!("Invalid audio input device: {name}"))?,
+            None => host
+                .default_input_device()
+                .ok_or("No input device available")?,
+        };
+
+        let config = device
+            .default_input_config()
+            .map_err(|e| format!("{e:?}"))?;
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+
+        log::debug!(
+            "Audio Input Device {} with config {:?}",
+            device.name().unwrap_or_default(),
+            config
+        );
+
+        let (producer, samples) = ring_buffer(sample_rate as usize * MAX_CAPTURE_SECONDS);
+        let shared = Arc::new(CaptureShared::default());
+        let writer = CaptureWriter {
+            samples: producer,
+            shared: shared.clone(),
+            mono: Vec::with_capacity(sample_rate as usize * CALLBACK_SECONDS),
+        };
+
+        let stream_config = config.config();
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => build_stream::<f32>(&device, &stream_config, writer, channels),
+            SampleFormat::I16 => build_stream::<i16>(&device, &stream_config, writer, channels),
+            SampleFormat::U16 => build_stream::<u16>(&device, &stream_config, writer, channels),
+            SampleFormat::I32 => build_stream::<i32>(&device, &stream_config, writer, channels),
+            format => Err(format!("Unsupported input sample format: {format:?}")),
+        }?;
+
+        stream.play().map_err(|e| format!("{e:?}"))?;
+
+        Ok(Self {
+            _stream: stream,
+            samples,
+            shared,
+            sample_rate,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn read(&self, samples: &mut Vec<f32>) {
+        self.samples.drain_into(samples);
+        let dropped = self.shared.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            log::warn!("Audio capture dropped {dropped} samples not read in time");
+        }
+    }
+
+    pub fn level(&self) -> f32 {
+        f32::from_bits(self.shared.level.load(Ordering::Relaxed))
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut writer: CaptureWriter,
+    channels: usize,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let frames = config.sample_rate.0 as usize * CALLBACK_SECONDS;
+    let mut buffer = Vec::with_capacity(frames * channels);
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _| {
+                buffer.clear();
+                buffer.extend(data.iter().map(|s| s.to_sample::<f32>()));
+                writer.push(&buffer, channels);
+            },
+            |err| {
+                log::error!("{:?}", err);
+            },
+            None,
+        )
+        .map_err(|e| format!("{e:?}"))
+}
+
+pub(crate) fn input_devices() -> Vec<String> {
+    match cpal::default_host().input_devices() {
+        Ok(devices) => devices.filter_map(|d| d.name().ok()).collect(),
+        Err(err) => {
+            log::error!("{:?}", err);
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_mono_and_level() {
+        let (producer, samples) = ring_buffer(3);
+        let shared = Arc::new(CaptureShared::default());
+        let mut writer = CaptureWriter {
+            samples: producer,
+            shared: shared.clone(),
+            mono: vec![],
+        };
+
+        writer.push(&[0.5, 0.5, -0.5, -0.5], 2);
+        assert_eq!(f32::from_bits(shared.level.load(Ordering::Relaxed)), 0.5);
+
+        // the new samples that don't fit are dropped and counted
+        writer.push(&[1.0, 0.0, 0.2, 0.2], 2);
+        let mut read = vec![];
+        samples.drain_into(&mut read);
+        assert_eq!(read, [0.5, -0.5, 0.5]);
+        assert_eq!(shared.dropped.load(Ordering::Relaxed), 1);
+    }
+}
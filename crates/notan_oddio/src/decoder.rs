@@ -1,25 +1,190 @@
+use notan_audio::{AudioGeneratorFactory, AudioSourceKind};
 use oddio::Frames;
 use std::io::Cursor;
 use std::io::ErrorKind::UnexpectedEof;
 use std::sync::Arc;
 use symphonia::core::audio::SampleBuffer;
 use symphonia::core::codecs::Decoder;
-use symphonia::core::errors::Error::IoError;
-use symphonia::core::formats::{FormatReader, Packet};
+use symphonia::core::errors::Error::{DecodeError, IoError};
+use symphonia::core::formats::{FormatReader, Packet, SeekMode, SeekTo};
 use symphonia::core::io::MediaSourceStream;
+use symphonia::core::units::Time;
 use symphonia::default::{get_codecs, get_probe};
 
+/// Audio data of a source
+#[derive(Clone)]
+pub(crate) enum AudioData {
+    /// Decoded frames
+    Static(Arc<Frames<[f32; 2]>>),
+    /// Encoded bytes decoded when the sound plays
+    Streaming(Arc<[u8]>),
+    /// Frames generated when the sound plays
+    Generator(AudioGeneratorFactory),
+}
+
+impl AudioData {
+    /// Duration in seconds, streams get it from the decoder when they play
+    pub fn duration(&self) -> Option<f64> {
+        match self {
+            AudioData::Static(frames) => Some(frames.runtime()),
+            AudioData::Streaming(_) | AudioData::Generator(_) => None,
+        }
+    }
+}
+
+pub(crate) fn audio_data_from_bytes(
+    bytes: &[u8],
+    kind: AudioSourceKind,
+) -> Result<AudioData, String> {
+    match kind {
+        AudioSourceKind::Static => Ok(AudioData::Static(frames_from_bytes(bytes)?)),
+        // there are no threads to decode in the background on wasm
+        #[cfg(target_arch = "wasm32")]
+        AudioSourceKind::Streaming => Ok(AudioData::Static(frames_from_bytes(bytes)?)),
+        #[cfg(not(target_arch = "wasm32"))]
+        AudioSourceKind::Streaming => {
+            // check that the data can be decoded before it plays
+            let bytes: Arc<[u8]> = Arc::from(bytes);
+            StreamDecoder::new(bytes.clone())?;
+            Ok(AudioData::Streaming(bytes))
+        }
+    }
+}
+
 pub(crate) fn frames_from_bytes(bytes: &[u8]) -> Result<Arc<Frames<[f32; 2]>>, String> {
     let (mut samples, sample_rate) = decode_bytes(bytes.to_vec())?;
     let stereo = oddio::frame_stereo(&mut samples);
     Ok(Frames::from_slice(sample_rate, stereo))
 }
 
-fn decode_bytes(bytes: Vec<u8>) -> Result<(Vec<f32>, u32), String> {
-    let cursor = Box::new(Cursor::new(bytes));
-    let media = MediaSourceStream::new(cursor, Default::default());
+pub(crate) fn frames_from_pcm(
+    samples: &[f32],
+    channels: u16,
+    sample_rate: u32,
+) -> Result<Arc<Frames<[f32; 2]>>, String> {
+    if sample_rate == 0 {
+        return Err("The sample rate of the PCM data must be greater than 0".to_string());
+    }
+
+    let mut samples = samples.to_vec();
+    match channels {
+        1 if !samples.is_empty() => mono_to_stereo(&mut samples),
+        1 => {}
+        2 if samples.len() % 2 == 0 => {}
+        2 => return Err("Stereo PCM data must have an even number of samples".to_string()),
+        _ => return Err(format!("Unsupported number of PCM channels: {channels}")),
+    }
+
+    let stereo = oddio::frame_stereo(&mut samples);
+    Ok(Frames::from_slice(sample_rate, stereo))
+}
+
+/// Decodes the audio packet by packet
+pub(crate) struct StreamDecoder {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    sample_rate: u32,
+    is_stereo: bool,
+    // frames to discard after seeking to reach the exact position
+    skip_frames: usize,
+}
+
+impl StreamDecoder {
+    pub fn new(bytes: Arc<[u8]>) -> Result<Self, String> {
+        let (format, decoder, track_id) = open_media(Cursor::new(bytes))?;
+        let (sample_rate, is_stereo) = track_info(decoder.as_ref())?;
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            sample_rate,
+            is_stereo,
+            skip_frames: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Duration in seconds if the format provides it
+    pub fn duration(&self) -> Option<f64> {
+        let frames = self.decoder.codec_params().n_frames?;
+        Some(frames as f64 / self.sample_rate as f64)
+    }
+
+    /// Moves the decoder to the position in seconds
+    pub fn seek(&mut self, position: f64) -> Result<(), String> {
+        let seeked = self
+            .format
+            .seek(
+                SeekMode::Accurate,
+                SeekTo::Time {
+                    time: Time::from(position.max(0.0)),
+                    track_id: Some(self.track_id),
+                },
+            )
+            .map_err(|e| format!("Cannot seek the audio: {e}"))?;
+
+        self.decoder.reset();
+
+        // the format can seek before the position required
+        let diff = seeked.required_ts.saturating_sub(seeked.actual_ts);
+        self.skip_frames = match self.decoder.codec_params().time_base {
+            Some(time_base) => {
+                let time = time_base.calc_time(diff);
+                ((time.seconds as f64 + time.frac) * self.sample_rate as f64) as usize
+            }
+            None => diff as usize,
+        };
+
+        Ok(())
+    }
+
+    /// Decodes the next packet adding stereo samples, returns false at the end of the data
+    /// Corrupt packets are skipped without adding samples, only IO errors stop the decoder
+    pub fn decode_next(&mut self, samples: &mut Vec<f32>) -> Result<bool, String> {
+        loop {
+            match self.format.next_packet() {
+                Ok(packet) => {
+                    if packet.track_id() != self.track_id {
+                        continue;
+                    }
+
+                    let start = samples.len();
+                    decode_packet(samples, &mut self.decoder, packet)?;
+                    if !self.is_stereo && samples.len() > start {
+                        let mut mono = samples.split_off(start);
+                        mono_to_stereo(&mut mono);
+                        samples.extend(mono);
+                    }
+
+                    if self.skip_frames > 0 {
+                        let skip = (self.skip_frames * 2).min(samples.len() - start);
+                        samples.drain(start..start + skip);
+                        self.skip_frames -= skip / 2;
+                    }
 
-    let mut format = get_probe()
+                    return Ok(true);
+                }
+                Err(IoError(err)) if err.kind() == UnexpectedEof => return Ok(false),
+                Err(DecodeError(err)) => log::warn!("Skipping corrupt audio data: {err}"),
+                Err(err) => return Err(format!("Error decoding: {err}")),
+            }
+        }
+    }
+}
+
+type Media = (Box<dyn FormatReader>, Box<dyn Decoder>, u32);
+
+fn open_media<T>(cursor: Cursor<T>) -> Result<Media, String>
+where
+    T: AsRef<[u8]> + Send + Sync + 'static,
+{
+    let media = MediaSourceStream::new(Box::new(cursor), Default::default());
+
+    let format = get_probe()
         .format(
             &Default::default(),
             media,
@@ -35,10 +200,14 @@ fn decode_bytes(bytes: Vec<u8>) -> Result<(Vec<f32>, u32), String> {
 
     let track_id = track.id;
 
-    let mut decoder = get_codecs()
+    let decoder = get_codecs()
         .make(&track.codec_params, &Default::default())
         .map_err(|e| format!("Cannot get decoder: {e}"))?;
 
+    Ok((format, decoder, track_id))
+}
+
+fn track_info(decoder: &dyn Decoder) -> Result<(u32, bool), String> {
     let sample_rate = decoder
         .codec_params()
         .sample_rate
@@ -49,6 +218,13 @@ fn decode_bytes(bytes: Vec<u8>) -> Result<(Vec<f32>, u32), String> {
         .channels
         .is_some_and(|ch| ch.count() == 2);
 
+    Ok((sample_rate, is_stereo))
+}
+
+fn decode_bytes(bytes: Vec<u8>) -> Result<(Vec<f32>, u32), String> {
+    let (mut format, mut decoder, track_id) = open_media(Cursor::new(bytes))?;
+    let (sample_rate, is_stereo) = track_info(decoder.as_ref())?;
+
     let samples = get_samples(&mut decoder, &mut format, track_id, is_stereo)?;
     Ok((samples, sample_rate))
 }
@@ -127,6 +303,86 @@ fn decode_packet(
 
             Ok(())
         }
+        // the next packets can be decoded after a corrupt one
+        Err(DecodeError(err)) => {
+            log::warn!("Skipping corrupt audio packet: {err}");
+            Ok(())
+        }
         Err(err) => Err(format!("Error decoding: {err}")),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use symphonia::core::audio::AudioBufferRef;
+    use symphonia::core::codecs::{
+        CodecDescriptor, CodecParameters, DecoderOptions, FinalizeResult,
+    };
+    use symphonia::core::errors::Result;
+
+    /// Fails to decode one of the packets like if it was corrupt
+    struct CorruptDecoder {
+        inner: Box<dyn Decoder>,
+        packet: usize,
+        count: usize,
+    }
+
+    impl Decoder for CorruptDecoder {
+        fn try_new(_params: &CodecParameters, _options: &DecoderOptions) -> Result<Self> {
+            unimplemented!()
+        }
+
+        fn supported_codecs() -> &'static [CodecDescriptor] {
+            &[]
+        }
+
+        fn reset(&mut self) {
+            self.inner.reset()
+        }
+
+        fn codec_params(&self) -> &CodecParameters {
+            self.inner.codec_params()
+        }
+
+        fn decode(&mut self, packet: &Packet) -> Result<AudioBufferRef<'_>> {
+            self.count += 1;
+            if self.count == self.packet {
+                return Err(DecodeError("corrupt packet"));
+            }
+
+            self.inner.decode(packet)
+        }
+
+        fn finalize(&mut self) -> FinalizeResult {
+            self.inner.finalize()
+        }
+
+        fn last_decoded(&self) -> AudioBufferRef<'_> {
+            self.inner.last_decoded()
+        }
+    }
+
+    #[test]
+    fn skip_corrupt_packets() {
+        let bytes: Arc<[u8]> =
+            Arc::from(&include_bytes!("../../../examples/assets/click1.ogg")[..]);
+        let mut decoder = StreamDecoder::new(bytes.clone()).unwrap();
+        let mut expected = vec![];
+        while decoder.decode_next(&mut expected).unwrap() {}
+
+        let mut decoder = StreamDecoder::new(bytes.clone()).unwrap();
+        let (_, inner, _) = open_media(Cursor::new(bytes)).unwrap();
+        decoder.decoder = Box::new(CorruptDecoder {
+            inner,
+            packet: 3,
+            count: 0,
+        });
+
+        // the stream goes on after the corrupt packet
+        let mut samples = vec![];
+        while decoder.decode_next(&mut samples).unwrap() {}
+        assert!(!samples.is_empty());
+        assert!(samples.len() < expected.len());
+    }
+}
@@ -0,0 +1,522 @@
+use notan_audio::AudioEffect;
+use oddio::{Controlled, Filter, Signal, Swap};
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::mem::discriminant;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// max seconds of the delay effect
+const MAX_DELAY_TIME: f32 = 2.0;
+
+// freeverb tunings for 44100Hz, the right channel is spread to sound wider
+const COMB_LENGTHS: [usize; 4] = [1116, 1188, 1277, 1356];
+const ALLPASS_LENGTHS: [usize; 2] = [556, 441];
+const STEREO_SPREAD: usize = 23;
+const REVERB_INPUT_GAIN: f32 = 0.03;
+
+/// Applies a chain of effects to the signal
+/// The processors are created by the control and sent to the audio thread, the chain replaced
+/// goes back with the next effects sent so it's dropped by the control too.
+/// If the kind of the effects doesn't change only the parameters are sent.
+pub(crate) struct Effects<T: ?Sized> {
+    chain: Swap<EffectsUpdate>,
+    sent: RefCell<SentChain>,
+    applied: AtomicU64,
+    active: RefCell<Vec<Processor>>,
+    inner: T,
+}
+
+impl<T> Effects<T> {
+    pub fn new(signal: T) -> Self {
+        Self {
+            chain: Swap::new(Default::default),
+            sent: RefCell::new(Default::default()),
+            applied: AtomicU64::new(0),
+            active: RefCell::new(vec![]),
+            inner: signal,
+        }
+    }
+}
+
+/// Effects sent to the audio thread, the processors are only created when the kinds change
+#[derive(Default)]
+struct EffectsUpdate {
+    id: u64,
+    effects: Vec<AudioEffect>,
+    processors: Option<Vec<Processor>>,
+}
+
+/// Last processors sent by the control, only used on the control's thread
+#[derive(Default)]
+struct SentChain {
+    id: u64,
+    effects: Vec<AudioEffect>,
+    rate: u32,
+}
+
+impl SentChain {
+    fn same_kinds(&self, effects: &[AudioEffect], rate: u32) -> bool {
+        self.rate == rate
+            && self.effects.len() == effects.len()
+            && self
+                .effects
+                .iter()
+                .zip(effects)
+                .all(|(a, b)| discriminant(a) == discriminant(b))
+    }
+}
+
+impl<T: Signal<Frame = [f32; 2]>> Signal for Effects<T> {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        self.inner.sample(interval, out);
+
+        let mut chain = self.active.borrow_mut();
+        if self.chain.refresh() {
+            let update = unsafe { &mut *self.chain.received() };
+            match update.processors.as_mut() {
+                Some(received) => {
+                    // keeps the state of the processors of the same kind, so parameters change without clicks
+                    received
+                        .iter_mut()
+                        .zip(chain.iter_mut())
+                        .for_each(|(new, old)| new.keep_state(old));
+                    std::mem::swap(&mut *chain, received);
+                    self.applied.store(update.id, Ordering::Release);
+                }
+                None => chain
+                    .iter_mut()
+                    .zip(update.effects.iter())
+                    .for_each(|(processor, effect)| processor.set(*effect)),
+            }
+        }
+
+        chain
+            .iter_mut()
+            .for_each(|processor| out.iter_mut().for_each(|f| *f = processor.process(*f)));
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn handle_dropped(&self) {
+        self.inner.handle_dropped();
+    }
+}
+
+impl<T> Filter for Effects<T> {
+    type Inner = T;
+    fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Thread-safe control for an [`Effects`] filter
+pub(crate) struct EffectsControl<'a> {
+    chain: &'a Swap<EffectsUpdate>,
+    sent: &'a RefCell<SentChain>,
+    applied: &'a AtomicU64,
+}
+
+unsafe impl<'a, T: 'a> Controlled<'a> for Effects<T> {
+    type Control = EffectsControl<'a>;
+
+    unsafe fn make_control(signal: &'a Effects<T>) -> Self::Control {
+        EffectsControl {
+            chain: &signal.chain,
+            sent: &signal.sent,
+            applied: &signal.applied,
+        }
+    }
+}
+
+impl EffectsControl<'_> {
+    /// Sends the effects to the audio thread, `rate` is the sample rate of the output
+    pub fn set_effects(&mut self, effects: &[AudioEffect], rate: u32) {
+        let mut sent = self.sent.borrow_mut();
+
+        // the parameters are enough if the audio thread already uses processors of the same kind
+        let applied = self.applied.load(Ordering::Acquire) == sent.id;
+        let processors = if applied && sent.same_kinds(effects, rate) {
+            None
+        } else {
+            sent.id += 1;
+            sent.rate = rate;
+            sent.effects.clear();
+            sent.effects.extend_from_slice(effects);
+            Some(
+                effects
+                    .iter()
+                    .map(|effect| Processor::new(*effect, rate))
+                    .collect(),
+            )
+        };
+
+        unsafe {
+            // the pending chain was replaced on the audio thread or never received
+            let pending = &mut *self.chain.pending();
+            pending.id = sent.id;
+            pending.effects.clear();
+            pending.effects.extend_from_slice(effects);
+            pending.processors = processors;
+        }
+        self.chain.flush();
+    }
+}
+
+struct Processor {
+    effect: AudioEffect,
+    rate: f32,
+    kind: ProcessorKind,
+}
+
+enum ProcessorKind {
+    Biquad(Biquad),
+    Delay(Delay),
+    Reverb(Box<Reverb>),
+    Compressor(Compressor),
+}
+
+impl Processor {
+    fn new(effect: AudioEffect, rate: u32) -> Self {
+        let kind = match effect {
+            AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. } => {
+                ProcessorKind::Biquad(Biquad::default())
+            }
+            AudioEffect::Delay { .. } => ProcessorKind::Delay(Delay::new(rate)),
+            AudioEffect::Reverb { .. } => ProcessorKind::Reverb(Box::new(Reverb::new(rate))),
+            AudioEffect::Compressor { .. } => ProcessorKind::Compressor(Compressor::default()),
+        };
+
+        let mut processor = Self {
+            effect,
+            rate: rate as f32,
+            kind,
+        };
+        processor.set(effect);
+        processor
+    }
+
+    fn set(&mut self, effect: AudioEffect) {
+        self.effect = effect;
+        let rate = self.rate;
+        match (&mut self.kind, effect) {
+            (ProcessorKind::Biquad(b), AudioEffect::LowPass { cutoff, q }) => {
+                b.set(rate, cutoff, q, false)
+            }
+            (ProcessorKind::Biquad(b), AudioEffect::HighPass { cutoff, q }) => {
+                b.set(rate, cutoff, q, true)
+            }
+            (
+                ProcessorKind::Delay(d),
+                AudioEffect::Delay {
+                    time,
+                    feedback,
+                    mix,
+                },
+            ) => {
+                let len = d.buffer.len();
+                d.delay = ((time * rate) as usize).clamp(1, len - 1);
+                d.feedback = feedback.clamp(0.0, 0.99);
+                d.mix = mix.clamp(0.0, 1.0);
+            }
+            (
+                ProcessorKind::Reverb(r),
+                AudioEffect::Reverb {
+                    room_size,
+                    damping,
+                    mix,
+                },
+            ) => {
+                r.feedback = room_size.clamp(0.0, 1.0) * 0.28 + 0.7;
+                r.damping = damping.clamp(0.0, 1.0) * 0.4;
+                r.mix = mix.clamp(0.0, 1.0);
+            }
+            (
+                ProcessorKind::Compressor(c),
+                AudioEffect::Compressor {
+                    threshold,
+                    ratio,
+                    attack,
+                    release,
+                },
+            ) => {
+                c.threshold = threshold;
+                c.slope = 1.0 - 1.0 / ratio.max(1.0);
+                c.attack = time_coefficient(attack, rate);
+                c.release = time_coefficient(release, rate);
+            }
+            _ => unreachable!("The processor kind matches the effect"),
+        }
+    }
+
+    /// Takes the state of the old processor if it's the same kind
+    fn keep_state(&mut self, old: &mut Processor) {
+        let same_kind = discriminant(&self.effect) == discriminant(&old.effect);
+        if same_kind && self.rate == old.rate {
+            std::mem::swap(&mut self.kind, &mut old.kind);
+            self.set(self.effect);
+        }
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        match &mut self.kind {
+            ProcessorKind::Biquad(b) => b.process(frame),
+            ProcessorKind::Delay(d) => d.process(frame),
+            ProcessorKind::Reverb(r) => r.process(frame),
+            ProcessorKind::Compressor(c) => c.process(frame),
+        }
+    }
+}
+
+// coefficient of a one pole filter reaching the target in `time` seconds
+fn time_coefficient(time: f32, rate: f32) -> f32 {
+    if time <= 0.0 {
+        return 0.0;
+    }
+
+    (-1.0 / (time * rate)).exp()
+}
+
+fn mix(dry: [f32; 2], wet: [f32; 2], mix: f32) -> [f32; 2] {
+    [
+        dry[0] + (wet[0] - dry[0]) * mix,
+        dry[1] + (wet[1] - dry[1]) * mix,
+    ]
+}
+
+/// Second order filter using the coefficients of the Audio EQ Cookbook
+#[derive(Default)]
+struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+    // previous inputs and outputs of each channel
+    x: [[f32; 2]; 2],
+    y: [[f32; 2]; 2],
+}
+
+impl Biquad {
+    fn set(&mut self, rate: f32, cutoff: f32, q: f32, high_pass: bool) {
+        let w0 = 2.0 * PI * cutoff.clamp(10.0, rate * 0.49) / rate;
+        let alpha = w0.sin() / (2.0 * q.max(0.01));
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+        let b = if high_pass {
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0]
+        } else {
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0]
+        };
+
+        self.b = b.map(|b| b / a0);
+        self.a = [-2.0 * cos / a0, (1.0 - alpha) / a0];
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let mut out = [0.0; 2];
+        (0..2).for_each(|c| {
+            let [x1, x2] = self.x[c];
+            let [y1, y2] = self.y[c];
+            let x = frame[c];
+            let y =
+                self.b[0] * x + self.b[1] * x1 + self.b[2] * x2 - self.a[0] * y1 - self.a[1] * y2;
+            self.x[c] = [x, x1];
+            self.y[c] = [y, y1];
+            out[c] = y;
+        });
+        out
+    }
+}
+
+struct Delay {
+    buffer: Vec<[f32; 2]>,
+    index: usize,
+    delay: usize,
+    feedback: f32,
+    mix: f32,
+}
+
+impl Delay {
+    fn new(rate: u32) -> Self {
+        let len = (rate as f32 * MAX_DELAY_TIME) as usize + 1;
+        Self {
+            buffer: vec![[0.0; 2]; len.max(2)],
+            index: 0,
+            delay: 1,
+            feedback: 0.0,
+            mix: 0.0,
+        }
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let len = self.buffer.len();
+        let echo = self.buffer[(self.index + len - self.delay) % len];
+        self.buffer[self.index] = [
+            frame[0] + echo[0] * self.feedback,
+            frame[1] + echo[1] * self.feedback,
+        ];
+        self.index = (self.index + 1) % len;
+        mix(frame, echo, self.mix)
+    }
+}
+
+struct Comb {
+    buffer: Vec<f32>,
+    index: usize,
+    store: f32,
+}
+
+impl Comb {
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let out = self.buffer[self.index];
+        self.store = out * (1.0 - damping) + self.store * damping;
+        self.buffer[self.index] = input + self.store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        out
+    }
+}
+
+struct AllPass {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl AllPass {
+    fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * 0.5;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+/// Simplified freeverb with parallel comb filters followed by all-pass filters
+struct Reverb {
+    combs: [Vec<Comb>; 2],
+    allpasses: [Vec<AllPass>; 2],
+    feedback: f32,
+    damping: f32,
+    mix: f32,
+}
+
+impl Reverb {
+    fn new(rate: u32) -> Self {
+        let scale = rate as f32 / 44100.0;
+        let len = |n: usize, spread: usize| (((n + spread) as f32 * scale) as usize).max(1);
+        let combs = |spread| {
+            COMB_LENGTHS
+                .iter()
+                .map(|n| Comb {
+                    buffer: vec![0.0; len(*n, spread)],
+                    index: 0,
+                    store: 0.0,
+                })
+                .collect()
+        };
+        let allpasses = |spread| {
+            ALLPASS_LENGTHS
+                .iter()
+                .map(|n| AllPass {
+                    buffer: vec![0.0; len(*n, spread)],
+                    index: 0,
+                })
+                .collect()
+        };
+
+        Self {
+            combs: [combs(0), combs(STEREO_SPREAD)],
+            allpasses: [allpasses(0), allpasses(STEREO_SPREAD)],
+            feedback: 0.0,
+            damping: 0.0,
+            mix: 0.0,
+        }
+    }
+
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let input = (frame[0] + frame[1]) * REVERB_INPUT_GAIN;
+        let mut wet = [0.0; 2];
+        (0..2).for_each(|c| {
+            let (feedback, damping) = (self.feedback, self.damping);
+            let out = self.combs[c]
+                .iter_mut()
+                .map(|comb| comb.process(input, feedback, damping))
+                .sum();
+            wet[c] = self.allpasses[c]
+                .iter_mut()
+                .fold(out, |out, allpass| allpass.process(out));
+        });
+
+        mix(frame, wet, self.mix)
+    }
+}
+
+/// Peak compressor, with an infinite ratio it works as a limiter
+#[derive(Default)]
+struct Compressor {
+    threshold: f32,
+    slope: f32,
+    attack: f32,
+    release: f32,
+    envelope: f32,
+}
+
+impl Compressor {
+    fn process(&mut self, frame: [f32; 2]) -> [f32; 2] {
+        let level = frame[0].abs().max(frame[1].abs());
+        let coefficient = if level > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = level + (self.envelope - level) * coefficient;
+
+        let db = 20.0 * self.envelope.max(1e-6).log10();
+        if db <= self.threshold {
+            return frame;
+        }
+
+        let gain = 10f32.powf((self.threshold - db) * self.slope / 20.0);
+        frame.map(|s| s * gain)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use oddio::Constant;
+
+    fn run(signal: &Effects<Constant<[f32; 2]>>, effects: &[AudioEffect], len: usize) -> Vec<f32> {
+        unsafe { Effects::make_control(signal) }.set_effects(effects, 1000);
+        let mut out = vec![[0.0; 2]; len];
+        signal.sample(1.0 / 1000.0, &mut out);
+        out.iter().map(|f| f[0]).collect()
+    }
+
+    #[test]
+    fn effects_chain() {
+        let signal = Effects::new(Constant::new([1.0, 1.0]));
+
+        // a constant signal passes the low pass and is removed by the high pass
+        let out = run(&signal, &[AudioEffect::low_pass(100.0)], 200);
+        assert!((out[199] - 1.0).abs() < 0.01);
+        let out = run(&signal, &[AudioEffect::high_pass(100.0)], 200);
+        assert!(out[199].abs() < 0.01);
+
+        // the echo starts after the delay time
+        let out = run(&signal, &[AudioEffect::delay(0.01, 0.0)], 20);
+        assert_eq!(out[9], 0.5);
+        assert_eq!(out[10], 1.0);
+
+        // changing the parameters keeps the delay line and doesn't create new processors
+        let id = signal.sent.borrow().id;
+        let out = run(&signal, &[AudioEffect::delay(0.01, 0.5)], 20);
+        assert_eq!(out[0], 1.0);
+        assert_eq!(signal.sent.borrow().id, id);
+
+        // the limiter keeps the level under the threshold
+        let out = run(&signal, &[AudioEffect::limiter(-6.0)], 100);
+        assert!(out[99] < 0.51);
+        assert!(run(&signal, &[], 1)[0] == 1.0);
+    }
+}
@@ -0,0 +1,175 @@
+use oddio::{Controlled, Filter, Signal, Swap};
+use std::cell::Cell;
+
+#[derive(Clone, Copy, Default)]
+struct FadeCommand {
+    from: Option<f32>,
+    to: f32,
+    duration: f32,
+    stop: bool,
+}
+
+/// Level moving linearly to a target
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Ramp {
+    level: f32,
+    to: f32,
+    /// Level change per second
+    speed: f32,
+    stop: bool,
+}
+
+impl Default for Ramp {
+    fn default() -> Self {
+        Self {
+            level: 1.0,
+            to: 1.0,
+            speed: 0.0,
+            stop: false,
+        }
+    }
+}
+
+impl Ramp {
+    /// Moves the level from the current one, or `from`, to `to` in `duration` seconds
+    pub fn start(&mut self, from: Option<f32>, to: f32, duration: f32, stop: bool) {
+        let level = from.unwrap_or(self.level);
+        *self = Self {
+            level,
+            to,
+            speed: (to - level).abs() / duration.max(f32::EPSILON),
+            stop,
+        };
+    }
+
+    /// Moves the level towards the target for the seconds given
+    pub fn advance(&mut self, seconds: f32) {
+        let step = self.speed * seconds;
+        self.level = if self.level < self.to {
+            (self.level + step).min(self.to)
+        } else {
+            (self.level - step).max(self.to)
+        };
+    }
+
+    fn is_done(&self) -> bool {
+        self.level == self.to
+    }
+
+    /// The fade ended and it has to stop the signal
+    pub fn is_stopping(&self) -> bool {
+        self.stop && self.is_done()
+    }
+}
+
+/// Multiplies the signal by a level that moves linearly to a target,
+/// the level is updated on each frame so the fades are smooth and sample-accurate
+pub(crate) struct Fade<T: ?Sized> {
+    command: Swap<FadeCommand>,
+    ramp: Cell<Ramp>,
+    inner: T,
+}
+
+impl<T> Fade<T> {
+    pub fn new(signal: T) -> Self {
+        Self {
+            command: Swap::new(Default::default),
+            ramp: Cell::new(Ramp::default()),
+            inner: signal,
+        }
+    }
+}
+
+impl<T: Signal<Frame = [f32; 2]>> Signal for Fade<T> {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        let mut ramp = self.ramp.get();
+        if self.command.refresh() {
+            let command = unsafe { *self.command.received() };
+            ramp.start(command.from, command.to, command.duration, command.stop);
+        }
+
+        self.inner.sample(interval, out);
+
+        out.iter_mut().for_each(|frame| {
+            if !ramp.is_done() {
+                ramp.advance(interval);
+            }
+
+            frame[0] *= ramp.level;
+            frame[1] *= ramp.level;
+        });
+
+        self.ramp.set(ramp);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.ramp.get().is_stopping() || self.inner.is_finished()
+    }
+
+    fn handle_dropped(&self) {
+        self.inner.handle_dropped();
+    }
+}
+
+impl<T> Filter for Fade<T> {
+    type Inner = T;
+    fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Thread-safe control for a [`Fade`] filter
+pub(crate) struct FadeControl<'a>(&'a Swap<FadeCommand>);
+
+unsafe impl<'a, T: 'a> Controlled<'a> for Fade<T> {
+    type Control = FadeControl<'a>;
+
+    unsafe fn make_control(signal: &'a Fade<T>) -> Self::Control {
+        FadeControl(&signal.command)
+    }
+}
+
+impl FadeControl<'_> {
+    /// Moves the level from the current one, or `from`, to `to` in `duration` seconds
+    /// If `stop` is true the signal finishes when the fade ends
+    pub fn fade(&mut self, from: Option<f32>, to: f32, duration: f32, stop: bool) {
+        unsafe {
+            *self.0.pending() = FadeCommand {
+                from,
+                to,
+                duration,
+                stop,
+            };
+        }
+        self.0.flush();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use oddio::Constant;
+
+    #[test]
+    fn fade_and_stop() {
+        let mut signal = Fade::new(Constant::new([1.0, 1.0]));
+        let mut out = [[0.0; 2]; 4];
+
+        FadeControl(&signal.command).fade(Some(0.0), 1.0, 2.0, false);
+        signal.sample(1.0, &mut out);
+        assert_eq!(out.map(|f| f[0]), [0.5, 1.0, 1.0, 1.0]);
+
+        // continues from the current level
+        FadeControl(&signal.command).fade(None, 0.0, 4.0, true);
+        signal.sample(1.0, &mut out);
+        assert_eq!(out.map(|f| f[1]), [0.75, 0.5, 0.25, 0.0]);
+        assert!(signal.is_finished());
+
+        signal = Fade::new(Constant::new([1.0, 1.0]));
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [[1.0; 2]; 4]);
+        assert!(!signal.is_finished());
+    }
+}
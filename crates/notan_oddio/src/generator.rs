@@ -0,0 +1,78 @@
+use crate::playback::PlaybackState;
+use notan_audio::AudioGenerator;
+use oddio::Signal;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+
+/// Signal playing the frames of a user generator
+/// The generator cannot seek or loop, so it plays until it returns false
+pub(crate) struct GeneratorSignal {
+    generator: RefCell<Box<dyn AudioGenerator>>,
+    state: Arc<PlaybackState>,
+    finished: Cell<bool>,
+}
+
+impl GeneratorSignal {
+    pub fn new(generator: Box<dyn AudioGenerator>, state: Arc<PlaybackState>) -> Self {
+        Self {
+            generator: RefCell::new(generator),
+            state,
+            finished: Cell::new(false),
+        }
+    }
+}
+
+impl Signal for GeneratorSignal {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        out.fill([0.0; 2]);
+        if self.finished.get() {
+            return;
+        }
+
+        // the interval is the inverse of the device sample rate
+        let sample_rate = (1.0 / interval).round() as u32;
+        let playing = self.generator.borrow_mut().generate(sample_rate, out);
+        self.finished.set(!playing);
+
+        let _ = self.state.take_seek();
+        let position = self.state.position() + (interval * out.len() as f32) as f64;
+        self.state.set_position(position);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_until_finished() {
+        let mut count = 0;
+        let generator = move |rate: u32, frames: &mut [[f32; 2]]| {
+            assert_eq!(rate, 4);
+            frames.fill([0.5, -0.5]);
+            count += 1;
+            count < 2
+        };
+
+        let state = Arc::new(PlaybackState::new(None, false));
+        let signal = GeneratorSignal::new(Box::new(generator), state.clone());
+        let mut out = [[0.0; 2]; 4];
+
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [[0.5, -0.5]; 4]);
+        assert!(!signal.is_finished());
+
+        signal.sample(0.25, &mut out);
+        assert!(signal.is_finished());
+        assert_eq!(state.position(), 2.0);
+
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [[0.0; 2]; 4]);
+    }
+}
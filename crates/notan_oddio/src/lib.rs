@@ -1,5 +1,14 @@
 mod backend;
+mod capture;
 mod decoder;
+mod effect;
+mod fade;
+mod generator;
+mod offline;
+mod playback;
+mod ring;
+mod spatial;
+mod stream;
 
 #[cfg(target_arch = "wasm32")]
 mod webaudio;
@@ -0,0 +1,111 @@
+use crate::effect::Effects;
+use oddio::{Gain, Mixer, SplitSignal};
+use std::time::Instant;
+
+// frames rendered at once
+const CHUNK_FRAMES: usize = 1024;
+
+pub(crate) type MixerSignal = SplitSignal<Gain<Effects<Mixer<[f32; 2]>>>>;
+
+/// Time followed by the mixers without device that don't render
+pub(crate) enum Clock {
+    /// Time of the last update
+    System(Instant),
+    /// Seconds advanced by hand since the last update
+    #[cfg(test)]
+    Manual(f64),
+}
+
+impl Clock {
+    pub fn system() -> Self {
+        Self::System(Instant::now())
+    }
+
+    fn elapsed(&mut self) -> f64 {
+        match self {
+            Self::System(last) => {
+                let now = Instant::now();
+                let elapsed = (now - *last).as_secs_f64();
+                *last = now;
+                elapsed
+            }
+            #[cfg(test)]
+            Self::Manual(seconds) => std::mem::take(seconds),
+        }
+    }
+}
+
+/// Mixes the audio without a device
+/// With a clock nothing is mixed, the backend advances the sounds using the time passed
+pub(crate) struct OfflineMixer {
+    signal: MixerSignal,
+    sample_rate: u32,
+    clock: Option<Clock>,
+}
+
+impl OfflineMixer {
+    pub fn new(signal: MixerSignal, sample_rate: u32, clock: Option<Clock>) -> Self {
+        Self {
+            signal,
+            sample_rate,
+            clock,
+        }
+    }
+
+    pub fn uses_clock(&self) -> bool {
+        self.clock.is_some()
+    }
+
+    pub fn render(&mut self, frames: usize) -> Vec<[f32; 2]> {
+        let mut out = vec![[0.0; 2]; frames];
+        out.chunks_mut(CHUNK_FRAMES)
+            .for_each(|chunk| oddio::run(&self.signal, self.sample_rate, chunk));
+        self.update_finished();
+        out
+    }
+
+    /// Seconds passed since the last call, none without a clock
+    pub fn elapsed(&mut self) -> Option<f64> {
+        self.clock.as_mut().map(Clock::elapsed)
+    }
+
+    #[cfg(test)]
+    pub fn advance_clock(&mut self, seconds: f64) {
+        if let Some(Clock::Manual(elapsed)) = &mut self.clock {
+            *elapsed += seconds;
+        }
+    }
+
+    /// The mixer removes the stopped and dropped sounds before sampling, an empty pass does it now
+    pub fn update_finished(&self) {
+        oddio::run(&self.signal, self.sample_rate, &mut []);
+    }
+}
+
+/// Encodes the frames as a 16 bits stereo WAV file
+pub(crate) fn encode_wav(frames: &[[f32; 2]], sample_rate: u32) -> Vec<u8> {
+    let data_len = (frames.len() * 4) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+
+    // format: pcm, 2 channels, sample rate, bytes per second, block align, bits per sample
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 4).to_le_bytes());
+    bytes.extend_from_slice(&4u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    frames.iter().flatten().for_each(|sample| {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    });
+
+    bytes
+}
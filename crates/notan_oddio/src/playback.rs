@@ -0,0 +1,232 @@
+use notan_audio::LoopRegion;
+use oddio::{Frames, Signal};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::Thread;
+
+// value of the seek request when there is nothing to seek
+const NO_SEEK: u64 = u64::MAX;
+
+/// Position, seek requests and loop region shared between a sound and the audio thread
+pub(crate) struct PlaybackState {
+    position: AtomicU64,
+    seek: AtomicU64,
+    duration: AtomicU64,
+    looping: AtomicBool,
+    loop_start: AtomicU32,
+    loop_end: AtomicU32,
+    /// Thread decoding the stream, it's woken up when the sound seeks or loops
+    waker: OnceLock<Thread>,
+}
+
+impl PlaybackState {
+    pub fn new(duration: Option<f64>, repeat: bool) -> Self {
+        let state = Self {
+            position: AtomicU64::new(0.0f64.to_bits()),
+            seek: AtomicU64::new(NO_SEEK),
+            duration: AtomicU64::new(duration.unwrap_or(f64::NAN).to_bits()),
+            looping: AtomicBool::new(false),
+            loop_start: AtomicU32::new(0.0f32.to_bits()),
+            loop_end: AtomicU32::new(f32::INFINITY.to_bits()),
+            waker: OnceLock::new(),
+        };
+
+        if repeat {
+            state.set_loop_region(Some(LoopRegion::default()));
+        }
+
+        state
+    }
+
+    /// Current position in seconds
+    pub fn position(&self) -> f64 {
+        f64::from_bits(self.position.load(Ordering::Relaxed))
+    }
+
+    pub fn set_position(&self, position: f64) {
+        self.position.store(position.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn request_seek(&self, position: f64) {
+        self.seek
+            .store(position.max(0.0).to_bits(), Ordering::Relaxed);
+        self.wake();
+    }
+
+    /// Returns the position requested since the last call
+    pub fn take_seek(&self) -> Option<f64> {
+        match self.seek.swap(NO_SEEK, Ordering::Relaxed) {
+            NO_SEEK => None,
+            bits => Some(f64::from_bits(bits)),
+        }
+    }
+
+    /// Duration in seconds, streams without it on the format know it once decoded until the end
+    pub fn duration(&self) -> Option<f64> {
+        let duration = f64::from_bits(self.duration.load(Ordering::Relaxed));
+        (!duration.is_nan()).then_some(duration)
+    }
+
+    pub fn set_duration(&self, duration: f64) {
+        self.duration.store(duration.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn set_loop_region(&self, region: Option<LoopRegion>) {
+        if let Some(region) = region {
+            let end = region.end.unwrap_or(f32::INFINITY);
+            self.loop_start
+                .store(region.start.max(0.0).to_bits(), Ordering::Relaxed);
+            self.loop_end.store(end.to_bits(), Ordering::Relaxed);
+        }
+
+        self.looping.store(region.is_some(), Ordering::Relaxed);
+        self.wake();
+    }
+
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        if !self.looping.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let start = f32::from_bits(self.loop_start.load(Ordering::Relaxed));
+        let end = f32::from_bits(self.loop_end.load(Ordering::Relaxed));
+        Some(LoopRegion {
+            start,
+            end: end.is_finite().then_some(end),
+        })
+    }
+
+    /// Start and end in seconds of the loop, the end is limited by the duration
+    pub fn loop_bounds(&self) -> Option<(f64, f64)> {
+        let region = self.loop_region()?;
+        let duration = self.duration().unwrap_or(f64::INFINITY);
+        let end = region
+            .end
+            .map_or(duration, |end| (end as f64).min(duration));
+        let start = region.start as f64;
+        (start < end).then_some((start, end))
+    }
+
+    pub fn set_waker(&self, thread: Thread) {
+        let _ = self.waker.set(thread);
+    }
+
+    /// Unparks the thread set as waker
+    pub fn wake(&self) {
+        if let Some(thread) = self.waker.get() {
+            thread.unpark();
+        }
+    }
+
+    /// Moves the position back inside the loop if it passed the end
+    pub fn wrap(&self, position: f64) -> f64 {
+        match self.loop_bounds() {
+            Some((start, end)) if position >= end && end.is_finite() => {
+                start + (position - end) % (end - start)
+            }
+            _ => position,
+        }
+    }
+}
+
+/// Signal playing decoded frames with support to seek and loop a region
+pub(crate) struct FramesPlayback {
+    frames: Arc<Frames<[f32; 2]>>,
+    state: Arc<PlaybackState>,
+    cursor: Cell<f64>,
+}
+
+impl FramesPlayback {
+    pub fn new(frames: Arc<Frames<[f32; 2]>>, state: Arc<PlaybackState>) -> Self {
+        Self {
+            frames,
+            state,
+            cursor: Cell::new(0.0),
+        }
+    }
+}
+
+impl Signal for FramesPlayback {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        let rate = self.frames.rate() as f64;
+        let len = self.frames.len();
+        if let Some(position) = self.state.take_seek() {
+            self.cursor.set(position * rate);
+        }
+
+        let bounds = self
+            .state
+            .loop_bounds()
+            .map(|(start, end)| (start * rate, end * rate));
+
+        let get = |i: usize| self.frames.get(i).copied().unwrap_or([0.0; 2]);
+        let ds = interval as f64 * rate;
+        let mut cursor = self.cursor.get();
+        out.iter_mut().for_each(|o| {
+            if let Some((start, end)) = bounds {
+                if cursor >= end {
+                    cursor = start + (cursor - end) % (end - start);
+                }
+            }
+
+            let index = cursor as usize;
+            let next = match bounds {
+                // interpolate the last frame of the loop with the first one
+                Some((start, end)) if (index + 1) as f64 >= end => start as usize,
+                _ => index + 1,
+            };
+
+            let (a, b) = (get(index), get(next));
+            let fract = (cursor - index as f64) as f32;
+            *o = [a[0] + (b[0] - a[0]) * fract, a[1] + (b[1] - a[1]) * fract];
+            cursor += ds;
+        });
+
+        // keep the cursor in the data when it ends to avoid overflows
+        let cursor = cursor.min(len as f64);
+        self.cursor.set(cursor);
+        self.state.set_position(cursor / rate);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state.loop_bounds().is_none() && self.cursor.get() >= self.frames.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seek_and_loop_region() {
+        let samples = (0..10).map(|i| [i as f32; 2]).collect::<Vec<_>>();
+        let frames = Frames::from_slice(1, &samples);
+        let state = Arc::new(PlaybackState::new(Some(10.0), false));
+        let signal = FramesPlayback::new(frames, state.clone());
+
+        let mut out = [[0.0; 2]; 4];
+        state.request_seek(3.0);
+        signal.sample(1.0, &mut out);
+        assert_eq!(out.map(|f| f[0]), [3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(state.position(), 7.0);
+
+        // plays the intro and then repeats the region
+        state.set_loop_region(Some(LoopRegion {
+            start: 6.0,
+            end: Some(8.0),
+        }));
+        signal.sample(1.0, &mut out);
+        assert_eq!(out.map(|f| f[0]), [7.0, 6.0, 7.0, 6.0]);
+        assert_eq!(state.wrap(9.0), 7.0);
+
+        // without loop the signal ends after the data
+        state.set_loop_region(None);
+        state.request_seek(9.0);
+        signal.sample(1.0, &mut out);
+        assert_eq!(out.map(|f| f[0]), [9.0, 0.0, 0.0, 0.0]);
+        assert!(signal.is_finished());
+    }
+}
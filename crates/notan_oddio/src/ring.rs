@@ -0,0 +1,161 @@
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Ring<T> {
+    data: Box<[UnsafeCell<T>]>,
+    /// Index of the next item read, only changed by the consumer
+    head: AtomicUsize,
+    /// Index of the next item written, only changed by the producer
+    tail: AtomicUsize,
+}
+
+// the producer only writes the free slots and the consumer only reads the written ones
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    fn slot(&self, index: usize) -> *mut T {
+        self.data[index % self.data.len()].get()
+    }
+}
+
+/// Creates a lock-free ring buffer with one thread writing and another one reading
+pub(crate) fn ring_buffer<T: Copy + Default>(
+    capacity: usize,
+) -> (RingProducer<T>, RingConsumer<T>) {
+    let ring = Arc::new(Ring {
+        data: (0..capacity.max(1))
+            .map(|_| UnsafeCell::new(T::default()))
+            .collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+
+    let producer = RingProducer {
+        ring: ring.clone(),
+        _not_sync: PhantomData,
+    };
+    let consumer = RingConsumer {
+        ring,
+        _not_sync: PhantomData,
+    };
+    (producer, consumer)
+}
+
+/// Writing side of the ring buffer, it can be moved to another thread but not shared
+pub(crate) struct RingProducer<T> {
+    ring: Arc<Ring<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T: Copy> RingProducer<T> {
+    /// Writes the items that fit returning how many were written
+    pub fn push(&self, items: &[T]) -> usize {
+        let ring = &self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let free = ring.data.len() - tail.wrapping_sub(ring.head.load(Ordering::Acquire));
+        let len = items.len().min(free);
+        items[..len].iter().enumerate().for_each(|(i, item)| {
+            // safety: the slot is free so the consumer is not reading it
+            unsafe { *ring.slot(tail.wrapping_add(i)) = *item };
+        });
+
+        ring.tail.store(tail.wrapping_add(len), Ordering::Release);
+        len
+    }
+}
+
+/// Reading side of the ring buffer, it can be moved to another thread but not shared
+pub(crate) struct RingConsumer<T> {
+    ring: Arc<Ring<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T: Copy> RingConsumer<T> {
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Item at the index without removing it
+    pub fn get(&self, index: usize) -> Option<T> {
+        let ring = &self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        let len = ring.tail.load(Ordering::Acquire).wrapping_sub(head);
+        // safety: the slot is written so the producer is not writing it
+        (index < len).then(|| unsafe { *ring.slot(head.wrapping_add(index)) })
+    }
+
+    /// Moves all the items written to the vector
+    pub fn drain_into(&self, items: &mut Vec<T>) {
+        let len = self.len();
+        items.extend((0..len).filter_map(|i| self.get(i)));
+        self.skip(len);
+    }
+
+    /// Removes the first items, up to the ones written
+    pub fn skip(&self, count: usize) {
+        let ring = &self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        let len = ring.tail.load(Ordering::Acquire).wrapping_sub(head);
+        ring.head
+            .store(head.wrapping_add(count.min(len)), Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_and_read() {
+        let (producer, consumer) = ring_buffer::<u32>(4);
+        assert_eq!(producer.push(&[1, 2, 3]), 3);
+        assert_eq!(producer.push(&[4, 5]), 1);
+        assert_eq!(
+            (consumer.get(0), consumer.get(3), consumer.get(4)),
+            (Some(1), Some(4), None)
+        );
+
+        // the slots are reused after reading
+        consumer.skip(2);
+        assert_eq!(producer.push(&[5, 6, 7]), 2);
+        let mut items = vec![];
+        consumer.drain_into(&mut items);
+        assert_eq!(items, [3, 4, 5, 6]);
+        assert!(consumer.is_empty());
+        consumer.skip(10);
+        assert!(consumer.get(0).is_none());
+    }
+
+    #[test]
+    fn threads() {
+        let (producer, consumer) = ring_buffer::<usize>(16);
+        let thread = std::thread::spawn(move || {
+            let mut next = 0;
+            while next < 10_000 {
+                next += producer.push(&[next]);
+            }
+        });
+
+        let mut items = vec![];
+        while items.len() < 10_000 {
+            if let Some(item) = consumer.get(0) {
+                items.push(item);
+                consumer.skip(1);
+            }
+        }
+        thread.join().unwrap();
+        assert!(items.iter().enumerate().all(|(i, n)| i == *n));
+    }
+}
@@ -0,0 +1,256 @@
+use notan_audio::{AudioListener, SpatialOptions};
+use oddio::{Controlled, Filter, Signal};
+use std::cell::Cell;
+use std::f32::consts::FRAC_PI_2;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// frames mixed at once when converting to mono
+const CHUNK_SIZE: usize = 256;
+
+// the doppler effect needs a buffer as long as the time the sound takes to travel
+const MAX_DOPPLER_DISTANCE: f32 = 1000.0;
+
+/// Moves a stereo signal to the left or the right, changes are interpolated to avoid clicks
+pub(crate) struct Pan<T: ?Sized> {
+    pan: AtomicU32,
+    current: Cell<f32>,
+    inner: T,
+}
+
+impl<T> Pan<T> {
+    pub fn new(signal: T) -> Self {
+        Self {
+            pan: AtomicU32::new(0.0f32.to_bits()),
+            current: Cell::new(0.0),
+            inner: signal,
+        }
+    }
+}
+
+impl<T: Signal<Frame = [f32; 2]>> Signal for Pan<T> {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        self.inner.sample(interval, out);
+
+        let target = f32::from_bits(self.pan.load(Ordering::Relaxed));
+        let start = self.current.get();
+        if target == 0.0 && start == 0.0 {
+            return;
+        }
+
+        let step = (target - start) / out.len().max(1) as f32;
+        out.iter_mut().enumerate().for_each(|(i, frame)| {
+            *frame = pan_frame(*frame, start + step * (i + 1) as f32);
+        });
+        self.current.set(target);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn handle_dropped(&self) {
+        self.inner.handle_dropped();
+    }
+}
+
+impl<T> Filter for Pan<T> {
+    type Inner = T;
+    fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Thread-safe control for a [`Pan`] filter
+pub(crate) struct PanControl<'a>(&'a AtomicU32);
+
+unsafe impl<'a, T: 'a> Controlled<'a> for Pan<T> {
+    type Control = PanControl<'a>;
+
+    unsafe fn make_control(signal: &'a Pan<T>) -> Self::Control {
+        PanControl(&signal.pan)
+    }
+}
+
+impl PanControl<'_> {
+    pub fn set_pan(&mut self, pan: f32) {
+        self.0
+            .store(pan.clamp(-1.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+// equal power stereo panning, the same used by the web audio api
+fn pan_frame([left, right]: [f32; 2], pan: f32) -> [f32; 2] {
+    if pan <= 0.0 {
+        let x = (pan + 1.0) * FRAC_PI_2;
+        [left + right * x.cos(), right * x.sin()]
+    } else {
+        let x = pan * FRAC_PI_2;
+        [left * x.cos(), right + left * x.sin()]
+    }
+}
+
+/// Averages the channels of a stereo signal to play it on a spatial scene
+pub(crate) struct Mono<T: ?Sized>(T);
+
+impl<T> Mono<T> {
+    pub fn new(signal: T) -> Self {
+        Self(signal)
+    }
+}
+
+impl<T: Signal<Frame = [f32; 2]>> Signal for Mono<T> {
+    type Frame = f32;
+
+    fn sample(&self, interval: f32, out: &mut [f32]) {
+        let mut buf = [[0.0; 2]; CHUNK_SIZE];
+        out.chunks_mut(CHUNK_SIZE).for_each(|chunk| {
+            let buf = &mut buf[..chunk.len()];
+            self.0.sample(interval, buf);
+            chunk
+                .iter_mut()
+                .zip(buf.iter())
+                .for_each(|(o, [l, r])| *o = (l + r) * 0.5);
+        });
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+
+    fn handle_dropped(&self) {
+        self.0.handle_dropped();
+    }
+}
+
+impl<T> Filter for Mono<T> {
+    type Inner = T;
+    fn inner(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Position and velocity in meters relative to the listener, and the volume attenuation
+pub(crate) struct SpatialMotion {
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub attenuation: f32,
+}
+
+impl SpatialMotion {
+    pub fn new(options: &SpatialOptions, listener: &AudioListener) -> Self {
+        let position = to_listener_space(listener, sub(options.position, listener.position));
+        let distance = length(position);
+        let attenuation = options.attenuation(distance);
+
+        let scale = 1.0 / listener.units_per_meter.max(f32::EPSILON);
+        if options.doppler {
+            let velocity = to_listener_space(listener, sub(options.velocity, listener.velocity));
+            return Self {
+                position: mul(position, scale),
+                velocity: mul(velocity, scale),
+                attenuation,
+            };
+        }
+
+        // without doppler only the direction is used, one meter away to keep the delay constant
+        // sounds on top of the listener are heard like if they were on the XY plane
+        let direction = if distance > f32::EPSILON {
+            mul(position, 1.0 / distance)
+        } else {
+            [0.0, 1.0, 0.0]
+        };
+
+        Self {
+            position: direction,
+            velocity: [0.0; 3],
+            attenuation,
+        }
+    }
+
+    /// Distance in meters used to allocate the buffer of the delay
+    pub fn max_distance(options: &SpatialOptions, listener: &AudioListener) -> f32 {
+        if options.doppler {
+            let scale = 1.0 / listener.units_per_meter.max(f32::EPSILON);
+            (options.max_distance * scale).clamp(1.0, MAX_DOPPLER_DISTANCE)
+        } else {
+            1.0
+        }
+    }
+}
+
+// rotates the vector to the listener's basis where it faces -Z with +Y up
+fn to_listener_space(listener: &AudioListener, v: [f32; 3]) -> [f32; 3] {
+    let forward = normalize(listener.forward, [0.0, 0.0, -1.0]);
+    let right = normalize(cross(forward, listener.up), [1.0, 0.0, 0.0]);
+    let up = cross(right, forward);
+    [dot(v, right), dot(v, up), -dot(v, forward)]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn mul(a: [f32; 3], v: f32) -> [f32; 3] {
+    [a[0] * v, a[1] * v, a[2] * v]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3], fallback: [f32; 3]) -> [f32; 3] {
+    let len = length(a);
+    if len > f32::EPSILON {
+        mul(a, 1.0 / len)
+    } else {
+        fallback
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn listener_space() {
+        let mut listener = AudioListener {
+            position: [10.0, 10.0, 0.0],
+            ..Default::default()
+        };
+
+        // by default +X is on the right
+        let options = SpatialOptions::new(20.0, 10.0, 0.0);
+        let motion = SpatialMotion::new(&options, &listener);
+        assert_eq!(motion.position, [1.0, 0.0, 0.0]);
+        assert_eq!(motion.attenuation, 0.1);
+
+        // facing +X the sound is in front of the listener
+        listener.forward = [1.0, 0.0, 0.0];
+        let motion = SpatialMotion::new(&options, &listener);
+        assert_eq!(motion.position, [0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn pan_stereo() {
+        let [left, right] = pan_frame([1.0, 1.0], 0.0);
+        assert!((left - 1.0).abs() < 1e-6 && right == 1.0);
+
+        // all the signal goes to the right channel
+        let [left, right] = pan_frame([1.0, 1.0], 1.0);
+        assert!(left.abs() < 1e-6 && right == 2.0);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::decoder::StreamDecoder;
+use crate::playback::PlaybackState;
+use crate::ring::{ring_buffer, RingConsumer, RingProducer};
+use oddio::Signal;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+// seconds of audio decoded ahead
+const BUFFER_SECONDS: f32 = 1.0;
+
+// time to wait when the buffer is full
+const WAIT_TIME: Duration = Duration::from_millis(10);
+
+// value of `StreamShared::seek` without a seek, the bits of a NaN
+const NO_SEEK: u64 = u64::MAX;
+
+struct StreamShared {
+    finished: AtomicBool,
+    /// Set when the signal is dropped so the decoder thread ends
+    closed: AtomicBool,
+    /// Position in seconds as f64 bits where the signal jumps discarding the frames buffered,
+    /// the decoder doesn't write frames until the signal does it
+    seek: AtomicU64,
+}
+
+impl Default for StreamShared {
+    fn default() -> Self {
+        Self {
+            finished: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            seek: AtomicU64::new(NO_SEEK),
+        }
+    }
+}
+
+impl StreamShared {
+    fn finish(&self) {
+        self.finished.store(true, Ordering::Release);
+    }
+
+    fn is_seeking(&self) -> bool {
+        self.seek.load(Ordering::Acquire) != NO_SEEK
+    }
+}
+
+/// Signal playing the frames decoded on a background thread
+/// The thread is parked after the end of the data until the sound seeks or loops,
+/// and it ends when the signal is dropped or the data cannot be decoded
+pub(crate) struct StreamSignal {
+    shared: Arc<StreamShared>,
+    frames: RingConsumer<[f32; 2]>,
+    state: Arc<PlaybackState>,
+    rate: u32,
+    t: Cell<f32>,
+    /// Position in seconds of the first frame
+    position: Cell<f64>,
+}
+
+impl StreamSignal {
+    pub fn new(bytes: Arc<[u8]>, state: Arc<PlaybackState>) -> Result<Self, String> {
+        let (signal, stream) = Self::with_decoder(bytes, state)?;
+        let weak = Arc::downgrade(&signal.shared);
+        let handle = std::thread::Builder::new()
+            .name("notan_audio_stream".to_string())
+            .spawn(move || decode_stream(stream, weak))
+            .map_err(|e| format!("Cannot create the audio stream thread: {e}"))?;
+
+        signal.state.set_waker(handle.thread().clone());
+        Ok(signal)
+    }
+
+    // returns the signal and the decoder writing its frames, that runs on its own thread
+    fn with_decoder(
+        bytes: Arc<[u8]>,
+        state: Arc<PlaybackState>,
+    ) -> Result<(Self, StreamDecode), String> {
+        let decoder = StreamDecoder::new(bytes.clone())?;
+        let rate = decoder.sample_rate();
+        let capacity = (rate as f32 * BUFFER_SECONDS) as usize;
+        if let Some(duration) = decoder.duration() {
+            state.set_duration(duration);
+        }
+
+        let (producer, frames) = ring_buffer(capacity);
+        let stream = StreamDecode {
+            decoder,
+            bytes,
+            producer,
+            state: state.clone(),
+            samples: vec![],
+            pending: vec![],
+            decoded: 0.0,
+            at_end: false,
+            jump_to: None,
+        };
+
+        let signal = Self {
+            shared: Arc::new(StreamShared::default()),
+            frames,
+            state,
+            rate,
+            t: Cell::new(0.0),
+            position: Cell::new(0.0),
+        };
+
+        Ok((signal, stream))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StreamStep {
+    Decoded,
+    /// The buffer is full or the signal is seeking
+    Wait,
+    /// All the data is decoded and written
+    Idle,
+    Done,
+}
+
+/// Decodes the data writing the frames to the buffer read by the signal
+struct StreamDecode {
+    decoder: StreamDecoder,
+    bytes: Arc<[u8]>,
+    producer: RingProducer<[f32; 2]>,
+    state: Arc<PlaybackState>,
+    samples: Vec<f32>,
+    /// Frames decoded that didn't fit on the buffer yet
+    pending: Vec<[f32; 2]>,
+    /// Position in seconds of the next frame decoded
+    decoded: f64,
+    at_end: bool,
+    jump_to: Option<f64>,
+}
+
+impl StreamDecode {
+    fn step(&mut self, shared: &StreamShared) -> StreamStep {
+        let rate = self.decoder.sample_rate() as f64;
+
+        // after the end the thread waits until the signal is dropped in case it seeks or loops
+        let seek_to = self.state.take_seek().inspect(|position| {
+            self.pending.clear();
+            shared.seek.store(position.to_bits(), Ordering::Release);
+            self.state.set_position(*position);
+        });
+
+        // jumps to the start of the loop after its end or the end of the data
+        let seek_to = seek_to.or(self.jump_to.take());
+        let seek_to = match self.state.loop_bounds() {
+            Some((start, _)) if self.at_end && seek_to.is_none() => Some(start),
+            _ => seek_to,
+        };
+
+        if let Some(position) = seek_to {
+            match seek(&mut self.decoder, &self.bytes, position) {
+                Ok(position) => self.decoded = position,
+                Err(err) => {
+                    log::error!("{}", err);
+                    shared.finish();
+                    return StreamStep::Done;
+                }
+            }
+
+            self.at_end = false;
+            shared.finished.store(false, Ordering::Release);
+        }
+
+        // the frames are written after the signal discards the old ones
+        if !shared.is_seeking() && !self.pending.is_empty() {
+            let written = self.producer.push(&self.pending);
+            self.pending.drain(..written);
+        }
+
+        if shared.is_seeking() || !self.pending.is_empty() {
+            return StreamStep::Wait;
+        }
+
+        if self.at_end {
+            return StreamStep::Idle;
+        }
+
+        self.samples.clear();
+        match self.decoder.decode_next(&mut self.samples) {
+            Ok(true) => {
+                let mut len = self.samples.len() / 2;
+                let next = self.decoded + len as f64 / rate;
+
+                // the frames after the end of the loop are discarded to jump to the start
+                let loop_start = match self.state.loop_bounds() {
+                    Some((start, end)) if next >= end => {
+                        len = (((end - self.decoded) * rate).max(0.0) as usize).min(len);
+                        Some(start)
+                    }
+                    _ => None,
+                };
+
+                let frames = self.samples[..len * 2].chunks_exact(2);
+                self.pending.extend(frames.map(|s| [s[0], s[1]]));
+                let written = self.producer.push(&self.pending);
+                self.pending.drain(..written);
+
+                self.decoded = next;
+                self.jump_to = loop_start;
+            }
+            Ok(false) => {
+                if self.state.duration().is_none() {
+                    self.state.set_duration(self.decoded);
+                }
+
+                // the signal finishes when it plays the frames left
+                self.at_end = true;
+                if self.state.loop_bounds().is_none() {
+                    shared.finish();
+                }
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                shared.finish();
+                return StreamStep::Done;
+            }
+        }
+
+        StreamStep::Decoded
+    }
+}
+
+fn decode_stream(mut stream: StreamDecode, shared: Weak<StreamShared>) {
+    while let Some(shared) = shared.upgrade() {
+        if shared.closed.load(Ordering::Acquire) {
+            break;
+        }
+
+        let step = stream.step(&shared);
+        drop(shared);
+        match step {
+            StreamStep::Decoded => {}
+            StreamStep::Wait => std::thread::park_timeout(WAIT_TIME),
+            // the state unparks the thread if the sound seeks or loops, and the signal when it's dropped
+            StreamStep::Idle => std::thread::park(),
+            StreamStep::Done => break,
+        }
+    }
+}
+
+// moves the decoder to the position returning the position reached
+fn seek(decoder: &mut StreamDecoder, bytes: &Arc<[u8]>, position: f64) -> Result<f64, String> {
+    // reopen the data to go to the start, it works even with formats that cannot seek
+    if position <= 0.0 {
+        *decoder = StreamDecoder::new(bytes.clone())?;
+        return Ok(0.0);
+    }
+
+    decoder.seek(position)?;
+    Ok(position)
+}
+
+impl Drop for StreamSignal {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+        self.state.wake();
+    }
+}
+
+impl Signal for StreamSignal {
+    type Frame = [f32; 2];
+
+    fn sample(&self, interval: f32, out: &mut [[f32; 2]]) {
+        // discards the frames before the seek, the decoder waits for it
+        // the decoder can seek again meanwhile, so only the seek read is cleared
+        let mut seek = self.shared.seek.load(Ordering::Acquire);
+        while seek != NO_SEEK {
+            self.frames.skip(self.frames.len());
+            self.t.set(0.0);
+            self.position.set(f64::from_bits(seek));
+            match self.shared.seek.compare_exchange(
+                seek,
+                NO_SEEK,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(current) => seek = current,
+            }
+        }
+
+        let frames = &self.frames;
+        let get = |i: usize| frames.get(i).unwrap_or([0.0; 2]);
+        let t = self.t.get();
+        let ds = interval * self.rate as f32;
+        out.iter_mut().enumerate().for_each(|(i, o)| {
+            let s = t + ds * i as f32;
+            let (a, b) = (get(s as usize), get(s as usize + 1));
+            let fract = s.fract();
+            *o = [a[0] + (b[0] - a[0]) * fract, a[1] + (b[1] - a[1]) * fract];
+        });
+
+        // if the decoder is behind the position waits for it
+        let next = (t + ds * out.len() as f32).min(frames.len() as f32);
+        frames.skip(next as usize);
+        self.t.set(next.fract());
+
+        let position = self.position.get() + (next as usize) as f64 / self.rate as f64;
+        self.position.set(self.state.wrap(position));
+        self.state.set_position(self.position.get());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.shared.finished.load(Ordering::Acquire) && self.frames.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stream_until_finished() {
+        let bytes: Arc<[u8]> =
+            Arc::from(&include_bytes!("../../../examples/assets/click1.ogg")[..]);
+
+        let mut decoder = StreamDecoder::new(bytes.clone()).unwrap();
+        let mut samples = vec![];
+        while decoder.decode_next(&mut samples).unwrap() {}
+        let expected: Vec<[f32; 2]> = samples.chunks_exact(2).map(|s| [s[0], s[1]]).collect();
+
+        let state = Arc::new(PlaybackState::new(None, false));
+        let (signal, mut stream) = StreamSignal::with_decoder(bytes, state.clone()).unwrap();
+        let interval = 1.0 / signal.rate as f32;
+
+        // the frames played, the silence added while the decoder is behind is not counted
+        let mut played = vec![];
+        let mut out = [[0.0; 2]; 512];
+        let mut tries = 0;
+        while !signal.is_finished() {
+            assert!(tries < 1000, "The stream didn't finish");
+            tries += 1;
+
+            // one packet decoded each time to play the frames decoded and the ones missing
+            assert_ne!(stream.step(&signal.shared), StreamStep::Done);
+            let position = state.position();
+            signal.sample(interval, &mut out);
+            let len = ((state.position() - position) * signal.rate as f64).round() as usize;
+            played.extend_from_slice(&out[..len]);
+        }
+
+        // no frames are lost or repeated
+        assert!(played.iter().any(|f| f[0] != 0.0 || f[1] != 0.0));
+        assert_eq!(played.len(), expected.len());
+        assert!(played == expected, "The stream has gaps");
+        assert!(state
+            .duration()
+            .is_some_and(|d| (state.position() - d).abs() < 0.01));
+
+        // the decoder waits after the end until the sound seeks
+        assert_eq!(stream.step(&signal.shared), StreamStep::Idle);
+        state.request_seek(0.0);
+        assert_eq!(stream.step(&signal.shared), StreamStep::Wait);
+        signal.sample(interval, &mut out);
+        assert_eq!(stream.step(&signal.shared), StreamStep::Decoded);
+        assert!(!signal.is_finished());
+    }
+
+    #[test]
+    fn stream_thread_ends_when_dropped() {
+        let bytes: Arc<[u8]> =
+            Arc::from(&include_bytes!("../../../examples/assets/click1.ogg")[..]);
+        let state = Arc::new(PlaybackState::new(None, false));
+        let (signal, stream) = StreamSignal::with_decoder(bytes, state.clone()).unwrap();
+        let weak = Arc::downgrade(&signal.shared);
+        let handle = std::thread::spawn(move || decode_stream(stream, weak));
+        state.set_waker(handle.thread().clone());
+
+        // the short sound is decoded and the thread is parked until the signal is dropped
+        let mut tries = 0;
+        while !signal.shared.finished.load(Ordering::Acquire) {
+            assert!(tries < 1000, "The stream didn't decode the data");
+            tries += 1;
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        drop(signal);
+        handle.join().unwrap();
+    }
+}
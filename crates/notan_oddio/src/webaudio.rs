@@ -1,9 +1,11 @@
 use hashbrown::HashMap;
-use notan_audio::AudioBackend;
-use oddio::Frames;
-use std::sync::Arc;
+use notan_audio::{
+    AudioBackend, AudioEffect, AudioGeneratorFactory, AudioListener, AudioSourceKind, LoopRegion,
+    SpatialOptions,
+};
 
-use crate::decoder::frames_from_bytes;
+use crate::backend::BusState;
+use crate::decoder::{audio_data_from_bytes, frames_from_pcm, AudioData};
 
 const WARN_TEXT: &str =
     "AudioContext cannot be initiated until the user interacts with the webpage.";
@@ -14,7 +16,11 @@ const WARN_TEXT: &str =
 pub(crate) struct DummyAudioBackend {
     pub id_count: u64,
     pub volume: f32,
-    pub sources: HashMap<u64, Arc<Frames<[f32; 2]>>>,
+    pub sources: HashMap<u64, AudioData>,
+    pub bus_id_count: u64,
+    pub buses: HashMap<u64, BusState>,
+    pub effects: Vec<AudioEffect>,
+    pub listener: AudioListener,
 }
 
 impl DummyAudioBackend {
@@ -29,8 +35,21 @@ impl DummyAudioBackend {
             id_count: 0,
             volume: 1.0,
             sources: Default::default(),
+            bus_id_count: 0,
+            buses: Default::default(),
+            effects: vec![],
+            listener: Default::default(),
         }
     }
+
+    fn add_source(&mut self, data: AudioData) -> u64 {
+        let id = self.id_count;
+        self.sources.insert(id, data);
+
+        self.id_count += 1;
+
+        id
+    }
 }
 
 impl AudioBackend for DummyAudioBackend {
@@ -43,19 +62,36 @@ impl AudioBackend for DummyAudioBackend {
         self.volume
     }
 
-    fn create_source(&mut self, bytes: &[u8]) -> Result<u64, String> {
-        let frames = frames_from_bytes(bytes)?;
-
-        let id = self.id_count;
-        self.sources.insert(id, frames);
+    fn create_source(&mut self, bytes: &[u8], kind: AudioSourceKind) -> Result<u64, String> {
+        let data = audio_data_from_bytes(bytes, kind)?;
+        Ok(self.add_source(data))
+    }
 
-        self.id_count += 1;
+    fn create_source_from_pcm(
+        &mut self,
+        samples: &[f32],
+        channels: u16,
+        sample_rate: u32,
+    ) -> Result<u64, String> {
+        let frames = frames_from_pcm(samples, channels, sample_rate)?;
+        Ok(self.add_source(AudioData::Static(frames)))
+    }
 
-        Ok(id)
+    fn create_source_from_generator(
+        &mut self,
+        factory: AudioGeneratorFactory,
+    ) -> Result<u64, String> {
+        Ok(self.add_source(AudioData::Generator(factory)))
     }
 
     #[allow(unreachable_code)]
-    fn play_sound(&mut self, _source: u64, _volume: f32, _repeat: bool) -> Result<u64, String> {
+    fn play_sound(
+        &mut self,
+        _source: u64,
+        _volume: f32,
+        _repeat: bool,
+        _bus: Option<u64>,
+    ) -> Result<u64, String> {
         log::error!("{}", WARN_TEXT);
         #[cfg(debug_assertions)]
         {
@@ -94,7 +130,115 @@ impl AudioBackend for DummyAudioBackend {
         0.0
     }
 
-    fn clean(&mut self, _sources: &[u64], _sounds: &[u64]) {
+    fn fade(&mut self, _sound: u64, _from: Option<f32>, _to: f32, _duration: f32, _stop: bool) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    fn set_effects(&mut self, _sound: u64, _effects: &[AudioEffect]) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    // the global effects are set when the audio context is enabled
+    fn set_global_effects(&mut self, effects: &[AudioEffect]) {
+        self.effects = effects.to_vec();
+    }
+
+    fn global_effects(&self) -> Vec<AudioEffect> {
+        self.effects.clone()
+    }
+
+    fn seek(&mut self, _sound: u64, _position: f32) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    fn set_playback_rate(&mut self, _sound: u64, _rate: f32) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    fn set_loop_region(&mut self, _sound: u64, _region: Option<LoopRegion>) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    fn set_pan(&mut self, _sound: u64, _pan: f32) {
+        log::error!("{}", WARN_TEXT);
+    }
+
+    fn set_spatial_options(&mut self, _sound: u64, _options: SpatialOptions) {
         log::error!("{}", WARN_TEXT);
     }
+
+    // the listener is set when the audio context is enabled
+    fn set_listener(&mut self, listener: AudioListener) {
+        self.listener = listener;
+    }
+
+    fn listener(&self) -> AudioListener {
+        self.listener
+    }
+
+    // buses are created when the audio context is enabled
+    fn create_bus(&mut self) -> Result<u64, String> {
+        let id = self.bus_id_count;
+        self.buses.insert(id, BusState::default());
+        self.bus_id_count += 1;
+        Ok(id)
+    }
+
+    fn set_bus_volume(&mut self, bus: u64, volume: f32) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.volume = volume.clamp(0.0, 1.0);
+        }
+    }
+
+    fn bus_volume(&self, bus: u64) -> f32 {
+        self.buses.get(&bus).map_or(0.0, |state| state.volume)
+    }
+
+    fn set_bus_muted(&mut self, bus: u64, muted: bool) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.muted = muted;
+        }
+    }
+
+    fn is_bus_muted(&self, bus: u64) -> bool {
+        self.buses.get(&bus).is_some_and(|state| state.muted)
+    }
+
+    fn pause_bus(&mut self, bus: u64) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.paused = true;
+        }
+    }
+
+    fn resume_bus(&mut self, bus: u64) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.paused = false;
+        }
+    }
+
+    fn is_bus_paused(&self, bus: u64) -> bool {
+        self.buses.get(&bus).is_some_and(|state| state.paused)
+    }
+
+    fn set_bus_effects(&mut self, bus: u64, effects: &[AudioEffect]) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.effects = effects.to_vec();
+        }
+    }
+
+    fn bus_effects(&self, bus: u64) -> Vec<AudioEffect> {
+        self.buses
+            .get(&bus)
+            .map_or_else(Vec::new, |state| state.effects.clone())
+    }
+
+    fn start_capture(&mut self, _device: Option<&str>) -> Result<(), String> {
+        Err(WARN_TEXT.to_string())
+    }
+
+    fn clean(&mut self, _sources: &[u64], _sounds: &[u64], buses: &[u64]) {
+        buses.iter().for_each(|id| {
+            self.buses.remove(id);
+        });
+    }
 }
@@ -1,5 +1,9 @@
-use crate::FONTS;
-use notan_glyph::{GlyphCalculator, GlyphCalculatorBuilder, GlyphCruncher, Section};
+use crate::{TextLayout, FACES, FONTS};
+use notan_glyph::ab_glyph::Font;
+use notan_glyph::{
+    section_bounds, section_glyphs, GlyphCalculator, GlyphCalculatorBuilder, GlyphCruncher,
+    Section, SectionGlyph, ShapingFaces,
+};
 use notan_math::{vec2, Rect, Vec2};
 
 /// Calculate the bounds of a glyph section
@@ -7,6 +11,7 @@ use notan_math::{vec2, Rect, Vec2};
 pub struct Calculator {
     fonts: usize,
     glyphs: Option<GlyphCalculator>,
+    faces: ShapingFaces,
 }
 
 impl Calculator {
@@ -14,6 +19,7 @@ impl Calculator {
         Self {
             fonts: 0,
             glyphs: None,
+            faces: ShapingFaces::default(),
         }
     }
 
@@ -21,6 +27,7 @@ impl Calculator {
         if let Some((length, calculator)) = generate_calculator_if_necessary(self.fonts) {
             self.fonts = length;
             self.glyphs = Some(calculator);
+            self.faces = FACES.read().clone();
         }
     }
 
@@ -32,7 +39,7 @@ impl Calculator {
         let glyphs = self.glyphs.as_mut().unwrap();
         let mut cache = glyphs.cache_scope();
 
-        match cache.glyph_bounds(section) {
+        match section_bounds(&mut cache, section, &self.faces) {
             None => Rect::default(),
             Some(bounds) => Rect {
                 x: bounds.min.x,
@@ -43,6 +50,38 @@ impl Calculator {
         }
     }
 
+    /// Returns the positioned glyphs of the section passed
+    pub fn glyphs(&mut self, section: &Section) -> Vec<SectionGlyph> {
+        self.create_calculator();
+
+        // Glyphs is always present so it's safe to unwrap
+        let glyphs = self.glyphs.as_mut().unwrap();
+        let mut cache = glyphs.cache_scope();
+        section_glyphs(&mut cache, section, &self.faces)
+    }
+
+    /// Returns the positioned glyphs with an outline, the ones that are drawn
+    pub fn visible_glyphs(&mut self, section: &Section) -> Vec<SectionGlyph> {
+        let mut glyphs = self.glyphs(section);
+
+        // Glyphs is always present so it's safe to unwrap
+        let calculator = self.glyphs.as_mut().unwrap();
+        let cache = calculator.cache_scope();
+        let fonts = cache.fonts();
+        glyphs.retain(|g| fonts[g.font_id.0].outline_glyph(g.glyph.clone()).is_some());
+        glyphs
+    }
+
+    /// Returns the layout of the section passed, used to place carets, select text or hit-test glyphs
+    pub fn layout(&mut self, section: &Section) -> TextLayout {
+        let glyphs = self.glyphs(section);
+
+        // Glyphs is always present so it's safe to unwrap
+        let calculator = self.glyphs.as_mut().unwrap();
+        let cache = calculator.cache_scope();
+        TextLayout::new(section, &glyphs, cache.fonts())
+    }
+
     /// Returns the bounds of the all sections mixed
     pub fn mixed_bounds(&mut self, sections: &[Section]) -> Rect {
         self.create_calculator();
@@ -53,7 +92,7 @@ impl Calculator {
 
         // iterate over sections to get the min position and the max size
         let rect = sections.iter().fold(MinMax::default(), |mm, section| {
-            match cache.glyph_bounds(section) {
+            match section_bounds(&mut cache, section, &self.faces) {
                 None => mm,
                 Some(bounds) => MinMax {
                     min: vec2(mm.min.x.min(bounds.min.x), mm.min.y.min(bounds.min.y)),
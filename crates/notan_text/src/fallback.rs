@@ -0,0 +1,267 @@
+use hashbrown::HashMap;
+use notan_glyph::ab_glyph::{
+    point, v2, CodepointIdIter, Font, FontArc, GlyphId, GlyphSvg, Outline, OutlineCurve, Point,
+    Rect,
+};
+use notan_glyph::FallbackGlyphs;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Font using a chain of fonts to find the glyphs missing on the primary one.
+/// The glyphs of the primary font keep their ids, the glyphs of the fallback fonts get
+/// new ids when they're used for the first time, and their metrics are scaled to match
+/// the primary font's height.
+pub(crate) struct FallbackFont {
+    primary: FontArc,
+    fallbacks: Vec<FontArc>,
+    ids: FallbackIds,
+}
+
+/// Ids given to the glyphs of the fallback fonts, shared with the faces used to shape the text
+#[derive(Clone)]
+pub(crate) struct FallbackIds {
+    first_id: usize,
+    scales: Arc<[f32]>,
+    inner: Arc<RwLock<IdTable>>,
+}
+
+#[derive(Default)]
+struct IdTable {
+    glyphs: Vec<(usize, GlyphId)>,
+    lookup: HashMap<(usize, GlyphId), GlyphId>,
+}
+
+impl FallbackIds {
+    fn new(primary: &FontArc, fallbacks: &[FontArc]) -> Self {
+        let scales = fallbacks
+            .iter()
+            .map(|font| {
+                let height = font.height_unscaled();
+                if height == 0.0 {
+                    return 1.0;
+                }
+
+                primary.height_unscaled() / height
+            })
+            .collect();
+
+        Self {
+            first_id: primary.glyph_count(),
+            scales,
+            inner: Default::default(),
+        }
+    }
+
+    /// Fallback index and the original id, none if the glyph is from the primary font
+    fn resolve(&self, id: GlyphId) -> Option<(usize, GlyphId)> {
+        let index = (id.0 as usize).checked_sub(self.first_id)?;
+        self.inner.read().glyphs.get(index).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.read().glyphs.len()
+    }
+}
+
+impl FallbackGlyphs for FallbackIds {
+    fn glyph_id(&self, index: usize, id: GlyphId) -> GlyphId {
+        if let Some(id) = self.inner.read().lookup.get(&(index, id)) {
+            return *id;
+        }
+
+        let mut ids = self.inner.write();
+        if let Some(id) = ids.lookup.get(&(index, id)) {
+            return *id;
+        }
+
+        let new_id = self.first_id + ids.glyphs.len();
+        if new_id > u16::MAX as usize {
+            log::warn!(
+                "Font fallback is out of glyph ids, the glyph {} of the fallback {} can't be used",
+                id.0,
+                index
+            );
+            return GlyphId(0);
+        }
+
+        let new_id = GlyphId(new_id as _);
+        ids.glyphs.push((index, id));
+        ids.lookup.insert((index, id), new_id);
+        new_id
+    }
+
+    fn scale(&self, index: usize) -> f32 {
+        self.scales[index]
+    }
+}
+
+impl FallbackFont {
+    pub fn new(primary: FontArc, fallbacks: Vec<FontArc>) -> Self {
+        let ids = FallbackIds::new(&primary, &fallbacks);
+        Self {
+            primary,
+            fallbacks,
+            ids,
+        }
+    }
+
+    /// Ids of the fallback glyphs, used to shape the text with the fallback fonts
+    pub fn ids(&self) -> FallbackIds {
+        self.ids.clone()
+    }
+
+    fn metric(&self, id: GlyphId, f: impl Fn(&FontArc, GlyphId) -> f32) -> f32 {
+        match self.ids.resolve(id) {
+            Some((index, id)) => f(&self.fallbacks[index], id) * self.ids.scale(index),
+            None => f(&self.primary, id),
+        }
+    }
+}
+
+impl Font for FallbackFont {
+    fn units_per_em(&self) -> Option<f32> {
+        self.primary.units_per_em()
+    }
+
+    fn ascent_unscaled(&self) -> f32 {
+        self.primary.ascent_unscaled()
+    }
+
+    fn descent_unscaled(&self) -> f32 {
+        self.primary.descent_unscaled()
+    }
+
+    fn line_gap_unscaled(&self) -> f32 {
+        self.primary.line_gap_unscaled()
+    }
+
+    fn glyph_id(&self, c: char) -> GlyphId {
+        let id = self.primary.glyph_id(c);
+        if id.0 != 0 {
+            return id;
+        }
+
+        self.fallbacks
+            .iter()
+            .enumerate()
+            .map(|(i, font)| (i, font.glyph_id(c)))
+            .find(|(_, id)| id.0 != 0)
+            .map_or(GlyphId(0), |(index, id)| self.ids.glyph_id(index, id))
+    }
+
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.metric(id, |f, id| f.h_advance_unscaled(id))
+    }
+
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.metric(id, |f, id| f.h_side_bearing_unscaled(id))
+    }
+
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.metric(id, |f, id| f.v_advance_unscaled(id))
+    }
+
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.metric(id, |f, id| f.v_side_bearing_unscaled(id))
+    }
+
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        match (self.ids.resolve(first), self.ids.resolve(second)) {
+            (None, None) => self.primary.kern_unscaled(first, second),
+            (Some((i1, first)), Some((i2, second))) if i1 == i2 => {
+                self.fallbacks[i1].kern_unscaled(first, second) * self.ids.scale(i1)
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        let Some((index, id)) = self.ids.resolve(id) else {
+            return self.primary.outline(id);
+        };
+
+        let scale = self.ids.scale(index);
+        let p = |p: Point| point(p.x * scale, p.y * scale);
+        let outline = self.fallbacks[index].outline(id)?;
+        Some(Outline {
+            bounds: Rect {
+                min: p(outline.bounds.min),
+                max: p(outline.bounds.max),
+            },
+            curves: outline
+                .curves
+                .into_iter()
+                .map(|curve| match curve {
+                    OutlineCurve::Line(a, b) => OutlineCurve::Line(p(a), p(b)),
+                    OutlineCurve::Quad(a, b, c) => OutlineCurve::Quad(p(a), p(b), p(c)),
+                    OutlineCurve::Cubic(a, b, c, d) => OutlineCurve::Cubic(p(a), p(b), p(c), p(d)),
+                })
+                .collect(),
+        })
+    }
+
+    fn glyph_count(&self) -> usize {
+        self.primary.glyph_count() + self.ids.len()
+    }
+
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.primary.codepoint_ids()
+    }
+
+    fn glyph_raster_image2(&self, id: GlyphId, pixel_size: u16) -> Option<v2::GlyphImage<'_>> {
+        match self.ids.resolve(id) {
+            Some((index, id)) => self.fallbacks[index].glyph_raster_image2(id, pixel_size),
+            None => self.primary.glyph_raster_image2(id, pixel_size),
+        }
+    }
+
+    fn glyph_svg_image(&self, id: GlyphId) -> Option<GlyphSvg<'_>> {
+        match self.ids.resolve(id) {
+            Some((index, id)) => self.fallbacks[index].glyph_svg_image(id),
+            None => self.primary.glyph_svg_image(id),
+        }
+    }
+
+    /// Data of the primary font, the text shaping uses the faces of the primary and the
+    /// fallback fonts registered with `ShapingFaces::push_with_fallbacks` instead
+    fn font_data(&self) -> &[u8] {
+        self.primary.font_data()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PIXEL: &[u8] = include_bytes!("../../../examples/assets/kenney_pixel-webfont.ttf");
+    const UBUNTU: &[u8] = include_bytes!("../../../examples/assets/Ubuntu-B.ttf");
+
+    #[test]
+    fn fallback_glyphs() {
+        let pixel = FontArc::try_from_slice(PIXEL).unwrap();
+        let ubuntu = FontArc::try_from_slice(UBUNTU).unwrap();
+        let font = FallbackFont::new(pixel.clone(), vec![ubuntu.clone()]);
+
+        // primary glyphs keep the same id and metrics
+        let a = font.glyph_id('a');
+        assert_eq!(a, pixel.glyph_id('a'));
+        assert_eq!(font.h_advance_unscaled(a), pixel.h_advance_unscaled(a));
+
+        // missing glyphs use new ids scaled to the primary's height
+        let c = 'Ж';
+        assert_eq!(pixel.glyph_id(c).0, 0);
+        let id = font.glyph_id(c);
+        assert_eq!(id.0 as usize, pixel.glyph_count());
+        assert_eq!(font.glyph_id(c), id);
+        assert!(font.outline(id).is_some());
+
+        let scale = pixel.height_unscaled() / ubuntu.height_unscaled();
+        let advance = ubuntu.h_advance_unscaled(ubuntu.glyph_id(c)) * scale;
+        assert!((font.h_advance_unscaled(id) - advance).abs() < 0.001);
+
+        // the shaping uses the same ids for the glyphs of the fallback fonts
+        let ids = font.ids();
+        assert_eq!(ids.glyph_id(0, ubuntu.glyph_id(c)), id);
+        assert!((ids.scale(0) - scale).abs() < 0.001);
+    }
+}
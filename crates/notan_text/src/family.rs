@@ -0,0 +1,154 @@
+use crate::{Font, Text, TextExtension};
+use notan_app::Graphics;
+use notan_glyph::ab_glyph::FontArc;
+
+/// Style used to select the variant of a font
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    #[default]
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+}
+
+impl FontStyle {
+    pub fn new(bold: bool, italic: bool) -> Self {
+        match (bold, italic) {
+            (false, false) => FontStyle::Regular,
+            (true, false) => FontStyle::Bold,
+            (false, true) => FontStyle::Italic,
+            (true, true) => FontStyle::BoldItalic,
+        }
+    }
+
+    pub fn is_bold(&self) -> bool {
+        matches!(self, FontStyle::Bold | FontStyle::BoldItalic)
+    }
+
+    pub fn is_italic(&self) -> bool {
+        matches!(self, FontStyle::Italic | FontStyle::BoldItalic)
+    }
+}
+
+enum FontSource<'a> {
+    Bytes(&'a [u8]),
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    System(&'a str),
+}
+
+impl FontSource<'_> {
+    /// Loads the font with the index of its face in the font data
+    #[allow(unused_variables)]
+    fn load(&self, style: FontStyle) -> Result<(FontArc, u32), String> {
+        match self {
+            FontSource::Bytes(data) => FontArc::try_from_vec(data.to_vec())
+                .map(|font| (font, 0))
+                .map_err(|e| e.to_string()),
+            #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+            FontSource::System(family) => crate::system::load_system_font(family, style)
+                .ok_or_else(|| format!("System font '{}' ({:?}) not found", family, style)),
+        }
+    }
+}
+
+/// Creates a font with style variants and a chain of fallback fonts used for
+/// the characters missing on the main font (emojis, CJK, etc...)
+pub struct FontFamilyBuilder<'a> {
+    gfx: &'a mut Graphics,
+    regular: FontSource<'a>,
+    variants: Vec<(FontStyle, FontSource<'a>)>,
+    fallbacks: Vec<FontSource<'a>>,
+}
+
+impl<'a> FontFamilyBuilder<'a> {
+    pub(crate) fn new(gfx: &'a mut Graphics, data: &'a [u8]) -> Self {
+        Self {
+            gfx,
+            regular: FontSource::Bytes(data),
+            variants: vec![],
+            fallbacks: vec![],
+        }
+    }
+
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    pub(crate) fn from_system(gfx: &'a mut Graphics, family: &'a str) -> Self {
+        Self {
+            gfx,
+            regular: FontSource::System(family),
+            variants: vec![],
+            fallbacks: vec![],
+        }
+    }
+
+    pub fn with_bold(mut self, data: &'a [u8]) -> Self {
+        self.variants
+            .push((FontStyle::Bold, FontSource::Bytes(data)));
+        self
+    }
+
+    pub fn with_italic(mut self, data: &'a [u8]) -> Self {
+        self.variants
+            .push((FontStyle::Italic, FontSource::Bytes(data)));
+        self
+    }
+
+    pub fn with_bold_italic(mut self, data: &'a [u8]) -> Self {
+        self.variants
+            .push((FontStyle::BoldItalic, FontSource::Bytes(data)));
+        self
+    }
+
+    /// Font used for the characters missing on the previous fonts of the chain
+    pub fn with_fallback(mut self, data: &'a [u8]) -> Self {
+        self.fallbacks.push(FontSource::Bytes(data));
+        self
+    }
+
+    /// Font installed on the system used for the characters missing on the previous fonts of the chain
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    pub fn with_system_fallback(mut self, family: &'a str) -> Self {
+        self.fallbacks.push(FontSource::System(family));
+        self
+    }
+
+    pub fn build(self) -> Result<Font, String> {
+        let Self {
+            gfx,
+            regular,
+            variants,
+            fallbacks,
+        } = self;
+
+        let font = regular.load(FontStyle::Regular)?;
+        #[allow(unused_mut)]
+        let mut variants = variants
+            .iter()
+            .map(|(style, source)| Ok((*style, source.load(*style)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // system families try to load the variants installed
+        #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+        if let FontSource::System(family) = regular {
+            for style in [FontStyle::Bold, FontStyle::Italic, FontStyle::BoldItalic] {
+                if variants.iter().any(|(s, _)| *s == style) {
+                    continue;
+                }
+
+                if let Some(font) = crate::system::load_system_font(family, style) {
+                    variants.push((style, font));
+                }
+            }
+        }
+
+        let fallbacks = fallbacks
+            .iter()
+            .map(|source| source.load(FontStyle::Regular))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(gfx
+            .extension_mut::<Text<'_>, TextExtension>()
+            .ok_or_else(|| "The TextExtension is not in use".to_string())?
+            .create_font_family(font, variants, fallbacks))
+    }
+}
@@ -0,0 +1,399 @@
+use notan_glyph::ab_glyph::{Font, FontArc, ScaleFont};
+use notan_glyph::{BuiltInLineBreaker, Layout, Section, SectionGlyph, VerticalAlign};
+use notan_math::{vec2, Rect, Vec2};
+use std::ops::Range;
+
+/// Positioned character of a text layout
+#[derive(Clone, Debug)]
+pub struct LayoutGlyph {
+    /// Byte index on the text, the texts of the section are joined in one
+    pub index: usize,
+    /// Index of the text in the section
+    pub section_index: usize,
+    pub character: char,
+    /// Area of the glyph using the advance as width and the line height as height
+    pub rect: Rect,
+    pub line: usize,
+}
+
+/// Visual line of a text layout, wrapped texts have more than one line per paragraph
+#[derive(Clone, Debug)]
+pub struct TextLine {
+    /// Byte range on the text, the texts of the section are joined in one
+    pub range: Range<usize>,
+    pub baseline: f32,
+    pub rect: Rect,
+}
+
+/// Position and height of a caret placed before a character
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Caret {
+    /// Top of the caret
+    pub position: Vec2,
+    pub height: f32,
+    pub line: usize,
+}
+
+/// Layout of a text section used to place carets, select text or check which
+/// character is under a point. Indices are byte indices on the texts joined.
+#[derive(Clone, Debug, Default)]
+pub struct TextLayout {
+    text: String,
+    glyphs: Vec<LayoutGlyph>,
+    lines: Vec<TextLine>,
+    position: Vec2,
+}
+
+struct LineBox {
+    range: Range<usize>,
+    baseline: f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    x: f32,
+    width: f32,
+    glyphs: Vec<LayoutGlyph>,
+}
+
+impl LineBox {
+    fn new(index: usize, baseline: f32, x: f32) -> Self {
+        Self {
+            range: index..index,
+            baseline,
+            ascent: 0.0,
+            descent: 0.0,
+            line_gap: 0.0,
+            x,
+            width: 0.0,
+            glyphs: vec![],
+        }
+    }
+
+    fn height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+    fn set_metrics<F: Font>(&mut self, font: impl ScaleFont<F>) {
+        self.ascent = self.ascent.max(font.ascent());
+        self.descent = self.descent.min(font.descent());
+        self.line_gap = self.line_gap.max(font.line_gap());
+    }
+}
+
+impl TextLayout {
+    pub(crate) fn new(section: &Section, glyphs: &[SectionGlyph], fonts: &[FontArc]) -> Self {
+        let mut text = String::new();
+        let offsets = section
+            .text
+            .iter()
+            .map(|t| {
+                let offset = text.len();
+                text.push_str(t.text);
+                offset
+            })
+            .collect::<Vec<_>>();
+
+        // glyphs come line by line, a new line starts when the baseline changes
+        let mut boxes: Vec<LineBox> = vec![];
+        glyphs.iter().for_each(|g| {
+            let index = offsets[g.section_index] + g.byte_index;
+            let character = text[index..].chars().next().unwrap_or_default();
+            let font = fonts[g.font_id.0].as_scaled(g.glyph.scale);
+            let pos = g.glyph.position;
+            let advance = font.h_advance(g.glyph.id);
+
+            let same_line = boxes
+                .last()
+                .is_some_and(|b| (b.baseline - pos.y).abs() < b.height() * 0.5);
+            if !same_line {
+                boxes.push(LineBox::new(index, pos.y, pos.x));
+            }
+
+            // glyphs in the same line can be reordered by bidi, so it's a min/max
+            let line = boxes.last_mut().unwrap();
+            let right = (line.x + line.width).max(pos.x + advance);
+            line.x = line.x.min(pos.x);
+            line.width = right - line.x;
+            line.range.start = line.range.start.min(index);
+            line.range.end = line.range.end.max(index + character.len_utf8());
+            line.set_metrics(font);
+            line.glyphs.push(LayoutGlyph {
+                index,
+                section_index: g.section_index,
+                character,
+                rect: Rect {
+                    x: pos.x,
+                    y: 0.0,
+                    width: advance,
+                    height: 0.0,
+                },
+                line: 0,
+            });
+        });
+
+        let position: Vec2 = section.screen_position.into();
+        let empty_line = |index: usize| {
+            // metrics of the text where the empty line is
+            let mut line = LineBox::new(index, 0.0, position.x);
+            let text_index = offsets.iter().rposition(|offset| *offset <= index);
+            if let Some(t) = text_index.and_then(|i| section.text.get(i)) {
+                if let Some(font) = fonts.get(t.font_id.0) {
+                    line.set_metrics(font.as_scaled(t.scale));
+                }
+            }
+            line
+        };
+
+        // paragraphs without glyphs (empty lines) need a line to place the caret
+        let mut lines: Vec<(LineBox, bool)> = vec![];
+        let mut boxes = boxes.into_iter().peekable();
+        paragraphs(&text).for_each(|range| {
+            let len = lines.len();
+            while let Some(line) = boxes.next_if(|b| b.range.start < range.end) {
+                lines.push((line, false));
+            }
+
+            if lines.len() == len {
+                lines.push((empty_line(range.start), true));
+            }
+        });
+        lines.extend(boxes.map(|line| (line, false)));
+
+        place_empty_lines(&mut lines, &section.layout, position.y);
+
+        let mut layout = Self {
+            position,
+            ..Default::default()
+        };
+
+        lines.into_iter().enumerate().for_each(|(i, (line, _))| {
+            let top = line.baseline - line.ascent;
+            let height = line.height();
+            layout.glyphs.extend(line.glyphs.into_iter().map(|mut g| {
+                g.rect.y = top;
+                g.rect.height = height;
+                g.line = i;
+                g
+            }));
+
+            layout.lines.push(TextLine {
+                range: line.range,
+                baseline: line.baseline,
+                rect: Rect {
+                    x: line.x,
+                    y: top,
+                    width: line.width,
+                    height,
+                },
+            });
+        });
+
+        layout.text = text;
+        layout
+    }
+}
+
+impl TextLayout {
+    /// Texts of the section joined
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Line where the caret for the index is placed
+    pub fn line_at(&self, index: usize) -> usize {
+        self.lines
+            .iter()
+            .rposition(|line| line.range.start <= index)
+            .unwrap_or_default()
+    }
+
+    /// Caret placed before the character at the index, the end of the text is `text().len()`
+    pub fn caret(&self, index: usize) -> Caret {
+        let index = index.min(self.text.len());
+        let line = self.line_at(index);
+        match self.lines.get(line) {
+            Some(l) => Caret {
+                position: vec2(self.x_in_line(line, index), l.rect.y),
+                height: l.rect.height,
+                line,
+            },
+            None => Caret {
+                position: self.position,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Index of the closest caret to the point
+    pub fn caret_at(&self, x: f32, y: f32) -> usize {
+        let Some(line) = self
+            .lines
+            .iter()
+            .position(|line| y < line.rect.max_y())
+            .or_else(|| self.lines.len().checked_sub(1))
+        else {
+            return 0;
+        };
+
+        let mut glyphs = self.line_glyphs(line).collect::<Vec<_>>();
+        glyphs.sort_by(|a, b| a.rect.x.total_cmp(&b.rect.x));
+        glyphs
+            .iter()
+            .find(|g| x < g.rect.center_x())
+            .map(|g| g.index)
+            .unwrap_or(self.lines[line].range.end)
+    }
+
+    /// Glyph under the point
+    pub fn glyph_at(&self, x: f32, y: f32) -> Option<&LayoutGlyph> {
+        self.glyphs.iter().find(|g| g.rect.contains(x, y))
+    }
+
+    /// Rectangles covering the characters of the range, one per line
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let start = range.start.max(line.range.start);
+                let end = range.end.min(line.range.end);
+                if start >= end {
+                    return None;
+                }
+
+                let x1 = self.x_in_line(i, start);
+                let x2 = self.x_in_line(i, end);
+                Some(Rect {
+                    x: x1.min(x2),
+                    y: line.rect.y,
+                    width: (x2 - x1).abs(),
+                    height: line.rect.height,
+                })
+            })
+            .collect()
+    }
+
+    fn line_glyphs(&self, line: usize) -> impl Iterator<Item = &LayoutGlyph> + Clone {
+        self.glyphs.iter().filter(move |g| g.line == line)
+    }
+
+    fn x_in_line(&self, line: usize, index: usize) -> f32 {
+        let glyphs = self.line_glyphs(line);
+        if let Some(g) = glyphs.clone().find(|g| g.index == index) {
+            return g.rect.x;
+        }
+
+        // end of the line or characters without glyph, like spaces trimmed or ligatures
+        glyphs
+            .filter(|g| g.index < index)
+            .max_by_key(|g| g.index)
+            .map_or(self.lines[line].rect.x, |g| g.rect.max_x())
+    }
+}
+
+// byte ranges of the paragraphs, without the line breaks
+fn paragraphs(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = 0;
+    text.split('\n').map(move |p| {
+        let range = start..start + p.len();
+        start = range.end + 1;
+        range
+    })
+}
+
+// empty lines are placed next to the closest line with glyphs
+fn place_empty_lines(lines: &mut [(LineBox, bool)], layout: &Layout<BuiltInLineBreaker>, y: f32) {
+    let Some(first) = lines.iter().position(|(_, empty)| !*empty) else {
+        let height = lines.iter().map(|(line, _)| line.height()).sum::<f32>();
+        let (Layout::SingleLine { v_align, .. } | Layout::Wrap { v_align, .. }) = layout;
+        let mut top = match v_align {
+            VerticalAlign::Top => y,
+            VerticalAlign::Center => y - height * 0.5,
+            VerticalAlign::Bottom => y - height,
+        };
+
+        lines.iter_mut().for_each(|(line, _)| {
+            line.baseline = top + line.ascent;
+            top += line.height();
+        });
+        return;
+    };
+
+    (0..first).rev().for_each(|i| {
+        let next = &lines[i + 1].0;
+        let baseline = next.baseline - next.ascent;
+        let line = &mut lines[i].0;
+        line.baseline = baseline + line.descent - line.line_gap;
+    });
+
+    (first + 1..lines.len()).for_each(|i| {
+        if !lines[i].1 {
+            return;
+        }
+
+        let prev = &lines[i - 1].0;
+        let bottom = prev.baseline - prev.descent + prev.line_gap;
+        let line = &mut lines[i].0;
+        line.baseline = bottom + line.ascent;
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use notan_glyph::{GlyphCalculatorBuilder, GlyphCruncher, Text};
+
+    const FONT: &[u8] = include_bytes!("../../../examples/assets/Ubuntu-B.ttf");
+
+    fn layout(section: &Section) -> TextLayout {
+        let font = FontArc::try_from_slice(FONT).unwrap();
+        let calculator = GlyphCalculatorBuilder::using_fonts(vec![font]).build();
+        let mut cache = calculator.cache_scope();
+        let glyphs = cache.glyphs(section).cloned().collect::<Vec<_>>();
+        TextLayout::new(section, &glyphs, cache.fonts())
+    }
+
+    #[test]
+    fn caret_and_selection() {
+        let section = Section::default()
+            .add_text(Text::new("Hello\n\nWorld").with_scale(20.0))
+            .with_screen_position((10.0, 10.0));
+        let layout = layout(&section);
+
+        // the empty line has its own line to place the caret
+        let lines = layout.lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].range, 6..6);
+        assert!(lines[0].rect.max_y() <= lines[1].rect.y + 0.01);
+        assert!(lines[1].rect.max_y() <= lines[2].rect.y + 0.01);
+
+        let start = layout.caret(0);
+        assert_eq!(start.position, vec2(10.0, 10.0));
+        assert_eq!(layout.caret(5).position.x, lines[0].rect.max_x());
+        assert_eq!(layout.caret(6).line, 1);
+        assert_eq!(layout.caret(6).position.x, 10.0);
+        assert_eq!(layout.caret(layout.text().len()).line, 2);
+
+        // hit-testing the left and right half of the 'W'
+        let w = layout.glyphs().iter().find(|g| g.character == 'W').unwrap();
+        let y = w.rect.center_y();
+        assert_eq!(layout.caret_at(w.rect.x + 1.0, y), 7);
+        assert_eq!(layout.caret_at(w.rect.max_x() - 1.0, y), 8);
+        assert_eq!(layout.caret_at(1000.0, y), layout.text().len());
+        assert_eq!(layout.glyph_at(w.rect.x + 1.0, y).unwrap().index, 7);
+
+        // the selection skips the empty line
+        let rects = layout.selection_rects(3..9);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0].max_x(), lines[0].rect.max_x());
+        assert_eq!(rects[1].x, w.rect.x);
+    }
+}
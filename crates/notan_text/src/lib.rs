@@ -1,5 +1,10 @@
 mod calculator;
 mod config;
+mod fallback;
+mod family;
+mod layout;
+#[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+mod system;
 
 use hashbrown::HashMap;
 use lazy_static::lazy_static;
@@ -7,35 +12,53 @@ use notan_app::{ExtContainer, GfxExtension, GfxRenderer, Graphics};
 use notan_glyph::ab_glyph::FontArc;
 use notan_glyph::{
     DefaultGlyphPipeline, FontId, GlyphBrush, GlyphBrushBuilder, GlyphPipeline, HorizontalAlign,
-    Layout, Section, Text as GText, VerticalAlign,
+    Layout, Section, ShapingFaces, Text as GText, VerticalAlign,
 };
 use notan_graphics::color::Color;
 use notan_graphics::pipeline::ClearOptions;
 use notan_graphics::{Device, RenderTexture, Renderer, Texture};
 use std::any::TypeId;
 use std::ops::DerefMut;
+use std::sync::Arc;
 
 pub use calculator::Calculator;
 pub use config::TextConfig;
+use fallback::FallbackFont;
+pub use family::{FontFamilyBuilder, FontStyle};
+pub use layout::{Caret, LayoutGlyph, TextLayout, TextLine};
 use notan_math::Mat4;
 use notan_math::Rect;
+#[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+pub use system::system_font_families;
 
 use parking_lot::RwLock;
 
 lazy_static! {
     static ref FONTS: RwLock<Vec<FontArc>> = RwLock::new(vec![]);
+    static ref FACES: RwLock<ShapingFaces> = RwLock::new(ShapingFaces::default());
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Font {
     id: u64,
     inner: FontId,
+    styles: [FontId; 4],
 }
 
 impl Font {
     pub fn id(&self) -> u64 {
         self.id
     }
+
+    /// Variant of the font family for the style, the same font if it has no variants
+    pub fn style(&self, style: FontStyle) -> Font {
+        let inner = self.styles[style as usize];
+        Font {
+            id: inner.0 as _,
+            inner,
+            styles: self.styles,
+        }
+    }
 }
 
 impl From<Font> for FontId {
@@ -78,16 +101,74 @@ impl TextExtension {
 
     pub fn create_font(&mut self, data: &[u8]) -> Result<Font, String> {
         let font = FontArc::try_from_vec(data.to_vec()).map_err(|err| err.to_string())?;
-        let id = self.glyph_brush.add_font(font.clone());
-
-        FONTS.write().push(font);
+        let id = self.add_font(font, |faces, font| faces.push(font));
 
         Ok(Font {
             id: id.0 as _,
             inner: id,
+            styles: [id; 4],
         })
     }
 
+    /// Registers the font and its style variants, using the fallback fonts for the missing glyphs.
+    /// The fonts are given with the index of their face in the font data.
+    pub(crate) fn create_font_family(
+        &mut self,
+        regular: (FontArc, u32),
+        variants: Vec<(FontStyle, (FontArc, u32))>,
+        fallbacks: Vec<(FontArc, u32)>,
+    ) -> Font {
+        let mut add = |(font, index): (FontArc, u32)| {
+            if fallbacks.is_empty() {
+                return self.add_font(font, |faces, font| faces.push_indexed(font, index));
+            }
+
+            let fallback = FallbackFont::new(
+                font.clone(),
+                fallbacks.iter().map(|(font, _)| font.clone()).collect(),
+            );
+            let ids = fallback.ids();
+            self.add_font(FontArc::new(fallback), |faces, _| {
+                faces.push_with_fallbacks(&font, index, &fallbacks, Arc::new(ids))
+            })
+        };
+
+        let id = add(regular);
+        let mut styles = [None; 4];
+        styles[FontStyle::Regular as usize] = Some(id);
+        variants.into_iter().for_each(|(style, font)| {
+            styles[style as usize] = Some(add(font));
+        });
+
+        // bold italic uses bold or italic if it's missing
+        let bold = styles[FontStyle::Bold as usize];
+        let italic = styles[FontStyle::Italic as usize];
+        let bold_italic = styles[FontStyle::BoldItalic as usize].or(bold).or(italic);
+        styles[FontStyle::BoldItalic as usize] = bold_italic;
+
+        Font {
+            id: id.0 as _,
+            inner: id,
+            styles: styles.map(|style| style.unwrap_or(id)),
+        }
+    }
+
+    fn add_font(
+        &mut self,
+        font: FontArc,
+        add_faces: impl FnOnce(&mut ShapingFaces, &FontArc),
+    ) -> FontId {
+        let id = self.glyph_brush.add_font(font.clone());
+
+        // the faces are parsed once and shared by the brush and the calculators
+        let mut faces = FACES.write();
+        add_faces(&mut faces, &font);
+        self.glyph_brush.set_shaping_faces(faces.clone());
+
+        FONTS.write().push(font);
+        id
+    }
+
     #[inline]
     pub fn add_pipeline<T>(&mut self, value: T)
     where
@@ -402,6 +483,14 @@ impl<'a> Text<'a> {
     pub fn bounds(&mut self) -> Rect {
         self.calculator.mixed_bounds(&self.sections)
     }
+
+    /// Layout of the last text added, used to place carets, select text or hit-test glyphs
+    pub fn last_layout(&mut self) -> TextLayout {
+        match self.sections.last() {
+            None => TextLayout::default(),
+            Some(section) => self.calculator.layout(section),
+        }
+    }
 }
 
 impl GfxRenderer for Text<'_> {
@@ -444,6 +533,9 @@ impl CreateText for RenderTexture {
 
 pub trait CreateFont {
     fn create_font(&mut self, data: &[u8]) -> Result<Font, String>;
+    fn create_font_family<'a>(&'a mut self, data: &'a [u8]) -> FontFamilyBuilder<'a>;
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    fn create_system_font_family<'a>(&'a mut self, family: &'a str) -> FontFamilyBuilder<'a>;
 }
 
 impl CreateFont for Graphics {
@@ -452,4 +544,13 @@ impl CreateFont for Graphics {
             .ok_or_else(|| "The TextExtension is not in use".to_string())?
             .create_font(data)
     }
+
+    fn create_font_family<'a>(&'a mut self, data: &'a [u8]) -> FontFamilyBuilder<'a> {
+        FontFamilyBuilder::new(self, data)
+    }
+
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    fn create_system_font_family<'a>(&'a mut self, family: &'a str) -> FontFamilyBuilder<'a> {
+        FontFamilyBuilder::from_system(self, family)
+    }
 }
@@ -0,0 +1,71 @@
+use crate::FontStyle;
+use fontdb::{Database, Family, Query, Style, Weight};
+use lazy_static::lazy_static;
+use notan_glyph::ab_glyph::{FontArc, FontVec};
+use parking_lot::Mutex;
+
+lazy_static! {
+    static ref SYSTEM_FONTS: Mutex<Option<Database>> = Mutex::new(None);
+}
+
+fn with_database<T>(f: impl FnOnce(&Database) -> T) -> T {
+    let mut db = SYSTEM_FONTS.lock();
+    let db = db.get_or_insert_with(|| {
+        let mut db = Database::new();
+        db.load_system_fonts();
+        log::debug!("Loaded {} system font faces", db.len());
+        db
+    });
+
+    f(db)
+}
+
+/// Returns the names of the font families installed on the system
+pub fn system_font_families() -> Vec<String> {
+    with_database(|db| {
+        let mut families = db
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect::<Vec<_>>();
+        families.sort();
+        families.dedup();
+        families
+    })
+}
+
+/// Loads a font installed on the system with the index of its face in the font file,
+/// none if the family doesn't have the style requested
+pub(crate) fn load_system_font(family: &str, style: FontStyle) -> Option<(FontArc, u32)> {
+    with_database(|db| {
+        let query = Query {
+            families: &[Family::Name(family)],
+            weight: if style.is_bold() {
+                Weight::BOLD
+            } else {
+                Weight::NORMAL
+            },
+            style: if style.is_italic() {
+                Style::Italic
+            } else {
+                Style::Normal
+            },
+            ..Default::default()
+        };
+
+        // the query returns the closest face, so we need to check that it's the style requested
+        let id = db.query(&query)?;
+        let face = db.face(id)?;
+        let is_bold = face.weight.0 >= Weight::SEMIBOLD.0;
+        let is_italic = face.style != Style::Normal;
+        if is_bold != style.is_bold() || is_italic != style.is_italic() {
+            return None;
+        }
+
+        db.with_face_data(id, |data, index| {
+            FontVec::try_from_vec_and_index(data.to_vec(), index)
+                .map(|font| (FontArc::new(font), index))
+                .map_err(|e| log::warn!("Invalid system font '{}': {}", family, e))
+                .ok()
+        })?
+    })
+}
@@ -326,7 +326,11 @@ impl BackendSystem for WinitBackend {
 
     #[cfg(feature = "audio")]
     fn get_audio_backend(&self) -> Rc<RefCell<dyn AudioBackend>> {
-        let backend = OddioBackend::new().unwrap();
+        // without an audio device the sounds are still tracked but not heard
+        let backend = OddioBackend::new().unwrap_or_else(|err| {
+            log::warn!("Cannot use the audio device, using a null audio backend: {err}");
+            OddioBackend::null()
+        });
         Rc::new(RefCell::new(backend))
     }
 }
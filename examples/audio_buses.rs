@@ -0,0 +1,93 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    music: AudioSource,
+    click: AudioSource,
+    music_bus: AudioBus,
+    sfx_bus: AudioBus,
+    sound: Option<Sound>,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 400))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let music = app
+        .audio
+        .create_source(include_bytes!("assets/jingles_NES00.ogg"))
+        .unwrap();
+
+    let click = app
+        .audio
+        .create_source(include_bytes!("assets/click1.ogg"))
+        .unwrap();
+
+    // Each bus mixes its sounds with its own volume, mute and pause state
+    let music_bus = app.audio.create_bus("music").unwrap();
+    let sfx_bus = app.audio.create_bus("sfx").unwrap();
+
+    State {
+        music,
+        click,
+        music_bus,
+        sfx_bus,
+        sound: None,
+    }
+}
+
+fn bus_controls(ui: &mut Ui, app: &mut App, bus: &AudioBus) {
+    ui.label(bus.name());
+
+    let mut volume = app.audio.bus_volume(bus);
+    if ui.add(egui::Slider::new(&mut volume, 0.0..=1.0)).changed() {
+        app.audio.set_bus_volume(bus, volume);
+    }
+
+    let mut muted = app.audio.is_bus_muted(bus);
+    if ui.checkbox(&mut muted, "Mute").changed() {
+        app.audio.set_bus_muted(bus, muted);
+    }
+
+    let paused = app.audio.is_bus_paused(bus);
+    if ui.button(if paused { "Resume" } else { "Pause" }).clicked() {
+        if paused {
+            app.audio.resume_bus(bus);
+        } else {
+            app.audio.pause_bus(bus);
+        }
+    }
+
+    ui.separator();
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            bus_controls(ui, app, &state.music_bus);
+            bus_controls(ui, app, &state.sfx_bus);
+
+            if state.sound.is_none() && ui.button("Play music").clicked() {
+                let sound = app
+                    .audio
+                    .play_sound_on_bus(&state.music, &state.music_bus, 1.0, true);
+                state.sound = Some(sound);
+            }
+
+            if ui.button("Play click").clicked() {
+                app.audio
+                    .play_sound_on_bus(&state.click, &state.sfx_bus, 1.0, false);
+            }
+        });
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
@@ -0,0 +1,77 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState, Default)]
+struct State {
+    devices: Vec<String>,
+    selected: Option<String>,
+    error: Option<String>,
+    peak: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 300))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    State {
+        devices: app.audio.input_devices(),
+        ..Default::default()
+    }
+}
+
+fn capture_controls(ui: &mut Ui, app: &mut App, state: &mut State) {
+    egui::ComboBox::from_label("Input")
+        .selected_text(state.selected.as_deref().unwrap_or("Default"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut state.selected, None, "Default");
+            state.devices.iter().for_each(|device| {
+                ui.selectable_value(&mut state.selected, Some(device.clone()), device);
+            });
+        });
+
+    if !app.audio.is_capturing() {
+        if ui.button("Start capture").clicked() {
+            state.error = app.audio.start_capture(state.selected.as_deref()).err();
+        }
+
+        if let Some(err) = &state.error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        return;
+    }
+
+    // The samples are read each frame, otherwise only the last seconds are kept
+    let samples = app.audio.read_capture();
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    state.peak = peak.max(state.peak * 0.95);
+
+    ui.label(format!(
+        "{} samples at {}Hz",
+        samples.len(),
+        app.audio.capture_sample_rate().unwrap_or(0)
+    ));
+    ui.label("Level");
+    ui.add(egui::ProgressBar::new(app.audio.capture_level()));
+    ui.label("Peak");
+    ui.add(egui::ProgressBar::new(state.peak));
+
+    if ui.button("Stop capture").clicked() {
+        app.audio.stop_capture();
+        state.peak = 0.0;
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| capture_controls(ui, app, state));
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
@@ -0,0 +1,75 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    music: [AudioSource; 2],
+    current: Option<(usize, Sound)>,
+    duration: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 300))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let music = [
+        app.audio
+            .create_source(include_bytes!("assets/jingles_NES00.ogg"))
+            .unwrap(),
+        app.audio
+            .create_source(include_bytes!("assets/jingles_PIZZI01.ogg"))
+            .unwrap(),
+    ];
+
+    State {
+        music,
+        current: None,
+        duration: 2.0,
+    }
+}
+
+fn fade_controls(ui: &mut Ui, app: &mut App, state: &mut State) {
+    ui.label("Fade duration");
+    ui.add(egui::Slider::new(&mut state.duration, 0.0..=5.0).suffix("s"));
+
+    // The fades run on the audio thread, so they are smooth even with a low frame rate
+    match state.current.take() {
+        None => {
+            if ui.button("Fade in").clicked() {
+                let sound = app.audio.play_sound(&state.music[0], 1.0, true);
+                app.audio.fade_in(&sound, state.duration);
+                state.current = Some((0, sound));
+            }
+        }
+        Some((index, sound)) => {
+            if ui.button("Crossfade").clicked() {
+                let next = (index + 1) % state.music.len();
+                let next_sound = app.audio.play_sound(&state.music[next], 1.0, true);
+                app.audio.crossfade(&sound, &next_sound, state.duration);
+                state.current = Some((next, next_sound));
+            } else if ui.button("Fade to half").clicked() {
+                app.audio.fade_to(&sound, 0.5, state.duration);
+                state.current = Some((index, sound));
+            } else if ui.button("Fade out and stop").clicked() {
+                app.audio.fade_out_and_stop(&sound, state.duration);
+            } else {
+                state.current = Some((index, sound));
+            }
+        }
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| fade_controls(ui, app, state));
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
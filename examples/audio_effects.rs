@@ -0,0 +1,108 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    music: AudioSource,
+    sound: Option<Sound>,
+    underwater: bool,
+    cutoff: f32,
+    room: bool,
+    room_size: f32,
+    echo: bool,
+    limiter: bool,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 400))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let music = app
+        .audio
+        .create_source(include_bytes!("assets/jingles_NES00.ogg"))
+        .unwrap();
+
+    State {
+        music,
+        sound: None,
+        underwater: false,
+        cutoff: 500.0,
+        room: false,
+        room_size: 0.7,
+        echo: false,
+        limiter: false,
+    }
+}
+
+// the effects keep their state when only the parameters change, so it can be called every frame
+fn sound_effects(state: &State) -> Vec<AudioEffect> {
+    let mut effects = vec![];
+    if state.underwater {
+        effects.push(AudioEffect::low_pass(state.cutoff));
+    }
+
+    if state.echo {
+        effects.push(AudioEffect::delay(0.3, 0.4));
+    }
+
+    if state.room {
+        effects.push(AudioEffect::reverb(state.room_size));
+    }
+
+    effects
+}
+
+fn effect_controls(ui: &mut Ui, app: &mut App, state: &mut State) {
+    let Some(sound) = &state.sound else {
+        if ui.button("Play").clicked() {
+            state.sound = Some(app.audio.play_sound(&state.music, 1.0, true));
+        }
+        return;
+    };
+
+    ui.checkbox(&mut state.underwater, "Underwater");
+    ui.add(egui::Slider::new(&mut state.cutoff, 100.0..=5000.0).suffix("Hz"));
+
+    ui.checkbox(&mut state.room, "Room");
+    ui.add(egui::Slider::new(&mut state.room_size, 0.0..=1.0));
+
+    ui.checkbox(&mut state.echo, "Echo");
+
+    let effects = sound_effects(state);
+    if effects != app.audio.effects(sound) {
+        app.audio.set_effects(sound, &effects);
+    }
+
+    // The global effects are applied to the mix of all the sounds
+    if ui
+        .checkbox(&mut state.limiter, "Limit the global mix to -12dB")
+        .changed()
+    {
+        let effects = if state.limiter {
+            vec![AudioEffect::limiter(-12.0)]
+        } else {
+            vec![]
+        };
+        app.audio.set_global_effects(&effects);
+    }
+
+    if ui.button("Stop").clicked() {
+        app.audio.stop(sound);
+        state.sound = None;
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| effect_controls(ui, app, state));
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
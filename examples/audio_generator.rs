@@ -0,0 +1,99 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+/// Sine wave that fades out, a new copy plays each time the source is played
+#[derive(Clone)]
+struct Tone {
+    frequency: f32,
+    phase: f32,
+    level: f32,
+}
+
+impl AudioGenerator for Tone {
+    fn generate(&mut self, sample_rate: u32, frames: &mut [[f32; 2]]) -> bool {
+        let step = self.frequency / sample_rate as f32;
+        let decay = 1.0 / sample_rate as f32;
+        frames.iter_mut().for_each(|frame| {
+            let value = (self.phase * std::f32::consts::TAU).sin() * self.level;
+            *frame = [value, value];
+            self.phase = (self.phase + step).fract();
+            self.level = (self.level - decay).max(0.0);
+        });
+
+        self.level > 0.0
+    }
+}
+
+#[derive(AppState)]
+struct State {
+    tones: Vec<AudioSource>,
+    noise: AudioSource,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 300))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let tones = [261.63, 329.63, 392.0]
+        .into_iter()
+        .map(|frequency| {
+            app.audio
+                .create_source_from_generator(Tone {
+                    frequency,
+                    phase: 0.0,
+                    level: 0.5,
+                })
+                .unwrap()
+        })
+        .collect();
+
+    // One second of mono white noise created from raw samples
+    let sample_rate = 44100;
+    let mut seed = 1u32;
+    let samples: Vec<f32> = (0..sample_rate)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed as f32 / u32::MAX as f32 - 0.5) * 0.2
+        })
+        .collect();
+    let noise = app
+        .audio
+        .create_source_from_pcm(&samples, 1, sample_rate)
+        .unwrap();
+
+    State { tones, noise }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.label("Generated tones");
+            ui.horizontal(|ui| {
+                ["C", "E", "G"]
+                    .iter()
+                    .zip(state.tones.iter())
+                    .for_each(|(name, tone)| {
+                        if ui.button(*name).clicked() {
+                            app.audio.play_sound(tone, 1.0, false);
+                        }
+                    });
+            });
+
+            ui.label("PCM samples");
+            if ui.button("Noise").clicked() {
+                app.audio.play_sound(&state.noise, 1.0, false);
+            }
+        });
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
@@ -0,0 +1,88 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    music: AudioSource,
+    sound: Option<Sound>,
+    rate: f32,
+    intro: bool,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 300))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let music = app
+        .audio
+        .create_source(include_bytes!("assets/jingles_NES00.ogg"))
+        .unwrap();
+
+    State {
+        music,
+        sound: None,
+        rate: 1.0,
+        intro: false,
+    }
+}
+
+fn playback_controls(ui: &mut Ui, app: &mut App, state: &mut State) {
+    let Some(sound) = &state.sound else {
+        if ui.button("Play").clicked() {
+            state.sound = Some(app.audio.play_sound(&state.music, 1.0, true));
+            state.rate = 1.0;
+        }
+        return;
+    };
+
+    // Drag the slider to seek
+    let duration = app.audio.duration(sound).unwrap_or(0.0);
+    let mut position = app.audio.position(sound);
+    ui.label("Position");
+    if ui
+        .add(egui::Slider::new(&mut position, 0.0..=duration).suffix("s"))
+        .changed()
+    {
+        app.audio.seek(sound, position);
+    }
+
+    // The playback rate changes the speed and the pitch
+    ui.label("Playback rate");
+    if ui
+        .add(egui::Slider::new(&mut state.rate, 0.25..=2.0))
+        .changed()
+    {
+        app.audio.set_playback_rate(sound, state.rate);
+    }
+
+    // Plays the first second once like an intro, and then repeats the rest
+    if ui
+        .checkbox(&mut state.intro, "Repeat after the first second")
+        .changed()
+    {
+        let start = if state.intro { 1.0 } else { 0.0 };
+        app.audio
+            .set_loop_region(sound, Some(LoopRegion::new(start, None)));
+    }
+
+    if ui.button("Stop").clicked() {
+        app.audio.stop(sound);
+        state.sound = None;
+        state.intro = false;
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| playback_controls(ui, app, state));
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
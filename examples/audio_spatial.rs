@@ -0,0 +1,105 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+    click: AudioSource,
+    emitter: Sound,
+    angle: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn spatial_options(x: f32, y: f32) -> SpatialOptions {
+    // the volume decreases from 50 to 400 pixels
+    SpatialOptions {
+        model: DistanceModel::Linear,
+        ref_distance: 50.0,
+        max_distance: 400.0,
+        ..SpatialOptions::new(x, y, 0.0)
+    }
+}
+
+fn setup(app: &mut App, gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+
+    let music = app
+        .audio
+        .create_source(include_bytes!("assets/jingles_NES00.ogg"))
+        .unwrap();
+
+    let click = app
+        .audio
+        .create_source(include_bytes!("assets/click1.ogg"))
+        .unwrap();
+
+    // The emitter moves around the listener placed on the mouse position
+    let emitter = app
+        .audio
+        .play_spatial_sound(&music, spatial_options(400.0, 300.0), 1.0, true);
+
+    State {
+        font,
+        click,
+        emitter,
+        angle: 0.0,
+    }
+}
+
+fn emitter_position(angle: f32) -> (f32, f32) {
+    (400.0 + angle.cos() * 250.0, 300.0 + angle.sin() * 150.0)
+}
+
+fn update(app: &mut App, state: &mut State) {
+    state.angle += app.timer.delta_f32() * 0.5;
+
+    let (x, y) = emitter_position(state.angle);
+    app.audio.set_sound_position(&state.emitter, x, y, 0.0);
+
+    let (mx, my) = app.mouse.position();
+    app.audio.set_listener_position(mx, my, 0.0);
+
+    if app.mouse.was_pressed(MouseButton::Left) {
+        app.audio
+            .play_spatial_sound(&state.click, spatial_options(400.0, 300.0), 1.0, false);
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    let (x, y) = emitter_position(state.angle);
+    draw.circle(400.0).position(x, y).alpha(0.1);
+    draw.circle(10.0).position(x, y).color(Color::ORANGE);
+
+    draw.circle(10.0).position(400.0, 300.0).color(Color::GREEN);
+
+    let (mx, my) = app.mouse.position();
+    draw.triangle(
+        (mx, my - 10.0),
+        (mx - 10.0, my + 10.0),
+        (mx + 10.0, my + 10.0),
+    )
+    .color(Color::AQUA);
+
+    draw.text(
+        &state.font,
+        "Move the mouse to listen, click to play a sound on the center",
+    )
+    .position(400.0, 20.0)
+    .size(20.0)
+    .h_align_center();
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,64 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+    music: AudioSource,
+    sound: Option<Sound>,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App, gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+
+    // The music is decoded on a background thread while it plays instead of at once
+    let music = app
+        .audio
+        .create_streaming_source(include_bytes!("assets/jingles_NES00.ogg"))
+        .unwrap();
+
+    State {
+        font,
+        music,
+        sound: None,
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    if app.keyboard.was_pressed(KeyCode::Space) {
+        match state.sound.take() {
+            Some(sound) => app.audio.stop(&sound),
+            None => state.sound = Some(app.audio.play_sound(&state.music, 1.0, true)),
+        }
+    }
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    let text = if state.sound.is_some() {
+        "Streaming music... Press SPACE to stop"
+    } else {
+        "Press SPACE to play the music"
+    };
+
+    draw.text(&state.font, text)
+        .position(400.0, 300.0)
+        .size(24.0)
+        .h_align_center()
+        .v_align_middle();
+
+    gfx.render(&draw);
+}
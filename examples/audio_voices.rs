@@ -0,0 +1,85 @@
+use notan::egui::{self, *};
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    click: AudioSource,
+    max_voices: usize,
+    stealing: VoiceStealing,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(EguiConfig)
+        .add_config(WindowConfig::default().set_size(300, 300))
+        .draw(draw)
+        .build()
+}
+
+fn setup(app: &mut App) -> State {
+    let click = app
+        .audio
+        .create_source(include_bytes!("assets/click1.ogg"))
+        .unwrap();
+
+    // The click is played once per frame even if it's triggered many times
+    app.audio.set_source_voices(
+        &click,
+        SourceVoices {
+            cooldown: 1,
+            ..Default::default()
+        },
+    );
+    app.audio.set_max_voices(Some(4));
+
+    State {
+        click,
+        max_voices: 4,
+        stealing: VoiceStealing::Oldest,
+    }
+}
+
+fn voice_controls(ui: &mut Ui, app: &mut App, state: &mut State) {
+    ui.label("Max voices");
+    if ui
+        .add(egui::Slider::new(&mut state.max_voices, 1..=16))
+        .changed()
+    {
+        app.audio.set_max_voices(Some(state.max_voices));
+    }
+
+    egui::ComboBox::from_label("Stealing")
+        .selected_text(format!("{:?}", state.stealing))
+        .show_ui(ui, |ui| {
+            [
+                VoiceStealing::Oldest,
+                VoiceStealing::Quietest,
+                VoiceStealing::LowestPriority,
+                VoiceStealing::Reject,
+            ]
+            .into_iter()
+            .for_each(|stealing| {
+                ui.selectable_value(&mut state.stealing, stealing, format!("{stealing:?}"));
+            });
+        });
+    app.audio.set_voice_stealing(state.stealing);
+
+    // Hold the button to trigger the click every frame
+    if ui.button("Click").is_pointer_button_down_on() {
+        (0..10).for_each(|_| {
+            app.audio.play_sound(&state.click, 1.0, false);
+        });
+    }
+
+    ui.label(format!("Active voices: {}", app.audio.active_voices()));
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins, state: &mut State) {
+    let mut output = plugins.egui(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| voice_controls(ui, app, state));
+    });
+
+    output.clear_color(Color::GRAY);
+    gfx.render(&output);
+}
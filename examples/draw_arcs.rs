@@ -37,14 +37,14 @@ fn draw(gfx: &mut Graphics) {
     gfx.render(&draw);
 }
 
-fn draw_arc(
-    path: &mut Path,
+fn draw_arc<'a, 'b>(
+    path: &'a mut Path<'b>,
     center_x: f32,
     center_y: f32,
     radius: f32,
     start_angle: f32,
     end_angle: f32,
-) -> &mut Path {
+) -> &'a mut Path<'b> {
     let start_angle = start_angle + 270.0;
     let end_angle = end_angle + 270.0;
 
@@ -59,14 +59,14 @@ fn draw_arc(
     path
 }
 
-fn draw_circle_section(
-    path: &mut Path,
+fn draw_circle_section<'a, 'b>(
+    path: &'a mut Path<'b>,
     center_x: f32,
     center_y: f32,
     radius: f32,
     start_angle: f32,
     end_angle: f32,
-) -> &mut Path {
+) -> &'a mut Path<'b> {
     let start_angle = start_angle + 270.0;
     let end_angle = end_angle + 270.0;
 
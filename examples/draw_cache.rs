@@ -0,0 +1,64 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+const TILE_SIZE: f32 = 20.0;
+
+#[derive(AppState)]
+struct State {
+    cache: DrawCache,
+    count: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(init)
+        .add_config(DrawConfig)
+        .draw(draw)
+        .build()
+}
+
+fn init(gfx: &mut Graphics) -> State {
+    // Record the static geometry once
+    let mut draw = gfx.create_draw();
+    for y in 0..20 {
+        for x in 0..30 {
+            let color = if (x + y) % 2 == 0 {
+                Color::from_rgb(0.2, 0.3, 0.4)
+            } else {
+                Color::from_rgb(0.3, 0.4, 0.5)
+            };
+
+            draw.rect(
+                (x as f32 * TILE_SIZE, y as f32 * TILE_SIZE),
+                (TILE_SIZE, TILE_SIZE),
+            )
+            .corner_radius(4.0)
+            .color(color);
+        }
+    }
+
+    // Upload it to the GPU
+    let cache = gfx.create_draw_cache(&draw).unwrap();
+
+    State { cache, count: 0.0 }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    // Draw the cached geometry without tessellating it again
+    draw.cached(&state.cache)
+        .translate(100.0, 100.0)
+        .alpha(state.count.sin().abs());
+
+    draw.cached(&state.cache)
+        .rotate_degrees_from((400.0, 300.0), state.count * 10.0)
+        .translate(100.0, 100.0)
+        .color(Color::ORANGE)
+        .alpha(0.3);
+
+    gfx.render(&draw);
+
+    state.count += app.timer.delta_f32();
+}
@@ -0,0 +1,138 @@
+use notan::draw::*;
+use notan::math::{vec2, Rect, Vec2};
+use notan::prelude::*;
+
+const WORLD_SIZE: f32 = 2000.0;
+const TILE_SIZE: f32 = 100.0;
+
+#[derive(AppState)]
+struct State {
+    camera: Camera2D,
+    player: Vec2,
+    click: Option<Vec2>,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    let win_config = WindowConfig::default().set_resizable(true);
+
+    notan::init_with(init)
+        .add_config(win_config)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn init() -> State {
+    // The camera will show 800x600 units of the world no matter the window size
+    let mut camera = Camera2D::new(800.0, 600.0);
+    camera.set_scale_mode(ScaleMode::Fit);
+    camera.set_follow_smoothing(0.3);
+    camera.set_bounds(Some(Rect {
+        x: 0.0,
+        y: 0.0,
+        width: WORLD_SIZE,
+        height: WORLD_SIZE,
+    }));
+
+    State {
+        camera,
+        player: vec2(WORLD_SIZE * 0.5, WORLD_SIZE * 0.5),
+        click: None,
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    let delta = app.timer.delta_f32();
+
+    let mut dir = Vec2::ZERO;
+    if app.keyboard.is_down(KeyCode::W) {
+        dir.y -= 1.0;
+    }
+    if app.keyboard.is_down(KeyCode::S) {
+        dir.y += 1.0;
+    }
+    if app.keyboard.is_down(KeyCode::A) {
+        dir.x -= 1.0;
+    }
+    if app.keyboard.is_down(KeyCode::D) {
+        dir.x += 1.0;
+    }
+
+    let pos = state.player + dir.normalize_or_zero() * 400.0 * delta;
+    state.player = pos.clamp(Vec2::ZERO, Vec2::splat(WORLD_SIZE));
+
+    let zoom = state.camera.zoom() + app.mouse.wheel_delta.y * 0.001;
+    state.camera.set_zoom(zoom.clamp(0.5, 3.0));
+
+    if app.keyboard.was_pressed(KeyCode::Space) {
+        state.camera.shake(15.0, 0.5);
+    }
+
+    // The mouse position is converted to world coordinates
+    if app.mouse.was_pressed(MouseButton::Left) {
+        let (x, y) = app.mouse.position();
+        state.click = Some(state.camera.screen_to_world(x, y));
+    }
+
+    let (width, height) = app.window().size();
+    state.camera.set_screen_size(width as _, height as _);
+    state.camera.follow(state.player.x, state.player.y);
+    state.camera.update(delta);
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+    draw.set_camera(&state.camera);
+
+    // world
+    let tiles = (WORLD_SIZE / TILE_SIZE) as usize;
+    for y in 0..tiles {
+        for x in 0..tiles {
+            let color = if (x + y) % 2 == 0 {
+                Color::from_rgb(0.2, 0.3, 0.4)
+            } else {
+                Color::from_rgb(0.3, 0.4, 0.5)
+            };
+
+            draw.rect(
+                (x as f32 * TILE_SIZE, y as f32 * TILE_SIZE),
+                (TILE_SIZE, TILE_SIZE),
+            )
+            .color(color);
+        }
+    }
+
+    if let Some(click) = state.click {
+        draw.circle(10.0)
+            .position(click.x, click.y)
+            .color(Color::RED);
+    }
+
+    draw.circle(20.0)
+        .position(state.player.x, state.player.y)
+        .color(Color::ORANGE);
+
+    // the content outside the viewport is covered with bars
+    let (width, height) = gfx.size();
+    let (width, height) = (width as f32, height as f32);
+    let vp = state.camera.viewport();
+
+    let mut bars = gfx.create_draw();
+    [
+        (0.0, 0.0, width, vp.y),
+        (0.0, vp.max_y(), width, height - vp.max_y()),
+        (0.0, 0.0, vp.x, height),
+        (vp.max_x(), 0.0, width - vp.max_x(), height),
+    ]
+    .into_iter()
+    .filter(|(_, _, w, h)| *w > 0.0 && *h > 0.0)
+    .for_each(|(x, y, w, h)| {
+        bars.rect((x, y), (w, h)).color(Color::BLACK);
+    });
+
+    gfx.render(&draw);
+    gfx.render(&bars);
+}
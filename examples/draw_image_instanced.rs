@@ -0,0 +1,91 @@
+use notan::draw::*;
+use notan::math::{vec2, Vec2};
+use notan::prelude::*;
+
+const COUNT: usize = 10000;
+
+#[derive(AppState)]
+struct State {
+    texture: Texture,
+    instances: ImageInstances,
+    data: Vec<ImageInstance>,
+    speeds: Vec<Vec2>,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(init)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn init(gfx: &mut Graphics) -> State {
+    let texture = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/bunny.png"))
+        .build()
+        .unwrap();
+
+    let mut rng = Random::default();
+    let data = (0..COUNT)
+        .map(|_| ImageInstance {
+            rotation: rng.gen_range(0.0..std::f32::consts::TAU),
+            color: Color::from_rgb(rng.gen(), rng.gen(), rng.gen()),
+            ..ImageInstance::new(rng.gen_range(0.0..800.0), rng.gen_range(0.0..600.0))
+        })
+        .collect::<Vec<_>>();
+
+    let speeds = (0..COUNT)
+        .map(|_| vec2(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)))
+        .collect();
+
+    // Upload the per instance data to the GPU
+    let instances = gfx.create_image_instances(&texture, &data).unwrap();
+
+    State {
+        texture,
+        instances,
+        data,
+        speeds,
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    let delta = app.timer.delta_f32();
+    state
+        .data
+        .iter_mut()
+        .zip(state.speeds.iter_mut())
+        .for_each(|(instance, speed)| {
+            instance.position += *speed * delta;
+            instance.rotation += delta;
+
+            if instance.position.x < 0.0 || instance.position.x > 800.0 {
+                speed.x *= -1.0;
+            }
+
+            if instance.position.y < 0.0 || instance.position.y > 600.0 {
+                speed.y *= -1.0;
+            }
+        });
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    // Only the instance buffer is updated, no vertices are generated
+    state.instances.set_instances(gfx, &state.data).unwrap();
+
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    // Draw all the bunnies with one draw call, it keeps the draw order with other elements
+    draw.rect((300.0, 200.0), (200.0, 200.0))
+        .color(Color::ORANGE);
+    draw.instanced_image(&state.instances);
+    draw.image(&state.texture)
+        .position(400.0, 300.0)
+        .scale(4.0, 4.0);
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,79 @@
+use notan::draw::*;
+use notan::math::{vec2, Mat3, Vec2};
+use notan::prelude::*;
+
+#[derive(AppState, Default)]
+struct State {
+    scroll: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(State::default)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn update(app: &mut App, state: &mut State) {
+    state.scroll = (state.scroll + app.timer.delta_f32() * 40.0) % 200.0;
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    // Circular "window" used as mask
+    let mut window = gfx.create_draw();
+    window.circle(260.0).position(400.0, 300.0);
+
+    // Hole inside the window used as inverted mask
+    let mut hole = gfx.create_draw();
+    hole.star(5, 70.0, 35.0).position(400.0, 300.0);
+
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    // Only the pixels inside the circle are visible
+    draw.push_mask(&window);
+    draw.rect((0.0, 0.0), (800.0, 600.0)).color(Color::GRAY);
+
+    // A panel clipped using scissors
+    draw.push_clip(200.0, 150.0, 400.0, 300.0);
+    draw.rect((200.0, 150.0), (400.0, 300.0))
+        .color(Color::from_rgb(0.2, 0.3, 0.5));
+
+    // A scroll view inside the panel, the rows are clipped by the panel and the window
+    draw.push_clip(220.0, 170.0, 360.0, 260.0);
+    (0..12).for_each(|i| {
+        let y = 170.0 + i as f32 * 40.0 - state.scroll;
+        let color = if i % 2 == 0 {
+            Color::ORANGE
+        } else {
+            Color::YELLOW
+        };
+        draw.rect((220.0, y), (360.0, 36.0)).color(color);
+    });
+    draw.pop_clip();
+    draw.pop_clip();
+
+    // Everything outside the star is visible (and inside the window)
+    draw.push_inverted_mask(&hole);
+    draw.circle(120.0)
+        .position(400.0, 300.0)
+        .color(Color::RED.with_alpha(0.6));
+    draw.pop_mask();
+
+    draw.pop_mask();
+
+    // A rotated clip fallbacks to a stencil mask
+    draw.transform().push(Mat3::from_scale_angle_translation(
+        Vec2::ONE,
+        0.3,
+        vec2(650.0, 480.0),
+    ));
+    draw.push_clip(-50.0, -50.0, 100.0, 100.0);
+    draw.circle(70.0).color(Color::GREEN);
+    draw.pop_clip();
+    draw.transform().pop();
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,137 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+//language=glsl
+const DISSOLVE_FRAGMENT: ShaderSource = notan::fragment_shader! {
+    r#"
+    #version 450
+    precision mediump float;
+
+    layout(location = 0) in vec2 v_uvs;
+    layout(location = 1) in vec4 v_color;
+
+    layout(binding = 0) uniform sampler2D u_texture;
+    layout(binding = 1) uniform sampler2D u_noise;
+    layout(set = 0, binding = 2) uniform Dissolve {
+        float u_progress;
+    };
+
+    layout(location = 0) out vec4 color;
+
+    void main() {
+        float noise = texture(u_noise, v_uvs).r;
+        if (noise < u_progress) {
+            discard;
+        }
+
+        vec4 tex_color = texture(u_texture, v_uvs) * v_color;
+        float edge = step(noise, u_progress + 0.05);
+        color = mix(tex_color, vec4(1.0, 0.5, 0.0, tex_color.a), edge);
+    }
+"#
+};
+
+//language=glsl
+const PULSE_FRAGMENT: ShaderSource = notan::fragment_shader! {
+    r#"
+    #version 450
+    precision mediump float;
+
+    layout(location = 0) in vec4 v_color;
+
+    layout(set = 0, binding = 1) uniform Pulse {
+        float u_time;
+    };
+
+    layout(location = 0) out vec4 color;
+
+    void main() {
+        float intensity = 0.6 + sin(u_time * 4.0) * 0.4;
+        color = vec4(v_color.rgb * intensity, v_color.a);
+    }
+"#
+};
+
+#[derive(AppState)]
+struct State {
+    texture: Texture,
+    dissolve: Material,
+    pulse: Material,
+    time: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(init)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn init(gfx: &mut Graphics) -> State {
+    let texture = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/ferris.png"))
+        .build()
+        .unwrap();
+
+    let noise = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/pattern.png"))
+        .build()
+        .unwrap();
+
+    let dissolve = gfx
+        .create_image_material(&DISSOLVE_FRAGMENT)
+        .with_texture(1, "u_noise", &noise)
+        .with_uniforms(2, "Dissolve", &[0.0])
+        .build()
+        .unwrap();
+
+    let pulse = gfx
+        .create_shape_material(&PULSE_FRAGMENT)
+        .with_uniforms(1, "Pulse", &[0.0])
+        .build()
+        .unwrap();
+
+    State {
+        texture,
+        dissolve,
+        pulse,
+        time: 0.0,
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    state.time += app.timer.delta_f32();
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let progress = (state.time * 0.5).sin() * 0.5 + 0.5;
+    state.dissolve.set_uniforms(gfx, 2, &[progress]);
+    state.pulse.set_uniforms(gfx, 1, &[state.time]);
+
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    // Image without a material
+    draw.image(&state.texture).position(10.0, 200.0);
+
+    // Image using the dissolve material
+    draw.image(&state.texture)
+        .position(10.0 + state.texture.width() + 40.0, 200.0)
+        .material(&state.dissolve);
+
+    // Shapes using the same material are batched together
+    draw.circle(40.0)
+        .position(100.0, 100.0)
+        .color(Color::ORANGE)
+        .material(&state.pulse);
+
+    draw.rect((200.0, 60.0), (160.0, 80.0))
+        .color(Color::AQUA)
+        .material(&state.pulse);
+
+    gfx.render(&draw);
+}
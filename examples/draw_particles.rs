@@ -0,0 +1,107 @@
+use notan::draw::*;
+use notan::math::vec2;
+use notan::prelude::*;
+use std::f32::consts::PI;
+
+// The fire follows the mouse, click to spawn an explosion of ferris
+#[derive(AppState)]
+struct State {
+    fire: ParticleEmitter,
+    explosions: Vec<ParticleEmitter>,
+    texture: Texture,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let texture = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/ferris.png"))
+        .build()
+        .unwrap();
+
+    let fire = ParticleEmitter::new(ParticleConfig {
+        shape: EmitterShape::Circle { radius: 10.0 },
+        rate: 200.0,
+        lifetime: (0.6, 1.2),
+        direction: -PI * 0.5,
+        spread: PI * 0.3,
+        speed: (40.0, 120.0),
+        gravity: vec2(0.0, -60.0),
+        size: (10.0, 24.0),
+        size_over_life: Curve::linear(1.0, 0.2),
+        angular_velocity: (-2.0, 2.0),
+        color_over_life: Curve::new(&[
+            (0.0, Color::YELLOW),
+            (0.4, Color::ORANGE),
+            (1.0, Color::RED),
+        ]),
+        blend: ParticleBlend::Add,
+        ..Default::default()
+    });
+
+    State {
+        fire,
+        explosions: vec![],
+        texture,
+    }
+}
+
+fn explosion(x: f32, y: f32) -> ParticleEmitter {
+    let mut emitter = ParticleEmitter::new(ParticleConfig {
+        rate: 0.0,
+        duration: Some(0.1),
+        bursts: vec![Burst {
+            time: 0.0,
+            count: 40,
+            interval: None,
+        }],
+        lifetime: (1.0, 1.5),
+        speed: (100.0, 300.0),
+        gravity: vec2(0.0, 300.0),
+        drag: 1.0,
+        size: (20.0, 40.0),
+        rotation: (0.0, PI * 2.0),
+        angular_velocity: (-4.0, 4.0),
+        alpha_over_life: Curve::new(&[(0.0, 1.0), (0.7, 1.0), (1.0, 0.0)]),
+        ..Default::default()
+    });
+
+    emitter.set_position(x, y);
+    emitter
+}
+
+fn update(app: &mut App, state: &mut State) {
+    let delta = app.timer.delta_f32();
+    let (x, y) = app.mouse.position();
+
+    if app.mouse.left_was_pressed() {
+        state.explosions.push(explosion(x, y));
+    }
+
+    state.fire.set_position(x, y);
+    state.fire.update(delta);
+
+    state.explosions.iter_mut().for_each(|e| e.update(delta));
+    state.explosions.retain(|e| !e.is_finished());
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    draw.particles(&state.fire);
+
+    state.explosions.iter().for_each(|e| {
+        draw.particles(e).texture(&state.texture);
+    });
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,79 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    offset: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(|| State { offset: 0.0 })
+        .add_config(DrawConfig)
+        .draw(draw)
+        .build()
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    let points = [
+        (60.0, 200.0),
+        (200.0, 60.0),
+        (340.0, 200.0),
+        (480.0, 60.0),
+        (620.0, 200.0),
+        (740.0, 100.0),
+    ];
+
+    // Per vertex colors with round joins
+    draw.polyline(&points)
+        .width(12.0)
+        .round_join()
+        .round_cap()
+        .color_vertex(&[
+            Color::RED,
+            Color::ORANGE,
+            Color::YELLOW,
+            Color::GREEN,
+            Color::BLUE,
+            Color::PURPLE,
+        ]);
+
+    // Animated dashes with bevel joins and an arrow head
+    draw.polyline(&points)
+        .width(6.0)
+        .bevel_join()
+        .dash(&[20.0, 10.0])
+        .dash_offset(-state.offset)
+        .end_arrow(24.0)
+        .color(Color::WHITE)
+        .translate(0.0, 180.0);
+
+    // Closed dotted polyline
+    draw.polyline(&[(100.0, 450.0), (300.0, 450.0), (200.0, 560.0)])
+        .closed()
+        .width(8.0)
+        .dotted(16.0)
+        .color(Color::AQUA);
+
+    // Dashed path with curves
+    draw.path()
+        .move_to(380.0, 520.0)
+        .cubic_bezier_to((440.0, 380.0), (620.0, 640.0), (700.0, 460.0))
+        .stroke(4.0)
+        .dash(&[12.0, 6.0, 2.0, 6.0])
+        .color(Color::MAGENTA);
+
+    // Lines with arrows in both sides
+    draw.line((400.0, 580.0), (760.0, 580.0))
+        .width(2.0)
+        .start_arrow(12.0)
+        .end_arrow(12.0)
+        .color(Color::SILVER);
+
+    gfx.render(&draw);
+
+    state.offset += app.timer.delta_f32() * 30.0;
+}
@@ -0,0 +1,99 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+const DIALOGUE: &str = "[size=30][b]Ferris:[/b][/size] Hello traveler! \
+    I found [color=#ffa500]a friendly crab [icon=ferris][/color] near the [wave]old river[/wave]. \
+    Be careful, the cave is [shake][color=red]very dangerous[/color][/shake] at night... \
+    [fx=fade][color=#8cf]Press space to restart[/color][/fx]";
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+    pixel_font: Font,
+    icon: Texture,
+    time: f32,
+    reveal: f32,
+    total: usize,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+
+    let pixel_font = gfx
+        .create_font(include_bytes!("assets/kenney_pixel-webfont.ttf"))
+        .unwrap();
+
+    let icon = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/ferris.png"))
+        .build()
+        .unwrap();
+
+    State {
+        font,
+        pixel_font,
+        icon,
+        time: 0.0,
+        reveal: 0.0,
+        total: markup_glyphs_count(DIALOGUE),
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    let delta = app.timer.delta_f32();
+    state.time += delta;
+
+    // typewriter effect, 30 glyphs per second
+    state.reveal = (state.reveal + delta * 30.0).min(state.total as _);
+    if app.keyboard.was_pressed(KeyCode::Space) {
+        state.reveal = 0.0;
+    }
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    draw.rect((40.0, 340.0), (720.0, 220.0))
+        .color(Color::from_rgb(0.1, 0.1, 0.2));
+
+    // custom effect used by the tag [fx=fade]
+    let time = state.time;
+    let fade = move |glyph: &mut TextGlyph| {
+        if glyph.effect == Some("fade") {
+            glyph.alpha *= (time * 3.0).sin() * 0.4 + 0.6;
+        }
+    };
+
+    draw.rich_text(&state.font, DIALOGUE)
+        .bold_font(&state.pixel_font)
+        .add_icon("ferris", &state.icon)
+        .position(60.0, 360.0)
+        .size(22.0)
+        .max_width(680.0)
+        .time(state.time)
+        .reveal(state.reveal as _)
+        .on_glyph(&fade);
+
+    draw.rich_text(
+        &state.font,
+        "[color=yellow]Rich[/color] [i]text[/i] [size=40]markup[/]",
+    )
+    .position(400.0, 150.0)
+    .size(30.0)
+    .h_align_center()
+    .v_align_middle();
+
+    gfx.render(&draw);
+}
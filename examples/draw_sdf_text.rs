@@ -0,0 +1,98 @@
+use notan::draw::*;
+use notan::math::vec2;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    font: SdfFont,
+    outline: SdfStyle,
+    glow: SdfStyle,
+    shadow: SdfStyle,
+    time: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_sdf_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .with_size(48.0)
+        .with_spread(8.0)
+        .build()
+        .unwrap();
+
+    let outline = gfx
+        .create_sdf_style()
+        .with_outline(Color::ORANGE, 0.15)
+        .build()
+        .unwrap();
+
+    let glow = gfx
+        .create_sdf_style()
+        .with_glow(Color::from_rgb(0.2, 0.8, 1.0), 0.4)
+        .build()
+        .unwrap();
+
+    let shadow = gfx
+        .create_sdf_style()
+        .with_shadow(Color::BLACK.with_alpha(0.7), vec2(4.0, 4.0), 0.1)
+        .build()
+        .unwrap();
+
+    State {
+        font,
+        outline,
+        glow,
+        shadow,
+        time: 0.0,
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    state.time += app.timer.delta_f32();
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::from_rgb(0.2, 0.2, 0.3));
+
+    // the text keeps sharp edges at any scale
+    let scale = 1.0 + (state.time * 1.5).sin() * 0.5;
+    draw.sdf_text(&state.font, "Sharp at any size")
+        .position(400.0, 100.0)
+        .size(40.0)
+        .h_align_center()
+        .v_align_middle()
+        .scale_from((400.0, 100.0), (scale, scale));
+
+    draw.sdf_text(&state.font, "Outline")
+        .position(400.0, 250.0)
+        .size(64.0)
+        .color(Color::BLACK)
+        .style(&state.outline)
+        .h_align_center()
+        .v_align_middle();
+
+    draw.sdf_text(&state.font, "Glow")
+        .position(400.0, 360.0)
+        .size(64.0)
+        .style(&state.glow)
+        .h_align_center()
+        .v_align_middle();
+
+    draw.sdf_text(&state.font, "Drop shadow with a long text that wraps")
+        .position(400.0, 440.0)
+        .size(40.0)
+        .max_width(500.0)
+        .style(&state.shadow)
+        .h_align_center();
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,70 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+    time: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+    State { font, time: 0.0 }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    state.time += app.timer.delta_f32();
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::from_rgb(0.3, 0.5, 0.7));
+
+    draw.text(&state.font, "Outline")
+        .position(400.0, 100.0)
+        .size(60.0)
+        .color(Color::WHITE)
+        .outline(Color::BLACK, 3.0)
+        .h_align_center()
+        .v_align_middle();
+
+    draw.text(&state.font, "Drop Shadow")
+        .position(400.0, 220.0)
+        .size(60.0)
+        .color(Color::ORANGE)
+        .shadow(Color::BLACK.with_alpha(0.6), (4.0, 4.0))
+        .shadow_blur(3.0)
+        .h_align_center()
+        .v_align_middle();
+
+    // rotate and tint each glyph
+    let time = state.time;
+    let swing = move |glyph: &mut TextGlyph| {
+        let t = time * 3.0 + glyph.index as f32 * 0.5;
+        glyph.rotation = t.sin() * 0.3;
+        glyph.offset.y = t.cos() * 6.0;
+        glyph.color = Color::from_rgb(0.8 + t.sin() * 0.2, 0.6 + t.cos() * 0.4, 0.4);
+    };
+
+    draw.text(&state.font, "Per glyph effects")
+        .position(400.0, 380.0)
+        .size(50.0)
+        .outline(Color::BLACK, 2.0)
+        .shadow(Color::BLACK.with_alpha(0.4), (3.0, 5.0))
+        .on_glyph(&swing)
+        .h_align_center()
+        .v_align_middle();
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,58 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+// The pixel font doesn't have cyrillic characters, they are taken from the fallback font.
+// Enable the feature `system_fonts` to use `create_system_font_family` or `with_system_fallback`
+const TEXT: &str = "Hello! Привет! [b]Bold text[/b] with fallback: Жук";
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font_family(include_bytes!("assets/kenney_pixel-webfont.ttf"))
+        .with_bold(include_bytes!("assets/Ubuntu-B.ttf"))
+        .with_fallback(include_bytes!("assets/Ubuntu-B.ttf"))
+        .build()
+        .unwrap();
+
+    State { font }
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    draw.text(&state.font, "Regular: Привет мир")
+        .position(400.0, 150.0)
+        .size(40.0)
+        .h_align_center()
+        .v_align_middle();
+
+    draw.text(&state.font, "Bold: Привет мир")
+        .font_style(FontStyle::Bold)
+        .position(400.0, 250.0)
+        .size(40.0)
+        .color(Color::ORANGE)
+        .h_align_center()
+        .v_align_middle();
+
+    draw.rich_text(&state.font, TEXT)
+        .position(400.0, 400.0)
+        .size(30.0)
+        .max_width(600.0)
+        .h_align_center()
+        .v_align_middle();
+
+    gfx.render(&draw);
+}
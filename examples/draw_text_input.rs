@@ -0,0 +1,160 @@
+use notan::app::Event;
+use notan::draw::*;
+use notan::prelude::*;
+
+// Simple text field using the text layout to place the caret, select text and hit-test with the mouse
+#[derive(AppState)]
+struct State {
+    font: Font,
+    text: String,
+    caret: usize,
+    anchor: usize,
+    layout: TextLayout,
+}
+
+impl State {
+    fn selection(&self) -> std::ops::Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let range = self.selection();
+        if range.is_empty() {
+            return false;
+        }
+
+        self.text.replace_range(range.clone(), "");
+        self.caret = range.start;
+        self.anchor = range.start;
+        true
+    }
+
+    fn insert(&mut self, c: char) {
+        self.delete_selection();
+        self.text.insert(self.caret, c);
+        self.caret += c.len_utf8();
+        self.anchor = self.caret;
+    }
+
+    fn move_caret(&mut self, index: usize, select: bool) {
+        self.caret = index;
+        if !select {
+            self.anchor = index;
+        }
+    }
+
+    fn prev_index(&self) -> usize {
+        self.text[..self.caret]
+            .chars()
+            .next_back()
+            .map_or(0, |c| self.caret - c.len_utf8())
+    }
+
+    fn next_index(&self) -> usize {
+        self.text[self.caret..]
+            .chars()
+            .next()
+            .map_or(self.caret, |c| self.caret + c.len_utf8())
+    }
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .event(event)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+
+    let text =
+        String::from("Click, drag or use the arrows to move the caret.\nShift selects text.");
+    let caret = text.len();
+    State {
+        font,
+        text,
+        caret,
+        anchor: caret,
+        layout: TextLayout::default(),
+    }
+}
+
+fn event(state: &mut State, event: Event) {
+    match event {
+        Event::ReceivedCharacter('\r') => state.insert('\n'),
+        Event::ReceivedCharacter(c) if !c.is_control() => state.insert(c),
+        _ => {}
+    }
+}
+
+fn update(app: &mut App, state: &mut State) {
+    let select = app.keyboard.shift();
+
+    if app.keyboard.was_pressed(KeyCode::Back) && !state.delete_selection() {
+        let prev = state.prev_index();
+        state.text.replace_range(prev..state.caret, "");
+        state.move_caret(prev, false);
+    }
+
+    if app.keyboard.was_pressed(KeyCode::Left) {
+        state.move_caret(state.prev_index(), select);
+    }
+
+    if app.keyboard.was_pressed(KeyCode::Right) {
+        state.move_caret(state.next_index(), select);
+    }
+
+    // up and down use the caret's x on the previous or next line
+    let up = app.keyboard.was_pressed(KeyCode::Up);
+    let down = app.keyboard.was_pressed(KeyCode::Down);
+    if up || down {
+        let caret = state.layout.caret(state.caret);
+        let offset = if up { -1.0 } else { caret.height * 1.5 };
+        let y = caret.position.y + offset;
+        let index = state.layout.caret_at(caret.position.x, y);
+        state.move_caret(index, select);
+    }
+
+    let (x, y) = app.mouse.position();
+    if app.mouse.left_was_pressed() {
+        state.move_caret(state.layout.caret_at(x, y), select);
+    } else if app.mouse.left_is_down() {
+        state.move_caret(state.layout.caret_at(x, y), true);
+    }
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    draw.text(&state.font, &state.text)
+        .position(40.0, 40.0)
+        .size(30.0)
+        .max_width(720.0);
+
+    state.layout = draw.last_text_layout();
+
+    state
+        .layout
+        .selection_rects(state.selection())
+        .iter()
+        .for_each(|rect| {
+            draw.rect((rect.x, rect.y), (rect.width, rect.height))
+                .color(Color::AQUA)
+                .alpha(0.4);
+        });
+
+    let caret = state.layout.caret(state.caret);
+    let (x, y) = caret.position.into();
+    draw.line((x, y), (x, y + caret.height))
+        .width(2.0)
+        .color(Color::ORANGE);
+
+    gfx.render(&draw);
+}
@@ -0,0 +1,46 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+// Run with `cargo run --example draw_text_shaping --features shaping`
+// Texts are shaped with rustybuzz: kerning, ligatures, complex scripts and bidirectional text.
+// Use a font that contains the scripts you need (arabic, hebrew, devanagari...)
+const TEXT: &str = "AVATAR Wave Toffee \"office\" 1234.\nLines are broken following the unicode rules, even with long URLs like https://github.com/Nazariglez/notan";
+
+#[derive(AppState)]
+struct State {
+    font: Font,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(setup)
+        .add_config(DrawConfig)
+        .draw(draw)
+        .build()
+}
+
+fn setup(gfx: &mut Graphics) -> State {
+    let font = gfx
+        .create_font(include_bytes!("assets/Ubuntu-B.ttf"))
+        .unwrap();
+    State { font }
+}
+
+fn draw(gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    draw.text(&state.font, TEXT)
+        .position(400.0, 300.0)
+        .size(40.0)
+        .max_width(600.0)
+        .h_align_center()
+        .v_align_middle();
+
+    let bounds = draw.last_text_bounds();
+    draw.rect((bounds.x, bounds.y), (bounds.width, bounds.height))
+        .stroke(2.0)
+        .color(Color::ORANGE);
+
+    gfx.render(&draw);
+}
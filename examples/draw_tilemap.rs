@@ -0,0 +1,55 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+#[derive(AppState)]
+struct State {
+    map: TiledMap,
+    textures: Vec<Texture>,
+    time: f32,
+}
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    notan::init_with(init)
+        .add_config(DrawConfig)
+        .draw(draw)
+        .build()
+}
+
+fn init(gfx: &mut Graphics) -> State {
+    let map = TiledMap::from_bytes(include_bytes!("assets/tilemap.tmx")).unwrap();
+
+    // One texture per tileset, following the same order
+    let texture = gfx
+        .create_texture()
+        .from_image(include_bytes!("assets/pattern.png"))
+        .build()
+        .unwrap();
+
+    State {
+        map,
+        textures: vec![texture],
+        time: 0.0,
+    }
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, state: &mut State) {
+    let mut draw = gfx.create_draw();
+    draw.clear(Color::BLACK);
+
+    // Only the tiles inside the screen are drawn
+    draw.tilemap(&state.map, &state.textures)
+        .time(state.time)
+        .translate((state.time * 0.5).sin() * 60.0, 0.0);
+
+    // Objects can be used to place other elements
+    if let Some(obj) = state.map.objects().find(|o| o.name == "player") {
+        draw.circle(10.0)
+            .position(obj.x, obj.y)
+            .color(Color::ORANGE);
+    }
+
+    gfx.render(&draw);
+
+    state.time += app.timer.delta_f32();
+}
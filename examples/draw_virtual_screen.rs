@@ -0,0 +1,62 @@
+use notan::draw::*;
+use notan::prelude::*;
+
+// Low resolution used to draw, it will be scaled to fit the window
+const WIDTH: u32 = 160;
+const HEIGHT: u32 = 90;
+
+#[notan_main]
+fn main() -> Result<(), String> {
+    let win_config = WindowConfig::default().set_resizable(true);
+
+    let virtual_screen = VirtualScreen::new(WIDTH, HEIGHT)
+        .with_scale_mode(ScaleMode::Integer)
+        .with_filter(TextureFilter::Nearest)
+        .with_bars_color(Color::from_rgb(0.1, 0.1, 0.1));
+
+    notan::init()
+        .add_config(win_config)
+        .add_config(DrawConfig)
+        .add_plugin(virtual_screen)
+        .update(update)
+        .draw(draw)
+        .build()
+}
+
+fn update(app: &mut App, plugins: &mut Plugins) {
+    if !app.keyboard.was_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let mut vs = plugins.get_mut::<VirtualScreen>().unwrap();
+    let mode = match vs.scale_mode() {
+        ScaleMode::Integer => ScaleMode::Fit,
+        ScaleMode::Fit => ScaleMode::Fill,
+        ScaleMode::Fill => ScaleMode::Stretch,
+        _ => ScaleMode::Integer,
+    };
+    notan::log::info!("Scale mode: {:?}", mode);
+    vs.set_scale_mode(mode);
+}
+
+fn draw(app: &mut App, gfx: &mut Graphics, plugins: &mut Plugins) {
+    let vs = plugins.get_mut::<VirtualScreen>().unwrap();
+
+    let mut draw = vs.create_draw();
+    draw.clear(Color::from_rgb(0.2, 0.3, 0.4));
+
+    draw.rect((1.0, 1.0), (WIDTH as f32 - 2.0, HEIGHT as f32 - 2.0))
+        .stroke(1.0)
+        .color(Color::WHITE);
+
+    draw.triangle((80.0, 20.0), (50.0, 70.0), (110.0, 70.0))
+        .color(Color::ORANGE);
+
+    // The mouse position is already mapped to the virtual resolution
+    let (x, y) = app.mouse.position();
+    draw.rect((x.floor() - 1.0, y.floor() - 1.0), (3.0, 3.0))
+        .color(Color::RED);
+
+    // Render to the virtual screen, it will be presented at the end of the frame
+    vs.render(gfx, &draw);
+}